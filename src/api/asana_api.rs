@@ -0,0 +1,624 @@
+//! Object-safe interface over the resource operations in this module.
+//!
+//! Services that talk to Asana can depend on `&dyn AsanaApi` (or a generic
+//! `A: AsanaApi`) instead of [`ApiClient`], and swap in
+//! `api::fake::FakeAsana` from the `fake` feature in their
+//! tests. Streaming listings, file transfers, and raw requests stay on
+//! [`ApiClient`] and the free functions, since they depend on the HTTP
+//! transport itself.
+
+use crate::{
+    api::{
+        ApiClient, ApiError, EventBatch, TaskUpsert, attachments, custom_fields, events, projects,
+        sections, stories, tags, tasks, users, webhooks, workspaces,
+    },
+    models::{
+        Attachment, AttachmentListParams, CustomField, MemberPermission, Project,
+        ProjectCreateRequest, ProjectListParams, ProjectMember, ProjectMembers, ProjectStatus,
+        ProjectUpdateRequest, Section, SectionCreateRequest, Story, StoryCreateRequest,
+        StoryListParams, StoryUpdateRequest, Tag, TagCreateRequest, TagListParams,
+        TagUpdateRequest, Task, TaskCreateRequest, TaskListParams, TaskReference, TaskSearchParams,
+        TaskUpdateData, TaskUpdateRequest, User, UserListParams, Webhook, WebhookCreateRequest,
+        WebhookListParams, Workspace, WorkspaceListParams,
+    },
+};
+use futures_util::future::BoxFuture;
+
+/// Result of a future returned by [`AsanaApi`].
+type ApiFuture<'a, T> = BoxFuture<'a, Result<T, ApiError>>;
+
+/// Resource operations against Asana, implemented by [`ApiClient`].
+///
+/// Each method mirrors the free function of the same name in the resource
+/// modules (`api::tasks::get_task` and so on) and has the same error
+/// behaviour.
+pub trait AsanaApi: Send + Sync {
+    /// List attachments on a task.
+    fn list_attachments(&self, params: AttachmentListParams) -> ApiFuture<'_, Vec<Attachment>>;
+    /// Retrieve attachment metadata.
+    fn get_attachment<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Attachment>;
+    /// Delete an attachment.
+    fn delete_attachment<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()>;
+
+    /// List custom fields defined in a workspace.
+    fn list_custom_fields<'a>(
+        &'a self,
+        workspace_gid: &'a str,
+        limit: Option<usize>,
+    ) -> ApiFuture<'a, Vec<CustomField>>;
+    /// Retrieve a custom field definition.
+    fn get_custom_field<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, CustomField>;
+
+    /// Fetch events for `resource` since the `sync` token.
+    fn get_events<'a>(
+        &'a self,
+        resource: &'a str,
+        sync: Option<&'a str>,
+    ) -> ApiFuture<'a, EventBatch>;
+
+    /// List projects.
+    fn list_projects(&self, params: ProjectListParams) -> ApiFuture<'_, Vec<Project>>;
+    /// Retrieve a project.
+    fn get_project<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Project>;
+    /// Create a project.
+    fn create_project(&self, request: ProjectCreateRequest) -> ApiFuture<'_, Project>;
+    /// Update a project.
+    fn update_project<'a>(
+        &'a self,
+        gid: &'a str,
+        request: ProjectUpdateRequest,
+    ) -> ApiFuture<'a, Project>;
+    /// Delete a project.
+    fn delete_project<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()>;
+    /// List project members.
+    fn list_members<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ProjectMembers>;
+    /// List status updates on a project.
+    fn list_statuses<'a>(
+        &'a self,
+        gid: &'a str,
+        limit: Option<usize>,
+    ) -> ApiFuture<'a, Vec<ProjectStatus>>;
+    /// Add users to a project, optionally with a role.
+    fn add_members<'a>(
+        &'a self,
+        gid: &'a str,
+        members: Vec<String>,
+        role: Option<MemberPermission>,
+    ) -> ApiFuture<'a, ()>;
+    /// Remove users from a project.
+    fn remove_members<'a>(&'a self, gid: &'a str, members: Vec<String>) -> ApiFuture<'a, ()>;
+    /// Change the role of a project membership.
+    fn update_member<'a>(
+        &'a self,
+        membership_gid: &'a str,
+        role: MemberPermission,
+    ) -> ApiFuture<'a, ProjectMember>;
+
+    /// List sections in a project.
+    fn list_sections<'a>(&'a self, project_gid: &'a str) -> ApiFuture<'a, Vec<Section>>;
+    /// Retrieve a section.
+    fn get_section<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Section>;
+    /// Create a section in a project.
+    fn create_section<'a>(
+        &'a self,
+        project_gid: &'a str,
+        request: SectionCreateRequest,
+    ) -> ApiFuture<'a, Section>;
+    /// List tasks in a section.
+    fn get_section_tasks<'a>(
+        &'a self,
+        gid: &'a str,
+        fields: Vec<String>,
+    ) -> ApiFuture<'a, Vec<Task>>;
+    /// Move a task into a section.
+    fn add_task_to_section<'a>(
+        &'a self,
+        section_gid: &'a str,
+        task_gid: String,
+        insert_before: Option<String>,
+        insert_after: Option<String>,
+    ) -> ApiFuture<'a, ()>;
+
+    /// List stories on a task.
+    fn list_stories(&self, params: StoryListParams) -> ApiFuture<'_, Vec<Story>>;
+    /// Retrieve a story.
+    fn get_story<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Story>;
+    /// Comment on a task.
+    fn create_story<'a>(
+        &'a self,
+        task_gid: &'a str,
+        request: StoryCreateRequest,
+    ) -> ApiFuture<'a, Story>;
+    /// Update a story.
+    fn update_story<'a>(
+        &'a self,
+        gid: &'a str,
+        request: StoryUpdateRequest,
+    ) -> ApiFuture<'a, Story>;
+    /// Delete a story.
+    fn delete_story<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()>;
+
+    /// List tags in a workspace.
+    fn list_tags(&self, params: TagListParams) -> ApiFuture<'_, Vec<Tag>>;
+    /// Retrieve a tag.
+    fn get_tag<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Tag>;
+    /// Create a tag.
+    fn create_tag(&self, request: TagCreateRequest) -> ApiFuture<'_, Tag>;
+    /// Update a tag.
+    fn update_tag<'a>(&'a self, gid: &'a str, request: TagUpdateRequest) -> ApiFuture<'a, Tag>;
+    /// Delete a tag.
+    fn delete_tag<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()>;
+
+    /// List tasks.
+    fn list_tasks(&self, params: TaskListParams) -> ApiFuture<'_, Vec<Task>>;
+    /// Search tasks in a workspace.
+    fn search_tasks(&self, params: TaskSearchParams) -> ApiFuture<'_, Vec<Task>>;
+    /// Retrieve a task.
+    fn get_task<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Task>;
+    /// Create a task.
+    fn create_task(&self, request: TaskCreateRequest) -> ApiFuture<'_, Task>;
+    /// Look a task up by its external id.
+    fn find_task_by_external_id<'a>(&'a self, external_id: &'a str) -> ApiFuture<'a, Option<Task>>;
+    /// Update a task.
+    fn update_task<'a>(&'a self, gid: &'a str, request: TaskUpdateRequest) -> ApiFuture<'a, Task>;
+    /// Delete a task.
+    fn delete_task<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()>;
+    /// List subtasks of a task.
+    fn list_subtasks<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Vec<Task>>;
+    /// List tasks that `gid` depends on.
+    fn list_dependencies<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Vec<TaskReference>>;
+    /// List tasks that depend on `gid`.
+    fn list_dependents<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Vec<TaskReference>>;
+    /// Mark `gid` as depending on other tasks.
+    fn add_dependencies<'a>(&'a self, gid: &'a str, dependencies: Vec<String>)
+    -> ApiFuture<'a, ()>;
+    /// Remove dependencies from `gid`.
+    fn remove_dependencies<'a>(
+        &'a self,
+        gid: &'a str,
+        dependencies: Vec<String>,
+    ) -> ApiFuture<'a, ()>;
+    /// Mark other tasks as depending on `gid`.
+    fn add_dependents<'a>(&'a self, gid: &'a str, dependents: Vec<String>) -> ApiFuture<'a, ()>;
+    /// Remove dependents from `gid`.
+    fn remove_dependents<'a>(&'a self, gid: &'a str, dependents: Vec<String>) -> ApiFuture<'a, ()>;
+    /// Add a task to a project, optionally in a section.
+    fn add_project<'a>(
+        &'a self,
+        gid: &'a str,
+        project: String,
+        section: Option<String>,
+    ) -> ApiFuture<'a, ()>;
+    /// Remove a task from a project.
+    fn remove_project<'a>(&'a self, gid: &'a str, project: String) -> ApiFuture<'a, ()>;
+    /// Add followers to a task.
+    fn add_followers<'a>(&'a self, gid: &'a str, followers: Vec<String>) -> ApiFuture<'a, ()>;
+    /// Remove followers from a task.
+    fn remove_followers<'a>(&'a self, gid: &'a str, followers: Vec<String>) -> ApiFuture<'a, ()>;
+    /// Tag a task.
+    fn add_tag<'a>(&'a self, gid: &'a str, tag: String) -> ApiFuture<'a, ()>;
+    /// Remove a tag from a task.
+    fn remove_tag<'a>(&'a self, gid: &'a str, tag: String) -> ApiFuture<'a, ()>;
+
+    /// Create a task, or update the one already carrying the payload's
+    /// external id.
+    ///
+    /// The default implementation looks the external id up and then creates
+    /// or updates the task.
+    fn create_or_update_task(
+        &self,
+        request: TaskCreateRequest,
+    ) -> ApiFuture<'_, (Task, TaskUpsert)> {
+        Box::pin(async move {
+            let existing = match &request.data.external {
+                Some(external) => self.find_task_by_external_id(&external.gid).await?,
+                None => None,
+            };
+            match existing {
+                Some(task) => {
+                    let update = TaskUpdateRequest {
+                        data: TaskUpdateData::from(request.data),
+                    };
+                    let task = self.update_task(&task.gid, update).await?;
+                    Ok((task, TaskUpsert::Updated))
+                }
+                None => Ok((self.create_task(request).await?, TaskUpsert::Created)),
+            }
+        })
+    }
+
+    /// Create or update several tasks, returning one result per request in
+    /// input order.
+    ///
+    /// The default implementation calls
+    /// [`create_or_update_task`](Self::create_or_update_task) for each
+    /// request in turn.
+    fn create_tasks_batch(
+        &self,
+        requests: Vec<TaskCreateRequest>,
+    ) -> ApiFuture<'_, Vec<Result<Task, ApiError>>> {
+        Box::pin(async move {
+            let mut results = Vec::with_capacity(requests.len());
+            for request in requests {
+                results.push(
+                    self.create_or_update_task(request)
+                        .await
+                        .map(|(task, _)| task),
+                );
+            }
+            Ok(results)
+        })
+    }
+
+    /// Update several tasks, returning one result per `(gid, request)` pair
+    /// in input order.
+    ///
+    /// The default implementation calls [`update_task`](Self::update_task)
+    /// for each pair in turn.
+    fn update_tasks_batch(
+        &self,
+        updates: Vec<(String, TaskUpdateRequest)>,
+    ) -> ApiFuture<'_, Vec<Result<Task, ApiError>>> {
+        Box::pin(async move {
+            let mut results = Vec::with_capacity(updates.len());
+            for (gid, request) in updates {
+                results.push(self.update_task(&gid, request).await);
+            }
+            Ok(results)
+        })
+    }
+
+    /// List users in a workspace.
+    fn list_users(&self, params: UserListParams) -> ApiFuture<'_, Vec<User>>;
+    /// Retrieve a user.
+    fn get_user<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, User>;
+    /// Retrieve the authenticated user.
+    fn get_current_user(&self) -> ApiFuture<'_, User>;
+
+    /// List webhooks in a workspace.
+    fn list_webhooks(&self, params: WebhookListParams) -> ApiFuture<'_, Vec<Webhook>>;
+    /// Retrieve a webhook.
+    fn get_webhook<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Webhook>;
+    /// Create a webhook.
+    fn create_webhook(&self, request: WebhookCreateRequest) -> ApiFuture<'_, Webhook>;
+    /// Delete a webhook.
+    fn delete_webhook<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()>;
+
+    /// List workspaces visible to the authenticated user.
+    fn list_workspaces(&self, params: WorkspaceListParams) -> ApiFuture<'_, Vec<Workspace>>;
+    /// Retrieve a workspace.
+    fn get_workspace<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Workspace>;
+}
+
+impl AsanaApi for ApiClient {
+    fn list_attachments(&self, params: AttachmentListParams) -> ApiFuture<'_, Vec<Attachment>> {
+        Box::pin(attachments::list_attachments(self, params))
+    }
+
+    fn get_attachment<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Attachment> {
+        Box::pin(attachments::get_attachment(self, gid))
+    }
+
+    fn delete_attachment<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()> {
+        Box::pin(attachments::delete_attachment(self, gid))
+    }
+
+    fn list_custom_fields<'a>(
+        &'a self,
+        workspace_gid: &'a str,
+        limit: Option<usize>,
+    ) -> ApiFuture<'a, Vec<CustomField>> {
+        Box::pin(custom_fields::list_custom_fields(
+            self,
+            workspace_gid,
+            limit,
+        ))
+    }
+
+    fn get_custom_field<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, CustomField> {
+        Box::pin(custom_fields::get_custom_field(self, gid))
+    }
+
+    fn get_events<'a>(
+        &'a self,
+        resource: &'a str,
+        sync: Option<&'a str>,
+    ) -> ApiFuture<'a, EventBatch> {
+        Box::pin(events::get_events(self, resource, sync))
+    }
+
+    fn list_projects(&self, params: ProjectListParams) -> ApiFuture<'_, Vec<Project>> {
+        Box::pin(projects::list_projects(self, params))
+    }
+
+    fn get_project<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Project> {
+        Box::pin(projects::get_project(self, gid, fields))
+    }
+
+    fn create_project(&self, request: ProjectCreateRequest) -> ApiFuture<'_, Project> {
+        Box::pin(projects::create_project(self, request))
+    }
+
+    fn update_project<'a>(
+        &'a self,
+        gid: &'a str,
+        request: ProjectUpdateRequest,
+    ) -> ApiFuture<'a, Project> {
+        Box::pin(projects::update_project(self, gid, request))
+    }
+
+    fn delete_project<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()> {
+        Box::pin(projects::delete_project(self, gid))
+    }
+
+    fn list_members<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ProjectMembers> {
+        Box::pin(projects::list_members(self, gid))
+    }
+
+    fn list_statuses<'a>(
+        &'a self,
+        gid: &'a str,
+        limit: Option<usize>,
+    ) -> ApiFuture<'a, Vec<ProjectStatus>> {
+        Box::pin(projects::list_statuses(self, gid, limit))
+    }
+
+    fn add_members<'a>(
+        &'a self,
+        gid: &'a str,
+        members: Vec<String>,
+        role: Option<MemberPermission>,
+    ) -> ApiFuture<'a, ()> {
+        Box::pin(projects::add_members(self, gid, members, role))
+    }
+
+    fn remove_members<'a>(&'a self, gid: &'a str, members: Vec<String>) -> ApiFuture<'a, ()> {
+        Box::pin(projects::remove_members(self, gid, members))
+    }
+
+    fn update_member<'a>(
+        &'a self,
+        membership_gid: &'a str,
+        role: MemberPermission,
+    ) -> ApiFuture<'a, ProjectMember> {
+        Box::pin(projects::update_member(self, membership_gid, role))
+    }
+
+    fn list_sections<'a>(&'a self, project_gid: &'a str) -> ApiFuture<'a, Vec<Section>> {
+        Box::pin(sections::list_sections(self, project_gid))
+    }
+
+    fn get_section<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Section> {
+        Box::pin(sections::get_section(self, gid, fields))
+    }
+
+    fn create_section<'a>(
+        &'a self,
+        project_gid: &'a str,
+        request: SectionCreateRequest,
+    ) -> ApiFuture<'a, Section> {
+        Box::pin(sections::create_section(self, project_gid, request))
+    }
+
+    fn get_section_tasks<'a>(
+        &'a self,
+        gid: &'a str,
+        fields: Vec<String>,
+    ) -> ApiFuture<'a, Vec<Task>> {
+        Box::pin(sections::get_section_tasks(self, gid, fields))
+    }
+
+    fn add_task_to_section<'a>(
+        &'a self,
+        section_gid: &'a str,
+        task_gid: String,
+        insert_before: Option<String>,
+        insert_after: Option<String>,
+    ) -> ApiFuture<'a, ()> {
+        Box::pin(sections::add_task_to_section(
+            self,
+            section_gid,
+            task_gid,
+            insert_before,
+            insert_after,
+        ))
+    }
+
+    fn list_stories(&self, params: StoryListParams) -> ApiFuture<'_, Vec<Story>> {
+        Box::pin(stories::list_stories(self, params))
+    }
+
+    fn get_story<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Story> {
+        Box::pin(stories::get_story(self, gid))
+    }
+
+    fn create_story<'a>(
+        &'a self,
+        task_gid: &'a str,
+        request: StoryCreateRequest,
+    ) -> ApiFuture<'a, Story> {
+        Box::pin(stories::create_story(self, task_gid, request))
+    }
+
+    fn update_story<'a>(
+        &'a self,
+        gid: &'a str,
+        request: StoryUpdateRequest,
+    ) -> ApiFuture<'a, Story> {
+        Box::pin(stories::update_story(self, gid, request))
+    }
+
+    fn delete_story<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()> {
+        Box::pin(stories::delete_story(self, gid))
+    }
+
+    fn list_tags(&self, params: TagListParams) -> ApiFuture<'_, Vec<Tag>> {
+        Box::pin(tags::list_tags(self, params))
+    }
+
+    fn get_tag<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Tag> {
+        Box::pin(tags::get_tag(self, gid))
+    }
+
+    fn create_tag(&self, request: TagCreateRequest) -> ApiFuture<'_, Tag> {
+        Box::pin(tags::create_tag(self, request))
+    }
+
+    fn update_tag<'a>(&'a self, gid: &'a str, request: TagUpdateRequest) -> ApiFuture<'a, Tag> {
+        Box::pin(tags::update_tag(self, gid, request))
+    }
+
+    fn delete_tag<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()> {
+        Box::pin(tags::delete_tag(self, gid))
+    }
+
+    fn list_tasks(&self, params: TaskListParams) -> ApiFuture<'_, Vec<Task>> {
+        Box::pin(tasks::list_tasks(self, params))
+    }
+
+    fn search_tasks(&self, params: TaskSearchParams) -> ApiFuture<'_, Vec<Task>> {
+        Box::pin(tasks::search_tasks(self, params))
+    }
+
+    fn get_task<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Task> {
+        Box::pin(tasks::get_task(self, gid, fields))
+    }
+
+    fn create_task(&self, request: TaskCreateRequest) -> ApiFuture<'_, Task> {
+        Box::pin(tasks::create_task(self, request))
+    }
+
+    fn find_task_by_external_id<'a>(&'a self, external_id: &'a str) -> ApiFuture<'a, Option<Task>> {
+        Box::pin(tasks::find_task_by_external_id(self, external_id))
+    }
+
+    fn update_task<'a>(&'a self, gid: &'a str, request: TaskUpdateRequest) -> ApiFuture<'a, Task> {
+        Box::pin(tasks::update_task(self, gid, request))
+    }
+
+    fn delete_task<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::delete_task(self, gid))
+    }
+
+    fn list_subtasks<'a>(&'a self, gid: &'a str, fields: Vec<String>) -> ApiFuture<'a, Vec<Task>> {
+        Box::pin(tasks::list_subtasks(self, gid, fields))
+    }
+
+    fn list_dependencies<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Vec<TaskReference>> {
+        Box::pin(tasks::list_dependencies(self, gid))
+    }
+
+    fn list_dependents<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Vec<TaskReference>> {
+        Box::pin(tasks::list_dependents(self, gid))
+    }
+
+    fn add_dependencies<'a>(
+        &'a self,
+        gid: &'a str,
+        dependencies: Vec<String>,
+    ) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::add_dependencies(self, gid, dependencies))
+    }
+
+    fn remove_dependencies<'a>(
+        &'a self,
+        gid: &'a str,
+        dependencies: Vec<String>,
+    ) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::remove_dependencies(self, gid, dependencies))
+    }
+
+    fn add_dependents<'a>(&'a self, gid: &'a str, dependents: Vec<String>) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::add_dependents(self, gid, dependents))
+    }
+
+    fn remove_dependents<'a>(&'a self, gid: &'a str, dependents: Vec<String>) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::remove_dependents(self, gid, dependents))
+    }
+
+    fn add_project<'a>(
+        &'a self,
+        gid: &'a str,
+        project: String,
+        section: Option<String>,
+    ) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::add_project(self, gid, project, section))
+    }
+
+    fn remove_project<'a>(&'a self, gid: &'a str, project: String) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::remove_project(self, gid, project))
+    }
+
+    fn add_followers<'a>(&'a self, gid: &'a str, followers: Vec<String>) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::add_followers(self, gid, followers))
+    }
+
+    fn remove_followers<'a>(&'a self, gid: &'a str, followers: Vec<String>) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::remove_followers(self, gid, followers))
+    }
+
+    fn add_tag<'a>(&'a self, gid: &'a str, tag: String) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::add_tag(self, gid, tag))
+    }
+
+    fn remove_tag<'a>(&'a self, gid: &'a str, tag: String) -> ApiFuture<'a, ()> {
+        Box::pin(tasks::remove_tag(self, gid, tag))
+    }
+
+    fn create_or_update_task(
+        &self,
+        request: TaskCreateRequest,
+    ) -> ApiFuture<'_, (Task, TaskUpsert)> {
+        Box::pin(tasks::create_or_update_task(self, request))
+    }
+
+    fn create_tasks_batch(
+        &self,
+        requests: Vec<TaskCreateRequest>,
+    ) -> ApiFuture<'_, Vec<Result<Task, ApiError>>> {
+        Box::pin(tasks::create_tasks_batch(self, requests))
+    }
+
+    fn update_tasks_batch(
+        &self,
+        updates: Vec<(String, TaskUpdateRequest)>,
+    ) -> ApiFuture<'_, Vec<Result<Task, ApiError>>> {
+        Box::pin(tasks::update_tasks_batch(self, updates))
+    }
+
+    fn list_users(&self, params: UserListParams) -> ApiFuture<'_, Vec<User>> {
+        Box::pin(users::list_users(self, params))
+    }
+
+    fn get_user<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, User> {
+        Box::pin(users::get_user(self, gid))
+    }
+
+    fn get_current_user(&self) -> ApiFuture<'_, User> {
+        Box::pin(users::get_current_user(self))
+    }
+
+    fn list_webhooks(&self, params: WebhookListParams) -> ApiFuture<'_, Vec<Webhook>> {
+        Box::pin(webhooks::list_webhooks(self, params))
+    }
+
+    fn get_webhook<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Webhook> {
+        Box::pin(webhooks::get_webhook(self, gid))
+    }
+
+    fn create_webhook(&self, request: WebhookCreateRequest) -> ApiFuture<'_, Webhook> {
+        Box::pin(webhooks::create_webhook(self, request))
+    }
+
+    fn delete_webhook<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, ()> {
+        Box::pin(webhooks::delete_webhook(self, gid))
+    }
+
+    fn list_workspaces(&self, params: WorkspaceListParams) -> ApiFuture<'_, Vec<Workspace>> {
+        Box::pin(workspaces::list_workspaces(self, params))
+    }
+
+    fn get_workspace<'a>(&'a self, gid: &'a str) -> ApiFuture<'a, Workspace> {
+        Box::pin(workspaces::get_workspace(self, gid))
+    }
+}
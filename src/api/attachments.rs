@@ -1,13 +1,13 @@
 //! High level attachment operations built on the core API client.
 
 use crate::{
-    api::{ApiClient, ApiError},
+    api::{ApiClient, ApiError, TransferProgress},
     models::{Attachment, AttachmentListParams, AttachmentUploadParams},
 };
 use futures_util::{StreamExt, pin_mut};
 use reqwest::multipart::{Form, Part};
 use serde::Deserialize;
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
@@ -20,7 +20,8 @@ pub async fn list_attachments(
     params: AttachmentListParams,
 ) -> Result<Vec<Attachment>, ApiError> {
     let endpoint = format!("/tasks/{}/attachments", params.task_gid);
-    let stream = client.paginate_with_limit::<Attachment>(&endpoint, vec![], params.limit);
+    let stream =
+        client.paginate_with_limit::<Attachment>(&endpoint, params.to_query(), params.limit);
     pin_mut!(stream);
 
     let mut attachments = Vec::new();
@@ -51,10 +52,25 @@ pub async fn upload_attachment(
     client: &ApiClient,
     params: AttachmentUploadParams,
 ) -> Result<Attachment, ApiError> {
-    // Read file
-    let file = File::open(&params.file_path)
+    upload_attachment_with_progress(client, params, None).await
+}
+
+/// Upload an attachment to a task, reporting progress as the file is sent.
+///
+/// The file is streamed from disk rather than read into memory, and is
+/// reopened if the upload has to be retried.
+///
+/// # Errors
+/// Returns [`ApiError`] if the file cannot be read, the upload fails, or network errors occur.
+pub async fn upload_attachment_with_progress(
+    client: &ApiClient,
+    params: AttachmentUploadParams,
+    progress: Option<Arc<dyn TransferProgress>>,
+) -> Result<Attachment, ApiError> {
+    let size = tokio::fs::metadata(&params.file_path)
         .await
-        .map_err(|e| ApiError::Other(format!("failed to open file: {e}")))?;
+        .map_err(|e| ApiError::Other(format!("failed to open file: {e}")))?
+        .len();
 
     // Get filename
     let filename = params.name.unwrap_or_else(|| {
@@ -66,16 +82,30 @@ pub async fn upload_attachment(
             .to_string()
     });
 
-    // Create multipart form
-    let stream = FramedRead::new(file, BytesCodec::new());
-    let file_body = reqwest::Body::wrap_stream(stream);
-    let file_part = Part::stream(file_body).file_name(filename);
+    let make_form = || {
+        let file = std::fs::File::open(&params.file_path)
+            .map_err(|e| ApiError::Other(format!("failed to open file: {e}")))?;
+        if let Some(progress) = &progress {
+            progress.start(0, Some(size));
+        }
+        let observer = progress.clone();
+        let stream =
+            FramedRead::new(File::from_std(file), BytesCodec::new()).inspect(move |chunk| {
+                if let (Some(progress), Ok(chunk)) = (&observer, chunk) {
+                    progress.advance(chunk.len() as u64);
+                }
+            });
+        let file_part = Part::stream_with_length(reqwest::Body::wrap_stream(stream), size)
+            .file_name(filename.clone());
+        Ok(Form::new().part("file", file_part))
+    };
 
-    let form = Form::new().part("file", file_part);
-
-    // POST multipart form
     let endpoint = format!("/tasks/{}/attachments", params.task_gid);
-    let response: SingleAttachmentResponse = client.post_multipart(&endpoint, form).await?;
+    let response: SingleAttachmentResponse =
+        client.post_multipart_with(&endpoint, make_form).await?;
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
 
     Ok(response.data)
 }
@@ -93,11 +123,29 @@ pub async fn delete_attachment(client: &ApiClient, gid: &str) -> Result<(), ApiE
 /// Download an attachment to a local file.
 ///
 /// # Errors
-/// Returns [`ApiError`] if the attachment cannot be fetched, downloaded, or written to disk.
+/// Returns [`ApiError`] if the attachment cannot be fetched, downloaded, or written to disk,
+/// or if the downloaded size does not match the attachment.
 pub async fn download_attachment(
     client: &ApiClient,
     gid: &str,
     output_path: &Path,
+) -> Result<(), ApiError> {
+    download_attachment_with_progress(client, gid, output_path, None).await
+}
+
+/// Download an attachment to a local file, reporting progress as it arrives.
+///
+/// The body is streamed to `<output_path>.part`, resumed from there if a
+/// previous attempt was interrupted, and checked against the attachment size.
+///
+/// # Errors
+/// Returns [`ApiError`] if the attachment cannot be fetched, downloaded, or written to disk,
+/// or if the downloaded size does not match the attachment.
+pub async fn download_attachment_with_progress(
+    client: &ApiClient,
+    gid: &str,
+    output_path: &Path,
+    progress: Option<&dyn TransferProgress>,
 ) -> Result<(), ApiError> {
     // Get attachment metadata to get download URL
     let attachment = get_attachment(client, gid).await?;
@@ -106,13 +154,9 @@ pub async fn download_attachment(
         .download_url
         .ok_or_else(|| ApiError::Other("attachment has no download URL".into()))?;
 
-    // Download file content
-    let bytes = client.download_file(&download_url).await?;
-
-    // Write to disk
-    tokio::fs::write(output_path, bytes)
-        .await
-        .map_err(|e| ApiError::Other(format!("failed to write file: {e}")))?;
+    client
+        .download_file_to(&download_url, output_path, attachment.size, progress)
+        .await?;
 
     Ok(())
 }
@@ -1,7 +1,10 @@
 //! Authentication helpers for the Asana API client.
 
+use crate::api::ApiError;
+use futures_util::future::BoxFuture;
 use secrecy::{ExposeSecret, SecretString};
 use std::fmt;
+use tokio::{process::Command, sync::Mutex};
 
 /// Wrapper around a Personal Access Token (PAT) ensuring secret handling.
 #[derive(Clone, Debug)]
@@ -35,11 +38,40 @@ impl fmt::Display for AuthToken {
 
 /// Trait for providing access tokens; enables testing without touching the
 /// concrete configuration layer.
+///
+/// Every `TokenProvider` is also an [`AccessTokenProvider`] that never
+/// refreshes, so it can be handed to
+/// [`ApiClient::with_token_provider`](crate::api::ApiClient::with_token_provider).
 pub trait TokenProvider: Send + Sync {
     /// Obtain a fresh Personal Access Token.
     fn personal_access_token(&self) -> SecretString;
 }
 
+/// Source of the bearer token sent with every request.
+///
+/// [`ApiClient`](crate::api::ApiClient) asks the provider for a token before
+/// each request. When Asana answers `401 Unauthorized`, the client calls
+/// [`AccessTokenProvider::refresh`] once and retries if a new token is
+/// available.
+pub trait AccessTokenProvider: Send + Sync {
+    /// Token to attach to the next request.
+    fn access_token(&self) -> BoxFuture<'_, Result<AuthToken, ApiError>>;
+
+    /// Replace a token Asana rejected, returning whether a different token is
+    /// now available. Providers without a refresh mechanism return `false`.
+    fn refresh<'a>(&'a self, rejected: &'a AuthToken) -> BoxFuture<'a, Result<bool, ApiError>> {
+        let _ = rejected;
+        Box::pin(async { Ok(false) })
+    }
+}
+
+impl<T: TokenProvider + ?Sized> AccessTokenProvider for T {
+    fn access_token(&self) -> BoxFuture<'_, Result<AuthToken, ApiError>> {
+        let token = AuthToken::new(self.personal_access_token());
+        Box::pin(async move { Ok(token) })
+    }
+}
+
 /// Simple token provider that always returns the same token.
 #[derive(Clone, Debug)]
 pub struct StaticTokenProvider {
@@ -65,3 +97,146 @@ impl From<AuthToken> for StaticTokenProvider {
         Self::new(token)
     }
 }
+
+/// Token provider that runs an external credential helper, in the style of
+/// git's `credential.helper`.
+///
+/// The command runs through the platform shell the first time a token is
+/// needed and its trimmed stdout becomes the token. The result is cached for
+/// the lifetime of the provider; a `401` re-runs the command once in case the
+/// secret was rotated.
+pub struct CommandTokenProvider {
+    command: String,
+    cached: Mutex<Option<AuthToken>>,
+}
+
+impl CommandTokenProvider {
+    /// Create a provider for the given shell command.
+    #[must_use]
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn run(&self) -> Result<AuthToken, ApiError> {
+        let output = shell_command(&self.command)
+            .stdin(std::process::Stdio::null())
+            .stderr(std::process::Stdio::inherit())
+            .output()
+            .await
+            .map_err(|err| {
+                ApiError::Authentication(format!(
+                    "failed to run token_command `{}`: {err}",
+                    self.command
+                ))
+            })?;
+        if !output.status.success() {
+            return Err(ApiError::Authentication(format!(
+                "token_command `{}` exited with {}",
+                self.command, output.status
+            )));
+        }
+        let stdout = String::from_utf8(output.stdout).map_err(|_| {
+            ApiError::Authentication(format!(
+                "token_command `{}` printed a non-UTF-8 token",
+                self.command
+            ))
+        })?;
+        let token = stdout.trim();
+        if token.is_empty() {
+            return Err(ApiError::Authentication(format!(
+                "token_command `{}` printed no token",
+                self.command
+            )));
+        }
+        Ok(AuthToken::new(SecretString::new(token.into())))
+    }
+}
+
+impl fmt::Debug for CommandTokenProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandTokenProvider")
+            .field("command", &self.command)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AccessTokenProvider for CommandTokenProvider {
+    fn access_token(&self) -> BoxFuture<'_, Result<AuthToken, ApiError>> {
+        Box::pin(async move {
+            let mut cached = self.cached.lock().await;
+            if let Some(token) = cached.as_ref() {
+                return Ok(token.clone());
+            }
+            let token = self.run().await?;
+            *cached = Some(token.clone());
+            Ok(token)
+        })
+    }
+
+    fn refresh<'a>(&'a self, rejected: &'a AuthToken) -> BoxFuture<'a, Result<bool, ApiError>> {
+        Box::pin(async move {
+            let mut cached = self.cached.lock().await;
+            if let Some(current) = cached.as_ref() {
+                if current.expose() != rejected.expose() {
+                    // Another request already fetched a newer token.
+                    return Ok(true);
+                }
+            }
+            let token = self.run().await?;
+            let changed = token.expose() != rejected.expose();
+            *cached = Some(token);
+            Ok(changed)
+        })
+    }
+}
+
+/// Build a command running `command` through the platform shell.
+pub(crate) fn shell_command(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_provider_caches_helper_output() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let counter = dir.path().join("runs");
+        let provider = CommandTokenProvider::new(format!(
+            "echo run >> '{}'; echo '  helper-token  '",
+            counter.display()
+        ));
+
+        let first = provider.access_token().await.expect("token");
+        let second = provider.access_token().await.expect("token");
+        assert_eq!(first.expose(), "helper-token");
+        assert_eq!(second.expose(), "helper-token");
+        let runs = std::fs::read_to_string(&counter).expect("counter file");
+        assert_eq!(runs.lines().count(), 1);
+
+        // The helper still returns the rejected token, so there is nothing new to retry with.
+        assert!(!provider.refresh(&first).await.expect("refresh"));
+    }
+
+    #[tokio::test]
+    async fn command_provider_reports_failures() {
+        let provider = CommandTokenProvider::new("exit 3");
+        let err = provider.access_token().await.expect_err("helper fails");
+        assert!(matches!(err, ApiError::Authentication(message) if message.contains("exit")));
+    }
+}
@@ -0,0 +1,118 @@
+//! Support for Asana's `/batch` endpoint, bundling several actions into one request.
+
+use crate::api::error::ApiError;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Maximum number of actions Asana accepts in a single `/batch` request.
+pub const MAX_BATCH_ACTIONS: usize = 10;
+
+/// A single action submitted through the batch API.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct BatchAction {
+    /// Lower-case HTTP method (`get`, `post`, `put`, `delete`).
+    pub method: String,
+    /// Path relative to the API base URL, e.g. `/tasks/123`.
+    pub relative_path: String,
+    /// Request payload (the contents of the usual `data` envelope).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    /// Optional request options such as `fields` or `limit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
+}
+
+impl BatchAction {
+    /// Construct an action for the given method and relative path.
+    #[must_use]
+    pub fn new(method: &Method, relative_path: impl Into<String>) -> Self {
+        Self {
+            method: method.as_str().to_ascii_lowercase(),
+            relative_path: relative_path.into(),
+            data: None,
+            options: None,
+        }
+    }
+
+    /// Attach the payload sent with the action.
+    #[must_use]
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Attach request options (for example `{ "fields": [...] }`).
+    #[must_use]
+    pub fn with_options(mut self, options: Value) -> Self {
+        self.options = Some(options);
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BatchRequest<'a> {
+    pub(crate) data: BatchRequestData<'a>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BatchRequestData<'a> {
+    pub(crate) actions: &'a [BatchAction],
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BatchResponse {
+    pub(crate) data: Vec<BatchActionResponse>,
+}
+
+/// Raw result of one action as reported by the batch endpoint.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BatchActionResponse {
+    status_code: u16,
+    #[serde(default)]
+    headers: Option<Value>,
+    #[serde(default)]
+    body: Value,
+}
+
+impl BatchActionResponse {
+    /// Convert the action response into its body or the matching [`ApiError`].
+    pub(crate) fn into_result(self) -> Result<Value, ApiError> {
+        let status = StatusCode::from_u16(self.status_code)
+            .map_err(|_| ApiError::Other(format!("invalid batch status {}", self.status_code)))?;
+        if status.is_success() {
+            return Ok(self.body);
+        }
+
+        let text = if self.body.is_null() {
+            String::new()
+        } else {
+            self.body.to_string()
+        };
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = self
+                .headers
+                .as_ref()
+                .and_then(|headers| {
+                    headers
+                        .get("Retry-After")
+                        .or_else(|| headers.get("retry-after"))
+                })
+                .and_then(|value| match value {
+                    Value::String(text) => text.parse::<f64>().ok(),
+                    Value::Number(number) => number.as_f64(),
+                    _ => None,
+                })
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .map_or(Duration::ZERO, Duration::from_secs_f64);
+            return Err(ApiError::RateLimited {
+                retry_after,
+                body: text,
+            });
+        }
+
+        Err(ApiError::from_status(status, text))
+    }
+}
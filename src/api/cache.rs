@@ -0,0 +1,770 @@
+//! On-disk response cache layout, maintenance, and dependency tracking.
+//!
+//! Entries live in `<cache_dir>/<first two key chars>/<key>.json` so large
+//! caches do not pile tens of thousands of files into one directory. Writes,
+//! reads, and removals are appended to an access index, which drives
+//! least-recently-used eviction once the cache grows past its size cap. The
+//! index is rebuilt from a directory scan before the first record is appended
+//! to a missing log, and clients fold only the records appended since they
+//! last read it.
+//!
+//! Every cached GET records the gids it touches: numeric path segments, gid
+//! query parameters, and the `gid` fields of the returned objects. A successful
+//! mutation collects the gids it affected in the same way and evicts every
+//! entry sharing at least one of them.
+
+use crate::api::error::ApiError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    fs,
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, SeekFrom},
+};
+use tracing::debug;
+
+/// File recording cache hit and miss counters across runs.
+pub const CACHE_STATS_FILE: &str = "cache-stats.json";
+
+/// Append-only log of entry writes, reads, and removals used for LRU eviction.
+pub const CACHE_INDEX_FILE: &str = "cache-index.log";
+
+/// Default upper bound on the combined size of cached responses (256 MiB).
+pub const DEFAULT_CACHE_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Length of the hex-encoded SHA-256 keys used as entry file names.
+const KEY_LENGTH: usize = 64;
+
+/// Number of leading key characters naming the shard directory.
+const SHARD_PREFIX: usize = 2;
+
+/// On-disk cache entry representation.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DiskCacheEntry {
+    /// Expiry as seconds since the Unix epoch.
+    pub(crate) expires_at: u64,
+    /// Write time as seconds since the Unix epoch (zero for legacy entries).
+    #[serde(default)]
+    pub(crate) stored_at: u64,
+    /// Base64-encoded response body.
+    pub(crate) body: String,
+    /// Resource gids the response depends on, used for invalidation.
+    #[serde(default)]
+    pub(crate) gids: BTreeSet<String>,
+}
+
+impl DiskCacheEntry {
+    pub(crate) fn is_expired(&self, now: SystemTime) -> bool {
+        now > UNIX_EPOCH + Duration::from_secs(self.expires_at)
+    }
+
+    /// When the response was cached, falling back to the expiry for entries
+    /// written before the timestamp was recorded.
+    pub(crate) fn stored_at(&self) -> SystemTime {
+        let secs = if self.stored_at == 0 {
+            self.expires_at
+        } else {
+            self.stored_at
+        };
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+}
+
+/// Persisted lookup counters; updates from concurrent processes may race, so
+/// the figures are approximate.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LookupCounters {
+    hits: u64,
+    misses: u64,
+}
+
+/// Summary of the disk cache contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of cached responses on disk.
+    pub entries: usize,
+    /// Entries past their expiry that `prune` would remove.
+    pub expired: usize,
+    /// Combined size of all entry files in bytes.
+    pub total_bytes: u64,
+    /// Write time of the oldest entry.
+    pub oldest: Option<SystemTime>,
+    /// GET lookups answered from the cache.
+    pub hits: u64,
+    /// GET lookups that had to contact the API.
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups served from the cache, if any were recorded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        (total > 0).then(|| self.hits as f64 / total as f64)
+    }
+}
+
+/// Collect statistics about the cache stored in `cache_dir`.
+///
+/// # Errors
+/// Returns [`ApiError::Cache`] if the directory cannot be read.
+pub async fn stats(cache_dir: &Path) -> Result<CacheStats, ApiError> {
+    let now = SystemTime::now();
+    let mut stats = CacheStats::default();
+    for path in entry_files(cache_dir).await? {
+        let Ok(metadata) = fs::metadata(&path).await else {
+            continue;
+        };
+        stats.entries += 1;
+        stats.total_bytes += metadata.len();
+        if let Ok(modified) = metadata.modified() {
+            stats.oldest = Some(stats.oldest.map_or(modified, |oldest| oldest.min(modified)));
+        }
+        if read_entry(&path)
+            .await
+            .is_none_or(|entry| entry.is_expired(now))
+        {
+            stats.expired += 1;
+        }
+    }
+
+    let counters = read_counters(cache_dir).await;
+    stats.hits = counters.hits;
+    stats.misses = counters.misses;
+    Ok(stats)
+}
+
+/// Remove every cached response and reset the hit counters.
+///
+/// # Errors
+/// Returns [`ApiError::Cache`] if the directory cannot be read or an entry
+/// cannot be deleted.
+pub async fn clear(cache_dir: &Path) -> Result<usize, ApiError> {
+    let files = entry_files(cache_dir).await?;
+    for path in &files {
+        remove_file(path).await?;
+    }
+    remove_file(&cache_dir.join(CACHE_STATS_FILE)).await?;
+    remove_file(&cache_dir.join(CACHE_INDEX_FILE)).await?;
+    Ok(files.len())
+}
+
+/// Remove expired and unreadable entries, returning how many were deleted.
+///
+/// # Errors
+/// Returns [`ApiError::Cache`] if the directory cannot be read or an entry
+/// cannot be deleted.
+pub async fn prune(cache_dir: &Path) -> Result<usize, ApiError> {
+    let now = SystemTime::now();
+    let mut removed = 0;
+    for path in entry_files(cache_dir).await? {
+        if read_entry(&path)
+            .await
+            .is_none_or(|entry| entry.is_expired(now))
+        {
+            remove_entry(cache_dir, &path).await?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Record the outcome of a cache lookup in the persisted counters.
+pub(crate) async fn record_lookup(cache_dir: &Path, hit: bool) {
+    let mut counters = read_counters(cache_dir).await;
+    if hit {
+        counters.hits += 1;
+    } else {
+        counters.misses += 1;
+    }
+    if let Ok(serialized) = serde_json::to_vec(&counters) {
+        let path = cache_dir.join(CACHE_STATS_FILE);
+        let tmp = path.with_extension("json.tmp");
+        if fs::write(&tmp, serialized).await.is_ok() {
+            fs::rename(&tmp, &path).await.ok();
+        }
+    }
+}
+
+/// Location of the entry file for `key` inside its shard directory.
+pub(crate) fn entry_path(cache_dir: &Path, key: &str) -> PathBuf {
+    let shard = key.get(..SHARD_PREFIX).unwrap_or("00");
+    cache_dir.join(shard).join(format!("{key}.json"))
+}
+
+/// List the entry files in `cache_dir`, skipping bookkeeping files.
+///
+/// Both shard directories and entries left at the top level by earlier
+/// versions are included, so maintenance commands still reach the latter.
+pub(crate) async fn entry_files(cache_dir: &Path) -> Result<Vec<PathBuf>, ApiError> {
+    let mut files = Vec::new();
+    let mut entries = match fs::read_dir(cache_dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(files),
+        Err(err) => return Err(ApiError::Cache(err)),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if is_entry_file(&path) {
+            files.push(path);
+        } else if is_shard_dir(&path) {
+            let mut shard = fs::read_dir(&path).await?;
+            while let Some(entry) = shard.next_entry().await? {
+                let path = entry.path();
+                if is_entry_file(&path) {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Read and parse a single entry file, returning `None` if it is unreadable.
+pub(crate) async fn read_entry(path: &Path) -> Option<DiskCacheEntry> {
+    let bytes = fs::read(path).await.ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn is_entry_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| {
+                stem.len() == KEY_LENGTH && stem.bytes().all(|byte| byte.is_ascii_hexdigit())
+            })
+}
+
+fn is_shard_dir(path: &Path) -> bool {
+    path.is_dir()
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.len() == SHARD_PREFIX && name.bytes().all(|byte| byte.is_ascii_hexdigit())
+            })
+}
+
+fn entry_key(path: &Path) -> Option<&str> {
+    path.file_stem().and_then(|stem| stem.to_str())
+}
+
+/// Delete an entry file and record the removal in the access index.
+///
+/// # Errors
+/// Returns [`ApiError::Cache`] if the file exists but cannot be deleted.
+pub(crate) async fn remove_entry(cache_dir: &Path, path: &Path) -> Result<(), ApiError> {
+    remove_file(path).await?;
+    if let Some(key) = entry_key(path) {
+        append_index(cache_dir, &format!("D {key}")).await;
+    }
+    Ok(())
+}
+
+async fn remove_file(path: &Path) -> Result<(), ApiError> {
+    match fs::remove_file(path).await {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(ApiError::Cache(err)),
+        _ => Ok(()),
+    }
+}
+
+/// Record that the entry for `key` was written with `size` bytes.
+pub(crate) async fn record_write(cache_dir: &Path, key: &str, size: u64) {
+    append_index(cache_dir, &format!("W {key} {} {size}", now_millis())).await;
+}
+
+/// Record that the entry for `key` was read from disk.
+pub(crate) async fn record_access(cache_dir: &Path, key: &str) {
+    append_index(cache_dir, &format!("A {key} {}", now_millis())).await;
+}
+
+/// Evict least-recently-used entries until the cache fits within `max_bytes`.
+///
+/// Returns how many entries were removed. The index is compacted whenever
+/// entries are evicted or the log has grown well beyond the live entries.
+///
+/// # Errors
+/// Returns [`ApiError::Cache`] if the cache directory cannot be scanned or an
+/// evicted entry cannot be deleted.
+pub async fn enforce_size_limit(cache_dir: &Path, max_bytes: u64) -> Result<usize, ApiError> {
+    enforce_size_limit_with(cache_dir, max_bytes, &mut AccessIndex::default()).await
+}
+
+/// [`enforce_size_limit`] with an index kept between calls, so only the log
+/// records appended since it was last read are folded in.
+pub(crate) async fn enforce_size_limit_with(
+    cache_dir: &Path,
+    max_bytes: u64,
+    index: &mut AccessIndex,
+) -> Result<usize, ApiError> {
+    index.refresh(cache_dir).await?;
+    let mut total = index.total_bytes();
+    let mut evicted = 0;
+    if total > max_bytes {
+        let mut by_age: Vec<_> = index
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.accessed_ms, key.clone()))
+            .collect();
+        by_age.sort_unstable();
+        for (_, key) in by_age {
+            if total <= max_bytes {
+                break;
+            }
+            if let Some(entry) = index.entries.remove(&key) {
+                debug!("evicting cache entry {key} ({} bytes)", entry.size);
+                remove_file(&entry_path(cache_dir, &key)).await?;
+                remove_file(&cache_dir.join(format!("{key}.json"))).await?;
+                total = total.saturating_sub(entry.size);
+                evicted += 1;
+            }
+        }
+    }
+
+    if evicted > 0 || index.lines > index.entries.len().saturating_mul(2) + 1024 {
+        index.persist(cache_dir).await;
+    }
+    Ok(evicted)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct IndexedEntry {
+    size: u64,
+    accessed_ms: u64,
+}
+
+/// Live view of the access index, folded from the append-only log.
+#[derive(Debug, Default)]
+pub(crate) struct AccessIndex {
+    entries: HashMap<String, IndexedEntry>,
+    lines: usize,
+    /// Header naming the compacted log the entries were folded from.
+    generation: Option<String>,
+    /// Bytes of the log folded so far.
+    read_to: u64,
+}
+
+impl AccessIndex {
+    /// Load the index, rebuilding it from a directory scan when the log is
+    /// missing (for example after an upgrade or a manual cleanup).
+    #[cfg(test)]
+    async fn load(cache_dir: &Path) -> Result<Self, ApiError> {
+        let mut index = Self::default();
+        index.refresh(cache_dir).await?;
+        Ok(index)
+    }
+
+    /// Fold the records appended since the last refresh, starting over when
+    /// the log was compacted (by any process) in the meantime.
+    async fn refresh(&mut self, cache_dir: &Path) -> Result<(), ApiError> {
+        let Ok(file) = fs::File::open(cache_dir.join(CACHE_INDEX_FILE)).await else {
+            *self = Self::rebuild(cache_dir).await?;
+            return Ok(());
+        };
+        let len = file.metadata().await.map_err(ApiError::Cache)?.len();
+        let mut reader = BufReader::new(file);
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .await
+            .map_err(ApiError::Cache)?;
+        let generation = header.strip_prefix("# ").map(|tag| tag.trim().to_string());
+        if generation != self.generation || len < self.read_to {
+            *self = Self {
+                generation,
+                ..Self::default()
+            };
+        }
+
+        reader
+            .seek(SeekFrom::Start(self.read_to))
+            .await
+            .map_err(ApiError::Cache)?;
+        let mut log = String::new();
+        reader
+            .read_to_string(&mut log)
+            .await
+            .map_err(ApiError::Cache)?;
+        // A record still being appended is folded on the next refresh.
+        let complete = log.rfind('\n').map_or(0, |end| end + 1);
+        self.fold(&log[..complete]);
+        self.read_to += complete as u64;
+        Ok(())
+    }
+
+    fn fold(&mut self, log: &str) {
+        for line in log.lines() {
+            self.lines += 1;
+            let mut parts = line.split_whitespace();
+            let (Some(op), Some(key)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let millis = parts
+                .next()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0);
+            match op {
+                "W" => {
+                    let size = parts
+                        .next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(0);
+                    self.entries.insert(
+                        key.to_string(),
+                        IndexedEntry {
+                            size,
+                            accessed_ms: millis,
+                        },
+                    );
+                }
+                "A" => {
+                    if let Some(entry) = self.entries.get_mut(key) {
+                        entry.accessed_ms = entry.accessed_ms.max(millis);
+                    }
+                }
+                "D" => {
+                    self.entries.remove(key);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn rebuild(cache_dir: &Path) -> Result<Self, ApiError> {
+        let mut index = Self::default();
+        for path in entry_files(cache_dir).await? {
+            let (Some(key), Ok(metadata)) = (entry_key(&path), fs::metadata(&path).await) else {
+                continue;
+            };
+            let accessed_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, millis);
+            index.entries.insert(
+                key.to_string(),
+                IndexedEntry {
+                    size: metadata.len(),
+                    accessed_ms,
+                },
+            );
+        }
+        index.persist(cache_dir).await;
+        Ok(index)
+    }
+
+    fn total_bytes(&self) -> u64 {
+        self.entries.values().map(|entry| entry.size).sum()
+    }
+
+    /// Rewrite the log as one write record per live entry, under a new
+    /// generation header.
+    async fn persist(&mut self, cache_dir: &Path) {
+        let generation = format!("{}-{}", now_millis(), std::process::id());
+        let mut log = format!("# {generation}\n");
+        for (key, entry) in &self.entries {
+            let _ = writeln!(log, "W {key} {} {}", entry.accessed_ms, entry.size);
+        }
+        let path = cache_dir.join(CACHE_INDEX_FILE);
+        let tmp = path.with_extension(format!("log.{}.tmp", std::process::id()));
+        if fs::write(&tmp, &log).await.is_ok() && fs::rename(&tmp, &path).await.is_ok() {
+            self.generation = Some(generation);
+            self.read_to = log.len() as u64;
+        }
+        self.lines = self.entries.len();
+    }
+}
+
+async fn append_index(cache_dir: &Path, record: &str) {
+    // Entries written while the log was missing are only known to a scan.
+    if !fs::try_exists(cache_dir.join(CACHE_INDEX_FILE))
+        .await
+        .unwrap_or(true)
+    {
+        if let Err(err) = AccessIndex::rebuild(cache_dir).await {
+            debug!("failed to rebuild cache index: {err}");
+        }
+    }
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(cache_dir.join(CACHE_INDEX_FILE))
+        .await;
+    if let Ok(mut file) = file {
+        file.write_all(format!("{record}\n").as_bytes()).await.ok();
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, millis)
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+async fn read_counters(cache_dir: &Path) -> LookupCounters {
+    fs::read(cache_dir.join(CACHE_STATS_FILE))
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Keys referencing broad containers (workspaces, teams, people). Following
+/// them would tie nearly every entry together and flush the whole cache on
+/// any write, so they are ignored when collecting gids.
+const SHARED_CONTAINER_KEYS: &[&str] = &[
+    "workspace",
+    "team",
+    "assignee",
+    "followers",
+    "members",
+    "owner",
+    "created_by",
+    "completed_by",
+    "likes",
+    "hearts",
+];
+
+/// Query parameters whose numeric values are not resource identifiers.
+const NON_GID_QUERY_KEYS: &[&str] = &["limit", "offset", "opt_fields", "opt_pretty"];
+
+/// Gids referenced by a cached GET request and its response body.
+#[must_use]
+pub fn gids_for_response(
+    path: &str,
+    query_pairs: &[(String, String)],
+    response: &[u8],
+) -> BTreeSet<String> {
+    let mut gids = gids_in_path(path);
+    gids.extend(gids_in_query(query_pairs));
+    if let Ok(value) = serde_json::from_slice::<Value>(response) {
+        collect_response_gids(&value, &mut gids);
+    }
+    gids
+}
+
+/// Gids affected by a successful mutating request.
+///
+/// Besides the path and response, the request body is scanned for gid
+/// references such as `parent`, `projects`, or the relative paths of
+/// `/batch` actions.
+#[must_use]
+pub fn gids_for_mutation(
+    path: &str,
+    query_pairs: &[(String, String)],
+    body: Option<&Value>,
+    response: &[u8],
+) -> BTreeSet<String> {
+    let mut gids = gids_for_response(path, query_pairs, response);
+    if let Some(body) = body {
+        collect_request_gids(body, &mut gids);
+    }
+    gids
+}
+
+fn is_gid(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+fn gids_in_path(path: &str) -> BTreeSet<String> {
+    path.split(['/', '?'])
+        .filter(|segment| is_gid(segment))
+        .map(str::to_string)
+        .collect()
+}
+
+fn gids_in_query(query_pairs: &[(String, String)]) -> impl Iterator<Item = String> + '_ {
+    query_pairs
+        .iter()
+        .filter(|(key, value)| {
+            is_gid(value)
+                && !NON_GID_QUERY_KEYS.contains(&key.as_str())
+                && !SHARED_CONTAINER_KEYS.contains(&key.as_str())
+        })
+        .map(|(_, value)| value.clone())
+}
+
+fn collect_response_gids(value: &Value, gids: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if SHARED_CONTAINER_KEYS.contains(&key.as_str()) {
+                    continue;
+                }
+                if key == "gid" {
+                    if let Some(gid) = child.as_str().filter(|gid| is_gid(gid)) {
+                        gids.insert(gid.to_string());
+                    }
+                } else {
+                    collect_response_gids(child, gids);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_response_gids(item, gids);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_request_gids(value: &Value, gids: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if !SHARED_CONTAINER_KEYS.contains(&key.as_str()) {
+                    collect_request_gids(child, gids);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_request_gids(item, gids);
+            }
+        }
+        Value::String(text) if text.starts_with('/') => gids.extend(gids_in_path(text)),
+        Value::String(text) if is_gid(text) => {
+            gids.insert(text.clone());
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn set(values: &[&str]) -> BTreeSet<String> {
+        values.iter().map(|value| (*value).to_string()).collect()
+    }
+
+    #[test]
+    fn response_gids_skip_shared_containers() {
+        let body = json!({
+            "data": {
+                "gid": "1",
+                "parent": { "gid": "2" },
+                "projects": [{ "gid": "3" }],
+                "workspace": { "gid": "99" },
+                "assignee": { "gid": "98" }
+            }
+        });
+        let gids = gids_for_response(
+            "/tasks/1",
+            &[("limit".into(), "50".into())],
+            body.to_string().as_bytes(),
+        );
+        assert_eq!(gids, set(&["1", "2", "3"]));
+    }
+
+    #[test]
+    fn mutation_gids_include_request_references() {
+        let body = json!({
+            "data": {
+                "actions": [
+                    { "method": "put", "relative_path": "/tasks/7", "data": { "parent": "8" } }
+                ]
+            }
+        });
+        let gids = gids_for_mutation("/batch", &[], Some(&body), b"{}");
+        assert_eq!(gids, set(&["7", "8"]));
+    }
+
+    fn write_entry(dir: &Path, key: char, expires_at: u64) {
+        let entry = DiskCacheEntry {
+            expires_at,
+            stored_at: 0,
+            body: String::new(),
+            gids: BTreeSet::new(),
+        };
+        let path = entry_path(dir, &key.to_string().repeat(KEY_LENGTH));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, serde_json::to_vec(&entry).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stats_prune_and_clear_skip_bookkeeping_files() {
+        let tmp = TempDir::new().unwrap();
+        let future = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 3600;
+        write_entry(tmp.path(), 'a', future);
+        write_entry(tmp.path(), 'b', 1);
+        std::fs::write(tmp.path().join("rate-limit.json"), "{}").unwrap();
+        record_lookup(tmp.path(), true).await;
+        record_lookup(tmp.path(), false).await;
+
+        let before = stats(tmp.path()).await.unwrap();
+        assert_eq!(before.entries, 2);
+        assert_eq!(before.expired, 1);
+        assert_eq!(before.hit_rate(), Some(0.5));
+
+        assert_eq!(prune(tmp.path()).await.unwrap(), 1);
+        assert_eq!(clear(tmp.path()).await.unwrap(), 1);
+        let after = stats(tmp.path()).await.unwrap();
+        assert_eq!(after.entries, 0);
+        assert_eq!(after.hits + after.misses, 0);
+        assert!(tmp.path().join("rate-limit.json").exists());
+    }
+
+    #[tokio::test]
+    async fn eviction_drops_least_recently_used_entries() {
+        let tmp = TempDir::new().unwrap();
+        let legacy = format!("{}.json", "c".repeat(KEY_LENGTH));
+        std::fs::write(tmp.path().join(&legacy), vec![b'x'; 100]).unwrap();
+        for key in ['a', 'b'] {
+            write_entry(tmp.path(), key, 1);
+        }
+        let size = std::fs::metadata(entry_path(tmp.path(), &"a".repeat(KEY_LENGTH)))
+            .unwrap()
+            .len();
+        assert!(tmp.path().join("aa").is_dir());
+        assert_eq!(entry_files(tmp.path()).await.unwrap().len(), 3);
+
+        // A missing index is rebuilt from a directory scan.
+        assert_eq!(enforce_size_limit(tmp.path(), u64::MAX).await.unwrap(), 0);
+        std::fs::write(
+            tmp.path().join(CACHE_INDEX_FILE),
+            format!(
+                "W {a} 10 {size}\nW {b} 20 {size}\nW {c} 5 100\nA {a} 30\n",
+                a = "a".repeat(KEY_LENGTH),
+                b = "b".repeat(KEY_LENGTH),
+                c = "c".repeat(KEY_LENGTH),
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(enforce_size_limit(tmp.path(), size).await.unwrap(), 2);
+        let remaining = entry_files(tmp.path()).await.unwrap();
+        assert_eq!(
+            remaining,
+            vec![entry_path(tmp.path(), &"a".repeat(KEY_LENGTH))]
+        );
+        let index = std::fs::read_to_string(tmp.path().join(CACHE_INDEX_FILE)).unwrap();
+        assert!(index.starts_with("# "));
+        assert_eq!(
+            index.lines().filter(|line| line.starts_with("W ")).count(),
+            1
+        );
+
+        remove_entry(tmp.path(), &remaining[0]).await.unwrap();
+        let index = AccessIndex::load(tmp.path()).await.unwrap();
+        assert!(index.entries.is_empty());
+    }
+}
@@ -0,0 +1,397 @@
+//! Record and replay HTTP interactions for reproducible runs.
+//!
+//! In record mode every request passing through `ApiClient::execute` is saved
+//! with its response as a JSON fixture, one file per method, path, and query
+//! combination. Replay mode answers requests from those fixtures without any
+//! network access. When the same request was recorded several times, the
+//! responses are replayed in order and the last one repeats once exhausted.
+//! Recording into an existing directory replaces a fixture's responses the
+//! first time its request is seen, so a re-record never replays stale ones.
+
+use crate::api::error::{ApiError, ReplayMiss};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+use tokio::{fs, sync::Mutex};
+use tracing::debug;
+
+/// Placeholder written in place of the access token.
+const REDACTED: &str = "[REDACTED]";
+
+/// Longest path-derived prefix used in fixture file names.
+const MAX_SLUG_LENGTH: usize = 80;
+
+/// Whether requests are recorded to or replayed from a fixture directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Perform requests normally and save each interaction to the directory.
+    Record(PathBuf),
+    /// Serve responses from fixtures in the directory, never touching the network.
+    Replay(PathBuf),
+}
+
+impl CassetteMode {
+    /// Directory holding the fixtures.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        match self {
+            Self::Record(dir) | Self::Replay(dir) => dir,
+        }
+    }
+}
+
+/// Request half of a fixture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method.
+    pub method: String,
+    /// Request path relative to the API base URL.
+    pub path: String,
+    /// Query parameters, sorted so that ordering never affects matching.
+    #[serde(default)]
+    pub query: Vec<(String, String)>,
+    /// Headers sent with the request, with credentials redacted.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON request body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl RecordedRequest {
+    fn new(method: &Method, path: &str, query: &[(String, String)], body: Option<&Value>) -> Self {
+        let mut query = query.to_vec();
+        query.sort();
+        let mut headers = BTreeMap::new();
+        headers.insert("authorization".to_string(), format!("Bearer {REDACTED}"));
+        Self {
+            method: method.as_str().to_string(),
+            path: path.to_string(),
+            query,
+            headers,
+            body: body.cloned(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        let query = self
+            .query
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        if query.is_empty() {
+            format!("{} {}", self.method, self.path)
+        } else {
+            format!("{} {}?{query}", self.method, self.path)
+        }
+    }
+
+    /// File name shared by every recording of this request.
+    fn file_name(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.describe());
+        let digest = format!("{:x}", hasher.finalize());
+
+        let mut slug: String = format!("{}{}", self.method, self.path)
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() {
+                    ch.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        slug.truncate(MAX_SLUG_LENGTH);
+        format!("{}-{}.json", slug.trim_end_matches('_'), &digest[..12])
+    }
+}
+
+/// Response half of a fixture.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// Error status; absent when the request succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Response body, stored as JSON when it parses and as a string otherwise.
+    pub body: Value,
+}
+
+impl RecordedResponse {
+    fn from_result(result: &Result<Vec<u8>, ApiError>) -> Option<Self> {
+        let (status, text) = match result {
+            Ok(bytes) => (None, String::from_utf8_lossy(bytes).into_owned()),
+            Err(ApiError::Http {
+                status,
+                message,
+                details,
+                ..
+            }) => (
+                Some(status.as_u16()),
+                details
+                    .as_ref()
+                    .map_or_else(|| message.clone(), Value::to_string),
+            ),
+            Err(ApiError::Authentication(body)) => {
+                (Some(StatusCode::UNAUTHORIZED.as_u16()), body.clone())
+            }
+            Err(ApiError::RateLimited { body, .. }) => {
+                (Some(StatusCode::TOO_MANY_REQUESTS.as_u16()), body.clone())
+            }
+            Err(_) => return None,
+        };
+        let body = serde_json::from_str(&text).unwrap_or(Value::String(text));
+        Some(Self { status, body })
+    }
+
+    fn into_result(self) -> Result<Vec<u8>, ApiError> {
+        let text = match self.body {
+            Value::String(text) => text,
+            other => other.to_string(),
+        };
+        match self.status {
+            None => Ok(text.into_bytes()),
+            Some(code) => {
+                let status = StatusCode::from_u16(code).map_err(|_| {
+                    ApiError::Other(format!("fixture has invalid status code {code}"))
+                })?;
+                Err(ApiError::from_status(status, text))
+            }
+        }
+    }
+}
+
+/// Every recorded response for one request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    /// The request being answered.
+    pub request: RecordedRequest,
+    /// Responses in the order they were recorded.
+    pub responses: Vec<RecordedResponse>,
+}
+
+/// Recorder or player bound to a fixture directory.
+#[derive(Debug)]
+pub(crate) struct Cassette {
+    mode: CassetteMode,
+    /// Responses recorded this session per fixture while recording; replay
+    /// cursors otherwise. Also serialises fixture updates.
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+impl Cassette {
+    pub(crate) fn new(mode: CassetteMode) -> Result<Self, ApiError> {
+        if let CassetteMode::Record(dir) = &mode {
+            std::fs::create_dir_all(dir)?;
+        }
+        Ok(Self {
+            mode,
+            cursors: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub(crate) const fn is_replay(&self) -> bool {
+        matches!(self.mode, CassetteMode::Replay(_))
+    }
+
+    /// Answer a request from the fixtures.
+    ///
+    /// # Errors
+    /// Returns a [`ReplayMiss`] when nothing was recorded for the
+    /// request, or the recorded error when the original request failed.
+    pub(crate) async fn replay(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &[(String, String)],
+        body: Option<&Value>,
+    ) -> Result<Vec<u8>, ApiError> {
+        let request = RecordedRequest::new(method, path, query, body);
+        let name = request.file_name();
+        let miss = || {
+            ApiError::from(ReplayMiss {
+                request: request.describe(),
+                dir: self.mode.dir().to_path_buf(),
+            })
+        };
+
+        let Some(fixture) = read_fixture(&self.mode.dir().join(&name)).await? else {
+            return Err(miss());
+        };
+        if fixture.request.method != request.method
+            || fixture.request.path != request.path
+            || fixture.request.query != request.query
+        {
+            return Err(miss());
+        }
+
+        let mut cursors = self.cursors.lock().await;
+        let cursor = cursors.entry(name).or_default();
+        let index = (*cursor).min(fixture.responses.len().saturating_sub(1));
+        *cursor += 1;
+        let response = fixture.responses.get(index).cloned().ok_or_else(miss)?;
+        debug!("replaying {} from fixture", request.describe());
+        response.into_result()
+    }
+
+    /// Append the outcome of a request to its fixture, replacing the
+    /// responses left by an earlier session on the first write.
+    ///
+    /// Failures that never reached Asana, such as network errors, are not
+    /// recorded. Any occurrence of `token` is redacted before writing.
+    pub(crate) async fn record(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &[(String, String)],
+        body: Option<&Value>,
+        result: &Result<Vec<u8>, ApiError>,
+        token: &str,
+    ) -> Result<(), ApiError> {
+        let Some(response) = RecordedResponse::from_result(result) else {
+            return Ok(());
+        };
+        let request = RecordedRequest::new(method, path, query, body);
+        let name = request.file_name();
+        let path = self.mode.dir().join(&name);
+
+        let mut recorded = self.cursors.lock().await;
+        let count = recorded.entry(name).or_default();
+        let earlier = if *count == 0 {
+            None
+        } else {
+            read_fixture(&path).await?
+        };
+        let mut fixture = earlier.unwrap_or(Fixture {
+            request,
+            responses: Vec::new(),
+        });
+        fixture.responses.push(response);
+        *count += 1;
+
+        let mut serialized = serde_json::to_string_pretty(&fixture)?;
+        if !token.is_empty() {
+            serialized = serialized.replace(token, REDACTED);
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serialized).await?;
+        fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+}
+
+async fn read_fixture(path: &Path) -> Result<Option<Fixture>, ApiError> {
+    match fs::read(path).await {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(ApiError::Cache(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn records_redacted_fixtures_and_replays_them_in_order() {
+        let tmp = TempDir::new().unwrap();
+        let recorder = Cassette::new(CassetteMode::Record(tmp.path().to_path_buf())).unwrap();
+        let query = vec![
+            ("opt_fields".to_string(), "name".to_string()),
+            ("limit".to_string(), "5".to_string()),
+        ];
+        for name in ["secret-token first", "second"] {
+            let body = json!({ "data": { "name": name } }).to_string().into_bytes();
+            recorder
+                .record(
+                    &Method::GET,
+                    "/tasks/1",
+                    &query,
+                    None,
+                    &Ok(body),
+                    "secret-token",
+                )
+                .await
+                .unwrap();
+        }
+        let missing = Err(ApiError::from_status(StatusCode::NOT_FOUND, "{}".into()));
+        recorder
+            .record(
+                &Method::GET,
+                "/tasks/2",
+                &[],
+                None,
+                &missing,
+                "secret-token",
+            )
+            .await
+            .unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(tmp.path()).unwrap().collect();
+        assert_eq!(files.len(), 2);
+        for file in files {
+            let text = std::fs::read_to_string(file.unwrap().path()).unwrap();
+            assert!(!text.contains("secret-token"));
+        }
+
+        let player = Cassette::new(CassetteMode::Replay(tmp.path().to_path_buf())).unwrap();
+        let reversed: Vec<_> = query.iter().rev().cloned().collect();
+        let mut names = Vec::new();
+        for _ in 0..3 {
+            let bytes = player
+                .replay(&Method::GET, "/tasks/1", &reversed, None)
+                .await
+                .unwrap();
+            let value: Value = serde_json::from_slice(&bytes).unwrap();
+            names.push(value["data"]["name"].as_str().unwrap().to_string());
+        }
+        assert_eq!(names, ["[REDACTED] first", "second", "second"]);
+
+        let err = player
+            .replay(&Method::GET, "/tasks/2", &[], None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::Http { status, .. } if status == StatusCode::NOT_FOUND));
+
+        let err = player
+            .replay(&Method::DELETE, "/tasks/1", &[], None)
+            .await
+            .unwrap_err();
+        assert!(err.replay_miss().is_some());
+        assert!(err.to_string().contains("DELETE /tasks/1"));
+    }
+
+    #[tokio::test]
+    async fn rerecording_replaces_earlier_responses() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().to_path_buf();
+        for names in [["old", "older"], ["new", "newer"]] {
+            let recorder = Cassette::new(CassetteMode::Record(dir.clone())).unwrap();
+            for name in names {
+                let body = json!({ "data": { "name": name } }).to_string().into_bytes();
+                recorder
+                    .record(&Method::GET, "/tasks/1", &[], None, &Ok(body), "")
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let fixture = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap();
+        let fixture: Fixture =
+            serde_json::from_slice(&std::fs::read(fixture.path()).unwrap()).unwrap();
+        let names: Vec<_> = fixture
+            .responses
+            .iter()
+            .map(|response| response.body["data"]["name"].clone())
+            .collect();
+        assert_eq!(names, [json!("new"), json!("newer")]);
+    }
+}
@@ -1,32 +1,51 @@
 //! Core asynchronous HTTP client for interacting with Asana's REST API.
 
 use crate::api::{
-    auth::AuthToken,
-    error::{ApiError, RateLimitInfo},
-    pagination::ListResponse,
+    auth::{AccessTokenProvider, AuthToken, StaticTokenProvider},
+    batch::{
+        BatchAction, BatchActionResponse, BatchRequest, BatchRequestData, BatchResponse,
+        MAX_BATCH_ACTIONS,
+    },
+    cache::{self, DiskCacheEntry, gids_for_mutation, gids_for_response},
+    cassette::{Cassette, CassetteMode},
+    error::{ApiError, PaginationExpired, RateLimitInfo},
+    har::{HarEntry, HarRecorder, PendingEntry},
+    network::NetworkOptions,
+    pagination::{ListResponse, ListingState, MAX_PAGE_SIZE},
+    progress::TransferProgress,
+    rate_limit::RateLimiter,
 };
 use async_stream::try_stream;
 use base64::{Engine as _, engine::general_purpose};
 use directories::ProjectDirs;
 use futures_core::Stream;
+use futures_util::{StreamExt, pin_mut};
 use reqwest::{
-    Method, StatusCode,
-    header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue, RETRY_AFTER, USER_AGENT},
+    Method, RequestBuilder, StatusCode,
+    header::{
+        ACCEPT, AUTHORIZATION, ETAG, HeaderMap, HeaderValue, IF_RANGE, LAST_MODIFIED, RANGE,
+        RETRY_AFTER, USER_AGENT,
+    },
 };
+use serde::Serialize;
 use serde::de::DeserializeOwned;
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::{
-    collections::HashMap,
-    path::PathBuf,
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
     },
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use tokio::{fs, sync::RwLock, time::sleep};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{Mutex, RwLock},
+    time::sleep,
+};
 use tracing::{debug, warn};
 
 const VERSION: &str = match option_env!("CARGO_PKG_VERSION") {
@@ -34,22 +53,30 @@ const VERSION: &str = match option_env!("CARGO_PKG_VERSION") {
     None => "unknown",
 };
 
+/// Default retention for expired cache entries kept for stale reads.
+pub const DEFAULT_STALE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Outcome of looking up a request in the memory and disk caches.
+enum CacheLookup {
+    Fresh(Vec<u8>),
+    Stale {
+        body: Vec<u8>,
+        stored_at: SystemTime,
+    },
+    Miss,
+}
+
 /// In-memory cache entry.
 #[derive(Clone)]
 struct CacheEntry {
     expires_at: Instant,
     value: Arc<Vec<u8>>,
-}
-
-/// On-disk cache entry representation.
-#[derive(Debug, Serialize, Deserialize)]
-struct DiskCacheEntry {
-    expires_at: u64,
-    body: String,
+    gids: Arc<BTreeSet<String>>,
 }
 
 /// Configurable options for the API client.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ApiClientOptions {
     /// Base URL for the Asana API.
     pub base_url: String,
@@ -67,6 +94,25 @@ pub struct ApiClientOptions {
     pub cache_dir: PathBuf,
     /// Whether the client should avoid network calls and use cached data only.
     pub offline: bool,
+    /// Whether requests are paced against the rate-limit budget shared
+    /// through the cache directory.
+    pub proactive_rate_limit: bool,
+    /// Skip cache lookups for GET requests while still storing fresh responses.
+    pub refresh_cache: bool,
+    /// How long expired entries are retained for offline or fallback use.
+    pub stale_max_age: Duration,
+    /// Serve retained stale entries when the network or API is unavailable.
+    pub stale_fallback: bool,
+    /// Upper bound on the on-disk cache size; `None` disables eviction.
+    pub cache_max_bytes: Option<u64>,
+    /// Record requests to, or replay them from, a fixture directory.
+    pub cassette: Option<CassetteMode>,
+    /// Write every HTTP exchange to this file in HAR 1.2 format.
+    pub trace_http: Option<PathBuf>,
+    /// Items requested per page from paginated endpoints.
+    pub page_size: Option<usize>,
+    /// Custom CA bundle, client certificate, and proxy settings.
+    pub network: NetworkOptions,
 }
 
 impl ApiClientOptions {
@@ -120,6 +166,69 @@ impl ApiClientOptions {
         self.offline = offline;
         self
     }
+
+    /// Enable or disable proactive rate limiting.
+    #[must_use]
+    pub const fn with_proactive_rate_limit(mut self, enabled: bool) -> Self {
+        self.proactive_rate_limit = enabled;
+        self
+    }
+
+    /// Bypass cached GET responses, replacing them with fresh ones.
+    #[must_use]
+    pub const fn with_refresh_cache(mut self, refresh: bool) -> Self {
+        self.refresh_cache = refresh;
+        self
+    }
+
+    /// Override how long expired entries are kept for stale reads.
+    #[must_use]
+    pub const fn with_stale_max_age(mut self, max_age: Duration) -> Self {
+        self.stale_max_age = max_age;
+        self
+    }
+
+    /// Serve stale entries when requests fail because the network is down.
+    #[must_use]
+    pub const fn with_stale_fallback(mut self, enabled: bool) -> Self {
+        self.stale_fallback = enabled;
+        self
+    }
+
+    /// Override the disk cache size cap; `None` leaves the cache unbounded.
+    #[must_use]
+    pub const fn with_cache_max_bytes(mut self, max_bytes: Option<u64>) -> Self {
+        self.cache_max_bytes = max_bytes;
+        self
+    }
+
+    /// Record or replay requests using a fixture directory.
+    #[must_use]
+    pub fn with_cassette(mut self, cassette: Option<CassetteMode>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Trace HTTP exchanges to a HAR file.
+    #[must_use]
+    pub fn with_trace_http(mut self, path: Option<PathBuf>) -> Self {
+        self.trace_http = path;
+        self
+    }
+
+    /// Request `size` items per page from paginated endpoints.
+    #[must_use]
+    pub const fn with_page_size(mut self, size: Option<usize>) -> Self {
+        self.page_size = size;
+        self
+    }
+
+    /// Apply TLS trust, client identity, and proxy settings.
+    #[must_use]
+    pub fn with_network(mut self, network: NetworkOptions) -> Self {
+        self.network = network;
+        self
+    }
 }
 
 impl Default for ApiClientOptions {
@@ -136,6 +245,15 @@ impl Default for ApiClientOptions {
             cache_ttl: Duration::from_secs(300),
             cache_dir,
             offline: false,
+            proactive_rate_limit: true,
+            refresh_cache: false,
+            stale_max_age: DEFAULT_STALE_MAX_AGE,
+            stale_fallback: false,
+            cache_max_bytes: Some(cache::DEFAULT_CACHE_MAX_BYTES),
+            cassette: None,
+            trace_http: None,
+            page_size: None,
+            network: NetworkOptions::default(),
         }
     }
 }
@@ -153,17 +271,29 @@ fn default_cache_dir() -> PathBuf {
 
 /// Builder for [`ApiClient`].
 pub struct ApiClientBuilder {
-    token: AuthToken,
+    tokens: Arc<dyn AccessTokenProvider>,
     options: ApiClientOptions,
+    on_stale: Option<StaleHook>,
 }
 
+/// Callback told the write time of each stale response a client serves.
+type StaleHook = Arc<dyn Fn(SystemTime) + Send + Sync>;
+
 impl ApiClientBuilder {
     /// Create a new builder.
     #[must_use]
     pub fn new(token: AuthToken) -> Self {
+        Self::with_token_provider(Arc::new(StaticTokenProvider::new(token)))
+    }
+
+    /// Create a builder whose tokens come from `provider`, consulted before
+    /// each request and after a `401 Unauthorized` response.
+    #[must_use]
+    pub fn with_token_provider(provider: Arc<dyn AccessTokenProvider>) -> Self {
         Self {
-            token,
+            tokens: provider,
             options: ApiClientOptions::default(),
+            on_stale: None,
         }
     }
 
@@ -223,35 +353,122 @@ impl ApiClientBuilder {
         self
     }
 
+    /// Configure proactive rate limiting.
+    #[must_use]
+    pub const fn proactive_rate_limit(mut self, enabled: bool) -> Self {
+        self.options.proactive_rate_limit = enabled;
+        self
+    }
+
+    /// Configure whether cached GET responses are bypassed and refreshed.
+    #[must_use]
+    pub const fn refresh_cache(mut self, refresh: bool) -> Self {
+        self.options.refresh_cache = refresh;
+        self
+    }
+
+    /// Override how long expired entries are kept for stale reads.
+    #[must_use]
+    pub const fn stale_max_age(mut self, max_age: Duration) -> Self {
+        self.options.stale_max_age = max_age;
+        self
+    }
+
+    /// Configure falling back to stale entries on network failures.
+    #[must_use]
+    pub const fn stale_fallback(mut self, enabled: bool) -> Self {
+        self.options.stale_fallback = enabled;
+        self
+    }
+
+    /// Override the disk cache size cap; `None` leaves the cache unbounded.
+    #[must_use]
+    pub const fn cache_max_bytes(mut self, max_bytes: Option<u64>) -> Self {
+        self.options.cache_max_bytes = max_bytes;
+        self
+    }
+
+    /// Configure record or replay mode.
+    #[must_use]
+    pub fn cassette(mut self, cassette: Option<CassetteMode>) -> Self {
+        self.options.cassette = cassette;
+        self
+    }
+
+    /// Trace HTTP exchanges to a HAR file.
+    #[must_use]
+    pub fn trace_http(mut self, path: Option<PathBuf>) -> Self {
+        self.options.trace_http = path;
+        self
+    }
+
+    /// Request `size` items per page from paginated endpoints.
+    #[must_use]
+    pub const fn page_size(mut self, size: Option<usize>) -> Self {
+        self.options.page_size = size;
+        self
+    }
+
+    /// Apply TLS trust, client identity, and proxy settings.
+    #[must_use]
+    pub fn network(mut self, network: NetworkOptions) -> Self {
+        self.options.network = network;
+        self
+    }
+
+    /// Call `hook` with the write time of every stale cache entry the client
+    /// serves in place of live data.
+    #[must_use]
+    pub fn on_stale(mut self, hook: impl Fn(SystemTime) + Send + Sync + 'static) -> Self {
+        self.on_stale = Some(Arc::new(hook));
+        self
+    }
+
     /// Finalise the builder, creating an [`ApiClient`].
     ///
     /// # Errors
     ///
     /// Returns an error if the cache directory cannot be created or if the HTTP client fails to initialize.
     pub fn build(self) -> Result<ApiClient, ApiError> {
-        ApiClient::with_options(self.token, self.options)
+        let mut client = ApiClient::with_token_provider(self.tokens, self.options)?;
+        client.on_stale = self.on_stale;
+        Ok(client)
     }
 }
 
 /// Asynchronous Asana API client handling retries, rate limiting, and caching.
 pub struct ApiClient {
     http: reqwest::Client,
-    token: AuthToken,
+    tokens: Arc<dyn AccessTokenProvider>,
     options: ApiClientOptions,
     memory_cache: Arc<RwLock<HashMap<String, CacheEntry>>>,
     offline: AtomicBool,
     rate_limit: Arc<RwLock<Option<RateLimitInfo>>>,
+    rate_limiter: Option<RateLimiter>,
+    cassette: Option<Arc<Cassette>>,
+    har: Option<Arc<HarRecorder>>,
+    access_index: Arc<Mutex<cache::AccessIndex>>,
+    listing_state: Option<ListingState>,
+    stale_as_of: Arc<std::sync::Mutex<Option<SystemTime>>>,
+    on_stale: Option<StaleHook>,
 }
 
 impl Clone for ApiClient {
     fn clone(&self) -> Self {
         Self {
             http: self.http.clone(),
-            token: self.token.clone(),
+            tokens: Arc::clone(&self.tokens),
             options: self.options.clone(),
             memory_cache: Arc::clone(&self.memory_cache),
             offline: AtomicBool::new(self.offline.load(Ordering::Relaxed)),
             rate_limit: Arc::clone(&self.rate_limit),
+            rate_limiter: self.rate_limiter.clone(),
+            cassette: self.cassette.clone(),
+            har: self.har.clone(),
+            access_index: Arc::clone(&self.access_index),
+            listing_state: self.listing_state.clone(),
+            stale_as_of: Arc::clone(&self.stale_as_of),
+            on_stale: self.on_stale.clone(),
         }
     }
 }
@@ -278,6 +495,18 @@ impl ApiClient {
     ///
     /// Returns an error if the cache directory cannot be created or if the HTTP client fails to initialize.
     pub fn with_options(token: AuthToken, options: ApiClientOptions) -> Result<Self, ApiError> {
+        Self::with_token_provider(Arc::new(StaticTokenProvider::new(token)), options)
+    }
+
+    /// Construct a client that obtains tokens from `tokens`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be created or if the HTTP client fails to initialize.
+    pub fn with_token_provider(
+        tokens: Arc<dyn AccessTokenProvider>,
+        options: ApiClientOptions,
+    ) -> Result<Self, ApiError> {
         std::fs::create_dir_all(&options.cache_dir)?;
 
         let mut default_headers = HeaderMap::new();
@@ -286,23 +515,69 @@ impl ApiClient {
             .unwrap_or_else(|_| HeaderValue::from_static("asana-cli"));
         default_headers.insert(USER_AGENT, user_agent_value);
 
-        let http = reqwest::Client::builder()
-            .timeout(options.timeout)
-            .connect_timeout(Duration::from_secs(10))
-            .default_headers(default_headers)
+        let http = options
+            .network
+            .apply(
+                reqwest::Client::builder()
+                    .timeout(options.timeout)
+                    .connect_timeout(Duration::from_secs(10))
+                    .default_headers(default_headers),
+            )?
             .build()?;
 
         let offline = options.offline;
+        let rate_limiter = options
+            .proactive_rate_limit
+            .then(|| RateLimiter::new(&options.cache_dir));
+        let cassette = options
+            .cassette
+            .clone()
+            .map(Cassette::new)
+            .transpose()?
+            .map(Arc::new);
+        let har = options
+            .trace_http
+            .as_deref()
+            .map(HarRecorder::shared)
+            .transpose()?;
         Ok(Self {
             http,
-            token,
+            tokens,
             options,
             memory_cache: Arc::new(RwLock::new(HashMap::new())),
             offline: AtomicBool::new(offline),
             rate_limit: Arc::new(RwLock::new(None)),
+            rate_limiter,
+            cassette,
+            har,
+            access_index: Arc::default(),
+            listing_state: None,
+            stale_as_of: Arc::default(),
+            on_stale: None,
         })
     }
 
+    /// Return a client whose listings record their paging state in `state`.
+    ///
+    /// Each listing overwrites the next offset, so track one listing per
+    /// state to know where it can resume.
+    #[must_use]
+    pub fn with_listing_state(&self, state: &ListingState) -> Self {
+        Self {
+            listing_state: Some(state.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Return a client whose listings do not touch the caller's
+    /// [`ListingState`], for listings nested inside a tracked one.
+    pub(crate) fn without_listing_state(&self) -> Self {
+        Self {
+            listing_state: None,
+            ..self.clone()
+        }
+    }
+
     /// Update offline mode at runtime.
     pub fn set_offline(&self, offline: bool) {
         self.offline.store(offline, Ordering::Relaxed);
@@ -314,6 +589,30 @@ impl ApiClient {
         self.offline.load(Ordering::Relaxed)
     }
 
+    /// Write time of the oldest stale cache entry this client (or a clone of
+    /// it) served in place of live data.
+    #[must_use]
+    pub fn served_stale(&self) -> Option<SystemTime> {
+        *self
+            .stale_as_of
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Record that a stale response written at `stored_at` was served.
+    fn note_stale(&self, stored_at: SystemTime) {
+        {
+            let mut oldest = self
+                .stale_as_of
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            *oldest = Some(oldest.map_or(stored_at, |current| current.min(stored_at)));
+        }
+        if let Some(hook) = &self.on_stale {
+            hook(stored_at);
+        }
+    }
+
     /// Retrieve the most recent rate-limit information captured from the API.
     #[must_use]
     pub async fn rate_limit_info(&self) -> Option<RateLimitInfo> {
@@ -448,11 +747,80 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Send an arbitrary request and return the decoded JSON response, for
+    /// endpoints without a typed helper. An empty response body yields `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails, the network request fails, or
+    /// the response is not JSON.
+    pub async fn request_json(
+        &self,
+        method: Method,
+        path: &str,
+        query_pairs: Vec<(String, String)>,
+        body: Option<Value>,
+    ) -> Result<Value, ApiError> {
+        let bytes = self.execute(method, path, query_pairs, body).await?;
+        if bytes.is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Items requested per page from paginated endpoints, if configured.
+    #[must_use]
+    pub const fn page_size(&self) -> Option<usize> {
+        self.options.page_size
+    }
+
+    /// Submit several actions through Asana's `/batch` endpoint.
+    ///
+    /// Actions are sent in chunks of [`MAX_BATCH_ACTIONS`]. The returned vector
+    /// holds one entry per action, in input order, carrying either the action's
+    /// response body or the error mapped from its individual status code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a batch request as a whole fails; failures of
+    /// individual actions are reported inside the returned vector instead.
+    pub async fn batch(
+        &self,
+        actions: &[BatchAction],
+    ) -> Result<Vec<Result<Value, ApiError>>, ApiError> {
+        let mut results = Vec::with_capacity(actions.len());
+        for chunk in actions.chunks(MAX_BATCH_ACTIONS) {
+            let request = BatchRequest {
+                data: BatchRequestData { actions: chunk },
+            };
+            let response: BatchResponse = self.post_json("/batch", &request).await?;
+            if response.data.len() != chunk.len() {
+                return Err(ApiError::Other(format!(
+                    "batch response contained {} results for {} actions",
+                    response.data.len(),
+                    chunk.len()
+                )));
+            }
+            results.extend(
+                response
+                    .data
+                    .into_iter()
+                    .map(BatchActionResponse::into_result),
+            );
+        }
+        Ok(results)
+    }
+
     /// POST multipart form data.
     ///
+    /// A form can only be sent once, so a failed attempt is not retried; use
+    /// [`post_multipart_with`](Self::post_multipart_with) for uploads that
+    /// should be.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the network request fails or the response cannot be deserialized.
+    /// Returns an error if the request fails or the response cannot be
+    /// deserialized.
     pub async fn post_multipart<R>(
         &self,
         path: &str,
@@ -461,59 +829,243 @@ impl ApiClient {
     where
         R: DeserializeOwned,
     {
-        let url = format!("{}{path}", self.options.base_url);
+        let form = std::sync::Mutex::new(Some(form));
+        self.post_multipart_with(path, || {
+            form.lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .take()
+                .ok_or_else(|| {
+                    ApiError::Other("multipart upload failed and the form cannot be resent".into())
+                })
+        })
+        .await
+    }
+
+    /// POST multipart form data built by `make_form`.
+    ///
+    /// `make_form` is called for every attempt so that streamed file parts can
+    /// be reopened when a retry is needed. Dependent cache entries are
+    /// invalidated on success, as for any other mutation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the form cannot be built, the request fails after
+    /// retries, or the response cannot be deserialized.
+    pub async fn post_multipart_with<R, F>(&self, path: &str, make_form: F) -> Result<R, ApiError>
+    where
+        R: DeserializeOwned,
+        F: Fn() -> Result<reqwest::multipart::Form, ApiError> + Send + Sync,
+    {
+        if self.is_offline() {
+            return Err(ApiError::Offline {
+                resource: path.to_string(),
+            });
+        }
 
+        let url = self.build_url(path);
         let response = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.token.expose()))
-            .multipart(form)
-            .send()
+            .send_with_retry(
+                |token| {
+                    Ok(self
+                        .authorized(Method::POST, &url, token)
+                        .multipart(make_form()?))
+                },
+                true,
+            )
             .await?;
-
-        let status = response.status();
         let bytes = response.bytes().await?;
-
-        if !status.is_success() {
-            let message = String::from_utf8_lossy(&bytes).to_string();
-            return Err(ApiError::Http {
-                status,
-                message,
-                details: None,
-            });
-        }
+        let gids = gids_for_mutation(path, &[], None, &bytes);
+        self.invalidate_cache(&gids).await;
 
         Self::parse_response(path, &bytes)
     }
 
-    /// Download file from URL.
+    /// Download a file into memory.
+    ///
+    /// Relative paths are resolved against the base URL; absolute URLs (such
+    /// as pre-signed attachment links) are fetched as-is. Use
+    /// [`download_file_to`](Self::download_file_to) to stream large files to
+    /// disk instead.
     ///
     /// # Errors
     ///
     /// Returns an error if the network request fails or the download fails.
     pub async fn download_file(&self, url: &str) -> Result<Vec<u8>, ApiError> {
+        if self.is_offline() {
+            return Err(ApiError::Offline {
+                resource: url.to_string(),
+            });
+        }
+
+        let url = self.build_url(url);
         let response = self
-            .http
-            .get(url)
-            .header("Authorization", format!("Bearer {}", self.token.expose()))
-            .send()
+            .send_with_retry(|token| Ok(self.authorized(Method::GET, &url, token)), false)
             .await?;
+        Ok(response.bytes().await?.to_vec())
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let message = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Download failed".to_string());
-            return Err(ApiError::Http {
-                status,
-                message,
-                details: None,
+    /// Stream a file to `destination`, resuming interrupted transfers.
+    ///
+    /// Bytes are written to `<destination>.part` and moved into place once the
+    /// transfer completes. The response's strong `ETag` (or `Last-Modified`)
+    /// is kept in `<destination>.part.validator`, and an existing partial file
+    /// is resumed with a `Range` request carrying it in `If-Range`, so a file
+    /// that changed in the meantime is fetched from zero instead of spliced
+    /// onto the old bytes. Partial files without a validator restart as well.
+    /// When `expected_size` is known the final length must match it.
+    ///
+    /// Failed requests are retried like any other call; a body cut off
+    /// mid-transfer is resumed up to the configured retry count.
+    ///
+    /// Relative paths are resolved against the base URL; absolute URLs (such
+    /// as pre-signed attachment links) are fetched as-is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails after retries, the file cannot be
+    /// written, or the downloaded size does not match `expected_size`.
+    pub async fn download_file_to(
+        &self,
+        url: &str,
+        destination: &Path,
+        expected_size: Option<u64>,
+        progress: Option<&dyn TransferProgress>,
+    ) -> Result<u64, ApiError> {
+        if self.is_offline() {
+            return Err(ApiError::Offline {
+                resource: url.to_string(),
             });
         }
 
-        let bytes = response.bytes().await?;
-        Ok(bytes.to_vec())
+        let url = self.build_url(url);
+        let partial = partial_download_path(destination);
+        let validator = download_validator_path(destination);
+        let mut resumes = 0usize;
+        let written = loop {
+            match self
+                .download_attempt(&url, &partial, &validator, progress)
+                .await?
+            {
+                Transfer::Complete(written) => break written,
+                Transfer::Interrupted(err) if resumes < self.options.max_retries => {
+                    let delay = self.backoff_delay(resumes);
+                    warn!("download interrupted: {err}; resuming after {delay:?}");
+                    sleep(delay).await;
+                    resumes += 1;
+                }
+                Transfer::Interrupted(err) => return Err(ApiError::Network(err)),
+            }
+        };
+
+        if let Some(expected) = expected_size {
+            if written > expected {
+                fs::remove_file(&partial).await.ok();
+                fs::remove_file(&validator).await.ok();
+            }
+            if written != expected {
+                return Err(ApiError::Other(format!(
+                    "downloaded {written} bytes but expected {expected}; \
+                     rerun the download to resume"
+                )));
+            }
+        }
+
+        fs::rename(&partial, destination)
+            .await
+            .map_err(|e| ApiError::Other(format!("failed to write file: {e}")))?;
+        fs::remove_file(&validator).await.ok();
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+        Ok(written)
+    }
+
+    /// Fetch the remainder of a download into `partial`.
+    async fn download_attempt(
+        &self,
+        url: &str,
+        partial: &Path,
+        validator_path: &Path,
+        progress: Option<&dyn TransferProgress>,
+    ) -> Result<Transfer, ApiError> {
+        let resume = match fs::metadata(partial).await {
+            Ok(meta) if meta.len() > 0 => fs::read_to_string(validator_path)
+                .await
+                .ok()
+                .map(|validator| (meta.len(), validator)),
+            _ => None,
+        };
+        let offset = resume.as_ref().map_or(0, |(offset, _)| *offset);
+        let response = self
+            .send_with_retry(
+                |token| {
+                    let request = self.authorized(Method::GET, url, token);
+                    Ok(match &resume {
+                        Some((offset, validator)) => request
+                            .header(RANGE, format!("bytes={offset}-"))
+                            .header(IF_RANGE, validator.as_str()),
+                        None => request,
+                    })
+                },
+                false,
+            )
+            .await;
+        let mut response = match response {
+            // The partial file already holds the whole body.
+            Err(ApiError::Http { status, .. })
+                if offset > 0 && status == StatusCode::RANGE_NOT_SATISFIABLE =>
+            {
+                return Ok(Transfer::Complete(offset));
+            }
+            other => other?,
+        };
+
+        let write_error = |e: std::io::Error| ApiError::Other(format!("failed to write file: {e}"));
+        let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        if !resumed {
+            match range_validator(response.headers()) {
+                Some(validator) => fs::write(validator_path, validator)
+                    .await
+                    .map_err(write_error)?,
+                None => {
+                    fs::remove_file(validator_path).await.ok();
+                }
+            }
+        }
+        let mut written = if resumed { offset } else { 0 };
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(partial)
+            .await
+            .map_err(write_error)?;
+        if resumed {
+            debug!("resuming download at byte {offset}");
+        }
+
+        if let Some(progress) = progress {
+            let total = response.content_length().map(|len| len + written);
+            progress.start(written, total);
+        }
+        loop {
+            let chunk = match response.chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(err) => {
+                    file.flush().await.map_err(write_error)?;
+                    return Ok(Transfer::Interrupted(err));
+                }
+            };
+            file.write_all(&chunk).await.map_err(write_error)?;
+            written += chunk.len() as u64;
+            if let Some(progress) = progress {
+                progress.advance(chunk.len() as u64);
+            }
+        }
+        file.flush().await.map_err(write_error)?;
+        Ok(Transfer::Complete(written))
     }
 
     /// Stream paginated endpoints as a series of pages (`Vec<T>`).
@@ -529,6 +1081,16 @@ impl ApiClient {
     }
 
     /// Stream paginated endpoints with an optional global item limit.
+    ///
+    /// Pages request the configured page size, shrunk to the items still
+    /// needed so a limited listing ends on a page boundary and the recorded
+    /// [`ListingState::next_offset`] resumes exactly after it. An `offset` pair in
+    /// `query` starts the listing from that token instead of the first page.
+    ///
+    /// When Asana expires the offset token mid-listing, the listing restarts
+    /// from the first page and skips items (by gid) that were already yielded.
+    /// Listings that began from a caller-supplied offset cannot restart and
+    /// fail with [`PaginationExpired`] instead.
     pub fn paginate_with_limit<T>(
         &self,
         path: impl Into<String>,
@@ -539,32 +1101,58 @@ impl ApiClient {
         T: DeserializeOwned + Send + 'static,
     {
         let path = path.into();
-        let client = self.clone();
+        let mut client = self.clone();
+        let mut query = query;
+        let start_offset = query
+            .iter()
+            .position(|(name, _)| name == "offset")
+            .map(|index| query.remove(index).1);
 
         try_stream! {
-            let mut next_offset: Option<String> = None;
+            let resumable = start_offset.is_none();
+            let mut next_offset = start_offset;
             let mut emitted: usize = 0;
+            let mut seen: HashSet<String> = HashSet::new();
+            let mut restarts = 0;
             loop {
-                if let Some(max) = max_items {
-                    if emitted >= max {
-                        break;
-                    }
+                let remaining = max_items.map(|max| max.saturating_sub(emitted));
+                if remaining == Some(0) {
+                    break;
                 }
 
                 let mut query_pairs = query.clone();
+                if let Some(size) = page_limit(client.options.page_size, remaining) {
+                    query_pairs.push(("limit".to_string(), size.to_string()));
+                }
                 if let Some(offset) = next_offset.clone() {
                     query_pairs.push(("offset".to_string(), offset));
                 }
 
-                let response: ListResponse<T> = match client
+                let response: ListResponse<Value> = match client
                     .get_json_with_pairs(&path, query_pairs.clone())
                     .await
                 {
                     Ok(resp) => resp,
-                    Err(ApiError::Http { status: StatusCode::BAD_REQUEST, details, message })
-                        if is_offset_expired(details.as_ref(), &message) =>
+                    Err(ApiError::Http { status: StatusCode::BAD_REQUEST, details, message, .. })
+                        if next_offset.is_some() && is_offset_expired(details.as_ref(), &message) =>
                     {
-                        break;
+                        if !resumable || restarts >= MAX_PAGINATION_RESTARTS {
+                            if let Some(state) = &client.listing_state {
+                                state.note_next_offset(None);
+                            }
+                            Err(PaginationExpired { path: path.clone(), emitted })?;
+                        }
+                        restarts += 1;
+                        if let Some(state) = &client.listing_state {
+                            state.note_restart();
+                        }
+                        // Cached pages carry the expired token, so refetch them.
+                        client.options.refresh_cache = true;
+                        warn!(
+                            "offset for {path} expired after {emitted} items; restarting from the first page"
+                        );
+                        next_offset = None;
+                        continue;
                     }
                     Err(err) => {
                         Err(err)?;
@@ -572,21 +1160,31 @@ impl ApiClient {
                     }
                 };
 
-                let mut items = response.data;
                 let next_offset_candidate = response
                     .next_page
                     .as_ref()
                     .and_then(|meta| meta.offset.clone());
 
-                if let Some(max) = max_items {
-                    if emitted + items.len() > max {
-                        items.truncate(max - emitted);
+                let mut items = Vec::with_capacity(response.data.len());
+                for value in response.data {
+                    let gid = value.get("gid").and_then(Value::as_str).map(str::to_owned);
+                    if let Some(gid) = gid {
+                        if !seen.insert(gid) {
+                            continue;
+                        }
                     }
+                    items.push(serde_json::from_value::<T>(value)?);
+                }
+                if let Some(remaining) = remaining {
+                    items.truncate(remaining);
                 }
 
                 emitted += items.len();
                 let continue_after_page = next_offset_candidate.is_some()
                     && max_items.is_none_or(|max| emitted < max);
+                if let Some(state) = &client.listing_state {
+                    state.note_next_offset(next_offset_candidate.clone());
+                }
 
                 yield items;
 
@@ -599,6 +1197,28 @@ impl ApiClient {
         }
     }
 
+    /// Stream individual items of a paginated endpoint, flattening the pages
+    /// produced by [`Self::paginate_with_limit`].
+    pub fn paginate_items<T>(
+        &self,
+        path: impl Into<String>,
+        query: Vec<(String, String)>,
+        max_items: Option<usize>,
+    ) -> impl Stream<Item = Result<T, ApiError>> + '_
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let pages = self.paginate_with_limit::<T>(path, query, max_items);
+        try_stream! {
+            pin_mut!(pages);
+            while let Some(page) = pages.next().await {
+                for item in page? {
+                    yield item;
+                }
+            }
+        }
+    }
+
     pub(crate) async fn get_json_with_pairs<T>(
         &self,
         path: &str,
@@ -640,7 +1260,7 @@ impl ApiClient {
         Ok(serde_json::from_value::<T>(value)?)
     }
 
-    #[allow(clippy::too_many_lines)]
+    /// Route a request through the cassette when one is configured.
     async fn execute(
         &self,
         method: Method,
@@ -648,38 +1268,193 @@ impl ApiClient {
         query_pairs: Vec<(String, String)>,
         body: Option<Value>,
     ) -> Result<Vec<u8>, ApiError> {
-        let mut cache_key = None;
-        if method == Method::GET {
-            let key = Self::build_cache_key(&method, path, &query_pairs);
-            if let Some(bytes) = self.get_from_cache(&key).await? {
-                return Ok(bytes);
-            }
-            cache_key = Some(key);
+        let Some(cassette) = self.cassette.as_deref() else {
+            return self.execute_live(method, path, query_pairs, body).await;
+        };
+        if cassette.is_replay() {
+            return cassette
+                .replay(&method, path, &query_pairs, body.as_ref())
+                .await;
+        }
+
+        let result = self
+            .execute_live(method.clone(), path, query_pairs.clone(), body.clone())
+            .await;
+        let token = self.tokens.access_token().await.ok();
+        if let Err(err) = cassette
+            .record(
+                &method,
+                path,
+                &query_pairs,
+                body.as_ref(),
+                &result,
+                token.as_ref().map_or("", AuthToken::expose),
+            )
+            .await
+        {
+            warn!("failed to record {method} {path}: {err}");
+        }
+        result
+    }
+
+    async fn execute_live(
+        &self,
+        method: Method,
+        path: &str,
+        query_pairs: Vec<(String, String)>,
+        body: Option<Value>,
+    ) -> Result<Vec<u8>, ApiError> {
+        if method != Method::GET {
+            return self.send(method, path, &query_pairs, body, None).await;
+        }
+        if is_uncacheable(path) {
             if self.is_offline() {
                 return Err(ApiError::Offline {
                     resource: path.to_string(),
                 });
             }
+            return self.send(method, path, &query_pairs, body, None).await;
+        }
+
+        let key = Self::build_cache_key(&method, path, &query_pairs);
+        let mut stale = None;
+        if !self.options.refresh_cache {
+            match self.get_from_cache(&key).await? {
+                CacheLookup::Fresh(bytes) => {
+                    cache::record_lookup(&self.options.cache_dir, true).await;
+                    self.trace_cache_hit(&method, path, &query_pairs, &bytes, "cache");
+                    return Ok(bytes);
+                }
+                CacheLookup::Stale { body, stored_at } => stale = Some((body, stored_at)),
+                CacheLookup::Miss => {}
+            }
         }
 
+        if self.is_offline() {
+            cache::record_lookup(&self.options.cache_dir, stale.is_some()).await;
+            return match stale {
+                Some((body, stored_at)) => {
+                    self.note_stale(stored_at);
+                    self.trace_cache_hit(&method, path, &query_pairs, &body, "stale");
+                    Ok(body)
+                }
+                None => Err(ApiError::Offline {
+                    resource: path.to_string(),
+                }),
+            };
+        }
+
+        cache::record_lookup(&self.options.cache_dir, false).await;
+        match self
+            .send(method.clone(), path, &query_pairs, body, Some(&key))
+            .await
+        {
+            Err(err) if self.options.stale_fallback && is_unavailable(&err) => {
+                let Some((body, stored_at)) = stale else {
+                    return Err(err);
+                };
+                warn!("serving stale cache for {path} after request failed: {err}");
+                self.note_stale(stored_at);
+                self.trace_cache_hit(&method, path, &query_pairs, &body, "stale");
+                Ok(body)
+            }
+            result => result,
+        }
+    }
+
+    fn trace_cache_hit(
+        &self,
+        method: &Method,
+        path: &str,
+        query_pairs: &[(String, String)],
+        body: &[u8],
+        source: &'static str,
+    ) {
+        if let Some(har) = &self.har {
+            let url = self.build_url(path);
+            har.record(&HarEntry::cache_hit(
+                method,
+                &url,
+                query_pairs,
+                body,
+                source,
+            ));
+        }
+    }
+
+    /// Send a request with retry handling, caching successful GETs under
+    /// `cache_key` and invalidating dependent entries after mutations.
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query_pairs: &[(String, String)],
+        body: Option<Value>,
+        cache_key: Option<&str>,
+    ) -> Result<Vec<u8>, ApiError> {
         let url = self.build_url(path);
+        let response = self
+            .send_with_retry(
+                |token| {
+                    let mut request = self.authorized(method.clone(), &url, token);
+                    if !query_pairs.is_empty() {
+                        request = request.query(query_pairs);
+                    }
+                    if let Some(ref json) = body {
+                        request = request.json(json);
+                    }
+                    Ok(request)
+                },
+                true,
+            )
+            .await?;
+
+        let bytes = response.bytes().await?.to_vec();
+        if let Some(key) = cache_key {
+            let gids = gids_for_response(path, query_pairs, &bytes);
+            self.write_cache(key, &bytes, gids).await?;
+        } else if method != Method::GET {
+            let gids = gids_for_mutation(path, query_pairs, body.as_ref(), &bytes);
+            self.invalidate_cache(&gids).await;
+        }
+        Ok(bytes)
+    }
+
+    /// Apply the shared resilience policy to a request: proactive pacing,
+    /// retries for timeouts, 429s, and 5xx responses, rate-limit tracking, and
+    /// mapping failures onto [`ApiError`].
+    ///
+    /// `build` is called once per attempt, so bodies that cannot be cloned
+    /// (such as streamed uploads) are recreated for every retry. When HTTP
+    /// tracing is enabled each attempt is traced; `capture_body` controls
+    /// whether successful response bodies are buffered into the trace, which
+    /// streaming callers such as downloads turn off.
+    async fn send_with_retry<F>(
+        &self,
+        build: F,
+        capture_body: bool,
+    ) -> Result<reqwest::Response, ApiError>
+    where
+        F: Fn(&AuthToken) -> Result<RequestBuilder, ApiError> + Send + Sync,
+    {
         let mut attempt = 0usize;
         let max_retries = self.options.max_retries;
-        let body_clone = body.clone();
+        let mut refreshed = false;
 
         loop {
-            let mut request = self.http.request(method.clone(), &url);
-            request = request.header(AUTHORIZATION, format!("Bearer {}", self.token.expose()));
-            if !query_pairs.is_empty() {
-                request = request.query(&query_pairs);
-            }
-            if let Some(ref json) = body_clone {
-                request = request.json(json);
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
             }
 
-            let response = request.send().await;
-            match response {
+            let token = self.tokens.access_token().await?;
+            let request = build(&token)?.build()?;
+            let trace = self.har.as_ref().map(|_| PendingEntry::new(&request));
+            let started = Instant::now();
+            let resp = match self.http.execute(request).await {
                 Err(err) => {
+                    if let (Some(har), Some(trace)) = (&self.har, trace) {
+                        har.record(&trace.fail(&err, started.elapsed()));
+                    }
                     if (err.is_timeout() || err.is_connect()) && attempt < max_retries {
                         let delay = self.backoff_delay(attempt);
                         debug!("retrying after network error: {err}; sleeping {delay:?}");
@@ -689,76 +1464,129 @@ impl ApiClient {
                     }
                     return Err(ApiError::Network(err));
                 }
-                Ok(resp) => {
-                    if resp.status().is_success() {
-                        let headers = resp.headers().clone();
-                        let bytes = resp.bytes().await?.to_vec();
-                        if let Some(info) = Self::extract_rate_limit_headers(&headers) {
-                            let mut guard = self.rate_limit.write().await;
-                            *guard = Some(info);
-                        }
-                        if let Some(ref key) = cache_key {
-                            self.write_cache(key, &bytes).await?;
-                        }
-                        return Ok(bytes);
-                    }
+                Ok(resp) => resp,
+            };
+            let wait = started.elapsed();
 
-                    let status = resp.status();
-
-                    if status == StatusCode::TOO_MANY_REQUESTS {
-                        if let Some(info) = Self::extract_rate_limit_headers(resp.headers()) {
-                            let mut guard = self.rate_limit.write().await;
-                            *guard = Some(info.clone());
-                        }
-                        let retry_after = Self::parse_retry_after(resp.headers())
-                            .unwrap_or_else(|| self.backoff_delay(attempt));
-                        if attempt < max_retries {
-                            debug!(
-                                "rate limited, waiting {:?} before retry (attempt {})",
-                                retry_after,
-                                attempt + 1
-                            );
-                            sleep(retry_after).await;
-                            attempt += 1;
-                            continue;
-                        }
-                        let body = resp.text().await.unwrap_or_default();
-                        return Err(ApiError::RateLimited { retry_after, body });
+            if let Some(info) = Self::extract_rate_limit_headers(resp.headers()) {
+                self.record_rate_limit(info).await;
+            }
+            let status = resp.status();
+            if status.is_success() {
+                return match (&self.har, trace) {
+                    (Some(har), Some(trace)) => {
+                        Self::trace_success(har, trace, resp, wait, capture_body).await
                     }
+                    _ => Ok(resp),
+                };
+            }
 
-                    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
-                        let body = resp.text().await.unwrap_or_default();
-                        return Err(ApiError::Authentication(body));
-                    }
+            let version = resp.version();
+            let headers = resp.headers().clone();
+            let text = resp.text().await.unwrap_or_default();
+            if let (Some(har), Some(trace)) = (&self.har, trace) {
+                let receive = started.elapsed().saturating_sub(wait);
+                har.record(&trace.respond(
+                    status,
+                    version,
+                    &headers,
+                    Some(text.as_bytes()),
+                    wait,
+                    receive,
+                ));
+            }
 
-                    if status.is_server_error() && attempt < max_retries {
-                        let delay = self.backoff_delay(attempt);
-                        warn!("server error {status}; retrying after {delay:?}");
-                        sleep(delay).await;
-                        attempt += 1;
-                        continue;
-                    }
+            if status == StatusCode::UNAUTHORIZED
+                && !refreshed
+                && self.tokens.refresh(&token).await?
+            {
+                debug!("access token rejected; retrying with a refreshed token");
+                refreshed = true;
+                continue;
+            }
 
-                    let text = resp.text().await.unwrap_or_default();
-                    let details = serde_json::from_str::<Value>(&text).ok();
-                    return Err(ApiError::http(
-                        status,
-                        if text.is_empty() {
-                            status
-                                .canonical_reason()
-                                .unwrap_or("unknown error")
-                                .to_string()
-                        } else {
-                            text
-                        },
-                        details,
-                    ));
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = Self::parse_retry_after(&headers)
+                    .unwrap_or_else(|| self.backoff_delay(attempt));
+                if attempt < max_retries {
+                    debug!(
+                        "rate limited, waiting {:?} before retry (attempt {})",
+                        retry_after,
+                        attempt + 1
+                    );
+                    sleep(retry_after).await;
+                    attempt += 1;
+                    continue;
                 }
+                return Err(ApiError::RateLimited {
+                    retry_after,
+                    body: text,
+                });
+            }
+
+            if status.is_server_error() && attempt < max_retries {
+                let delay = self.backoff_delay(attempt);
+                warn!("server error {status}; retrying after {delay:?}");
+                sleep(delay).await;
+                attempt += 1;
+                continue;
             }
+
+            return Err(ApiError::from_status(status, text));
+        }
+    }
+
+    /// Trace a successful response. Captured bodies are buffered and handed
+    /// back to the caller in a rebuilt response.
+    async fn trace_success(
+        har: &HarRecorder,
+        trace: PendingEntry,
+        resp: reqwest::Response,
+        wait: Duration,
+        capture_body: bool,
+    ) -> Result<reqwest::Response, ApiError> {
+        let status = resp.status();
+        let version = resp.version();
+        let headers = resp.headers().clone();
+        if !capture_body {
+            har.record(&trace.respond(status, version, &headers, None, wait, Duration::ZERO));
+            return Ok(resp);
+        }
+
+        let started = Instant::now();
+        let body = resp.bytes().await?;
+        har.record(&trace.respond(
+            status,
+            version,
+            &headers,
+            Some(&body),
+            wait,
+            started.elapsed(),
+        ));
+        let mut rebuilt = http::Response::new(body);
+        *rebuilt.status_mut() = status;
+        *rebuilt.version_mut() = version;
+        *rebuilt.headers_mut() = headers;
+        Ok(reqwest::Response::from(rebuilt))
+    }
+
+    /// Start a request carrying the access token.
+    ///
+    /// The token is only attached to URLs under the configured base URL so
+    /// that pre-signed download links never receive it.
+    fn authorized(&self, method: Method, url: &str, token: &AuthToken) -> RequestBuilder {
+        let request = self.http.request(method, url);
+        if url.starts_with(self.options.base_url.trim_end_matches('/')) {
+            request.header(AUTHORIZATION, format!("Bearer {}", token.expose()))
+        } else {
+            request
         }
     }
 
     fn build_url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return path.to_string();
+        }
         let trimmed_base = self.options.base_url.trim_end_matches('/');
         let trimmed_path = path.trim_start_matches('/');
         format!("{trimmed_base}/{trimmed_path}")
@@ -780,7 +1608,9 @@ impl ApiClient {
         format!("{:x}", hasher.finalize())
     }
 
-    async fn get_from_cache(&self, key: &str) -> Result<Option<Vec<u8>>, ApiError> {
+    /// Look up a cached response. Expired disk entries younger than
+    /// `stale_max_age` are reported as stale instead of being deleted.
+    async fn get_from_cache(&self, key: &str) -> Result<CacheLookup, ApiError> {
         let now = Instant::now();
         if let Some(entry) = {
             let guard = self.memory_cache.read().await;
@@ -788,45 +1618,67 @@ impl ApiClient {
         } {
             if entry.expires_at > now {
                 debug!("cache hit (memory) for {key}");
-                return Ok(Some((*entry.value).clone()));
+                return Ok(CacheLookup::Fresh((*entry.value).clone()));
             }
         }
 
         let path = self.cache_file_path(key);
-        match fs::read(&path).await {
-            Ok(bytes) => {
-                match serde_json::from_slice::<DiskCacheEntry>(&bytes) {
-                    Ok(entry) => {
-                        let expires_at = UNIX_EPOCH + Duration::from_secs(entry.expires_at);
-                        if SystemTime::now() <= expires_at {
-                            match general_purpose::STANDARD.decode(entry.body) {
-                                Ok(body) => {
-                                    self.store_in_memory(key.to_string(), body.clone());
-                                    return Ok(Some(body));
-                                }
-                                Err(err) => {
-                                    warn!("failed to decode cache entry: {err}");
-                                    fs::remove_file(&path).await.ok();
-                                }
-                            }
-                        } else {
-                            fs::remove_file(&path).await.ok();
-                        }
-                    }
-                    Err(err) => {
-                        warn!("failed to parse cache entry: {err}");
-                        fs::remove_file(&path).await.ok();
-                    }
-                }
-                Ok(None)
+        let bytes = match fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(CacheLookup::Miss);
+            }
+            Err(err) => return Err(ApiError::Cache(err)),
+        };
+
+        let entry = match serde_json::from_slice::<DiskCacheEntry>(&bytes) {
+            Ok(entry) => entry,
+            Err(err) => {
+                warn!("failed to parse cache entry: {err}");
+                cache::remove_entry(&self.options.cache_dir, &path)
+                    .await
+                    .ok();
+                return Ok(CacheLookup::Miss);
+            }
+        };
+
+        let now = SystemTime::now();
+        let fresh = !entry.is_expired(now);
+        let stored_at = entry.stored_at();
+        let retained = now
+            .duration_since(stored_at)
+            .is_ok_and(|age| age <= self.options.stale_max_age);
+        if !fresh && !retained {
+            cache::remove_entry(&self.options.cache_dir, &path)
+                .await
+                .ok();
+            return Ok(CacheLookup::Miss);
+        }
+        cache::record_access(&self.options.cache_dir, key).await;
+
+        match general_purpose::STANDARD.decode(entry.body) {
+            Ok(body) if fresh => {
+                self.store_in_memory(key.to_string(), body.clone(), entry.gids);
+                Ok(CacheLookup::Fresh(body))
+            }
+            Ok(body) => Ok(CacheLookup::Stale { body, stored_at }),
+            Err(err) => {
+                warn!("failed to decode cache entry: {err}");
+                cache::remove_entry(&self.options.cache_dir, &path)
+                    .await
+                    .ok();
+                Ok(CacheLookup::Miss)
             }
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
-            Err(err) => Err(ApiError::Cache(err)),
         }
     }
 
-    async fn write_cache(&self, key: &str, body: &[u8]) -> Result<(), ApiError> {
-        self.store_in_memory(key.to_string(), body.to_vec());
+    async fn write_cache(
+        &self,
+        key: &str,
+        body: &[u8],
+        gids: BTreeSet<String>,
+    ) -> Result<(), ApiError> {
+        self.store_in_memory(key.to_string(), body.to_vec(), gids.clone());
 
         let expires_at = SystemTime::now()
             .checked_add(self.options.cache_ttl)
@@ -843,7 +1695,11 @@ impl ApiClient {
 
         let entry = DiskCacheEntry {
             expires_at,
+            stored_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
             body: general_purpose::STANDARD.encode(body),
+            gids,
         };
 
         let path = self.cache_file_path(key);
@@ -851,16 +1707,31 @@ impl ApiClient {
             fs::create_dir_all(parent).await.ok();
         }
         let serialized = serde_json::to_vec(&entry)?;
+        let size = serialized.len() as u64;
         fs::write(path, serialized).await?;
+        cache::record_write(&self.options.cache_dir, key, size).await;
 
         debug!("cached response for key {key}");
+        if let Some(max_bytes) = self.options.cache_max_bytes {
+            let mut index = self.access_index.lock().await;
+            match cache::enforce_size_limit_with(&self.options.cache_dir, max_bytes, &mut index)
+                .await
+            {
+                Ok(0) => {}
+                Ok(evicted) => {
+                    debug!("evicted {evicted} cache entries to stay under {max_bytes} bytes");
+                }
+                Err(err) => warn!("failed to enforce cache size limit: {err}"),
+            }
+        }
         Ok(())
     }
 
-    fn store_in_memory(&self, key: String, body: Vec<u8>) {
+    fn store_in_memory(&self, key: String, body: Vec<u8>, gids: BTreeSet<String>) {
         let entry = CacheEntry {
             expires_at: Instant::now() + self.options.cache_ttl,
             value: Arc::new(body),
+            gids: Arc::new(gids),
         };
         let cache = self.memory_cache.clone();
         tokio::spawn(async move {
@@ -869,10 +1740,42 @@ impl ApiClient {
         });
     }
 
+    /// Evict every memory and disk entry that depends on one of `gids`.
+    ///
+    /// Invalidation is best effort: unreadable entries are left for the normal
+    /// expiry path and I/O errors are logged rather than failing the mutation.
+    async fn invalidate_cache(&self, gids: &BTreeSet<String>) {
+        if gids.is_empty() {
+            return;
+        }
+
+        {
+            let mut guard = self.memory_cache.write().await;
+            guard.retain(|_, entry| entry.gids.is_disjoint(gids));
+        }
+
+        let files = match cache::entry_files(&self.options.cache_dir).await {
+            Ok(files) => files,
+            Err(err) => {
+                warn!("failed to scan cache for invalidation: {err}");
+                return;
+            }
+        };
+        for path in files {
+            let Some(cached) = cache::read_entry(&path).await else {
+                continue;
+            };
+            if !cached.gids.is_disjoint(gids) {
+                debug!("invalidating cache entry {}", path.display());
+                cache::remove_entry(&self.options.cache_dir, &path)
+                    .await
+                    .ok();
+            }
+        }
+    }
+
     fn cache_file_path(&self, key: &str) -> PathBuf {
-        let mut filename = String::from(key);
-        filename.push_str(".json");
-        self.options.cache_dir.join(filename)
+        cache::entry_path(&self.options.cache_dir, key)
     }
 
     fn backoff_delay(&self, attempt: usize) -> Duration {
@@ -898,6 +1801,14 @@ impl ApiClient {
         })
     }
 
+    async fn record_rate_limit(&self, info: RateLimitInfo) {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.observe(&info).await;
+        }
+        let mut guard = self.rate_limit.write().await;
+        *guard = Some(info);
+    }
+
     fn extract_rate_limit_headers(headers: &HeaderMap) -> Option<RateLimitInfo> {
         let limit = headers
             .get("X-RateLimit-Limit")
@@ -926,6 +1837,73 @@ impl ApiClient {
     }
 }
 
+/// Sibling path holding an in-progress download.
+fn partial_download_path(destination: &Path) -> PathBuf {
+    let mut name = destination.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+/// Sibling path holding the `If-Range` validator of an in-progress download.
+fn download_validator_path(destination: &Path) -> PathBuf {
+    let mut name = destination.as_os_str().to_owned();
+    name.push(".part.validator");
+    PathBuf::from(name)
+}
+
+/// Validator that `If-Range` accepts: a strong `ETag`, else `Last-Modified`.
+fn range_validator(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| {
+            headers
+                .get(LAST_MODIFIED)
+                .and_then(|value| value.to_str().ok())
+        })
+}
+
+/// Outcome of one download request.
+enum Transfer {
+    /// The partial file holds this many bytes of the complete body.
+    Complete(u64),
+    /// The body was cut off; the partial file can be resumed.
+    Interrupted(reqwest::Error),
+}
+
+/// How often one listing may restart after its offset token expires.
+const MAX_PAGINATION_RESTARTS: usize = 2;
+
+/// Page size to request: the configured size (or Asana's maximum when only a
+/// limit is set), capped at the number of items still needed.
+fn page_limit(page_size: Option<usize>, remaining: Option<usize>) -> Option<usize> {
+    match (page_size, remaining) {
+        (None, None) => None,
+        (size, remaining) => Some(
+            size.unwrap_or(MAX_PAGE_SIZE)
+                .min(remaining.unwrap_or(MAX_PAGE_SIZE))
+                .clamp(1, MAX_PAGE_SIZE),
+        ),
+    }
+}
+
+/// GET endpoints whose responses depend on server-side state beyond the
+/// request itself, such as Events API sync tokens, and must never be cached.
+fn is_uncacheable(path: &str) -> bool {
+    path == "/events"
+}
+
+/// Whether a request failed because Asana could not be reached, making a
+/// stale cached response preferable to an error.
+fn is_unavailable(err: &ApiError) -> bool {
+    match err {
+        ApiError::Network(_) => true,
+        ApiError::Http { status, .. } => status.is_server_error(),
+        _ => false,
+    }
+}
+
 fn build_query_pairs(query: &[(&str, &str)]) -> Vec<(String, String)> {
     query
         .iter()
@@ -1001,6 +1979,41 @@ mod tests {
         drop(server);
     }
 
+    #[tokio::test]
+    async fn writes_evict_entries_cached_before_the_index_existed() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("GET", "/users/me")
+            .with_status(200)
+            .with_body(r#"{ "data": { "name": "Test User" } }"#)
+            .create_async()
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let cache_dir = tmp.path().join("cache");
+        let old_entries: Vec<PathBuf> = ['a', 'b']
+            .into_iter()
+            .map(|key| cache::entry_path(&cache_dir, &key.to_string().repeat(64)))
+            .collect();
+        for path in &old_entries {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, vec![b'x'; 1000]).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let client = ApiClient::builder(AuthToken::new(SecretString::new("test-token".into())))
+            .base_url(server.url())
+            .cache_dir(cache_dir.clone())
+            .cache_max_bytes(Some(500))
+            .build()
+            .unwrap();
+        let _user: Value = client.get_current_user().await.unwrap();
+
+        assert!(old_entries.iter().all(|path| !path.exists()));
+        assert_eq!(cache::entry_files(&cache_dir).await.unwrap().len(), 1);
+        drop(server);
+    }
+
     #[tokio::test]
     async fn rate_limit_retries_then_succeeds() {
         let mut server = Server::new_async().await;
@@ -1066,6 +2079,125 @@ mod tests {
         assert_eq!(cached["data"]["name"], "Cached User");
     }
 
+    #[tokio::test]
+    async fn expired_entries_serve_offline_and_as_fallback() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("GET", "/users/me")
+            .with_status(200)
+            .with_body(r#"{ "data": { "name": "Stale User" } }"#)
+            .create_async()
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let build = |base_url: String| {
+            ApiClient::builder(AuthToken::new(SecretString::new("stale-token".into())))
+                .base_url(base_url)
+                .cache_dir(tmp.path().join("cache"))
+                .cache_ttl(Duration::ZERO)
+                .max_retries(0)
+        };
+
+        let user: Value = build(server.url())
+            .build()
+            .unwrap()
+            .get_current_user()
+            .await
+            .unwrap();
+        assert_eq!(user["data"]["name"], "Stale User");
+        let url = server.url();
+        drop(server);
+
+        let offline: Value = build(url.clone())
+            .offline(true)
+            .build()
+            .unwrap()
+            .get_current_user()
+            .await
+            .unwrap();
+        assert_eq!(offline["data"]["name"], "Stale User");
+
+        let strict = build(url.clone()).build().unwrap().get_current_user().await;
+        assert!(strict.is_err());
+
+        let stale_hits = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let hook = Arc::clone(&stale_hits);
+        let fallback_client = build(url)
+            .stale_fallback(true)
+            .on_stale(move |stored_at| hook.lock().unwrap().push(stored_at))
+            .build()
+            .unwrap();
+        assert!(fallback_client.served_stale().is_none());
+        let fallback: Value = fallback_client.get_current_user().await.unwrap();
+        assert_eq!(fallback["data"]["name"], "Stale User");
+        assert!(fallback_client.served_stale().is_some());
+        assert_eq!(stale_hits.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn mutations_invalidate_dependent_cache_entries() {
+        let mut server = Server::new_async().await;
+        let task = server
+            .mock("GET", "/tasks/1")
+            .with_status(200)
+            .with_body(r#"{ "data": { "gid": "1", "name": "Old" } }"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let project_tasks = server
+            .mock("GET", "/projects/9/tasks")
+            .with_status(200)
+            .with_body(r#"{ "data": [{ "gid": "2", "name": "Sibling" }] }"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let tag = server
+            .mock("GET", "/tags/5")
+            .with_status(200)
+            .with_body(r#"{ "data": { "gid": "5", "name": "Unrelated" } }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let _update = server
+            .mock("PUT", "/tasks/1")
+            .with_status(200)
+            .with_body(r#"{ "data": { "gid": "1", "name": "New", "projects": [{ "gid": "9" }] } }"#)
+            .create_async()
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let build = || {
+            ApiClient::builder(AuthToken::new(SecretString::new("cache-token".into())))
+                .base_url(server.url())
+                .cache_dir(tmp.path().join("cache"))
+                .cache_ttl(Duration::from_secs(60))
+                .build()
+                .unwrap()
+        };
+
+        let client = build();
+        for path in ["/tasks/1", "/projects/9/tasks", "/tags/5"] {
+            let _: Value = client.get_json(path, &[]).await.unwrap();
+        }
+        let _: Value = client
+            .put_json(
+                "/tasks/1",
+                &serde_json::json!({ "data": { "name": "New" } }),
+            )
+            .await
+            .unwrap();
+
+        let _: Value = client.get_json("/tasks/1", &[]).await.unwrap();
+        let fresh = build();
+        for path in ["/projects/9/tasks", "/tags/5"] {
+            let _: Value = fresh.get_json(path, &[]).await.unwrap();
+        }
+
+        task.assert_async().await;
+        project_tasks.assert_async().await;
+        tag.assert_async().await;
+    }
+
     #[tokio::test]
     async fn rate_limit_headers_captured_on_success() {
         let mut server = Server::new_async().await;
@@ -1097,6 +2229,39 @@ mod tests {
         drop(server);
     }
 
+    #[tokio::test]
+    async fn exhausted_budget_paces_clients_sharing_cache_dir() {
+        let mut server = Server::new_async().await;
+        let _m = server
+            .mock("GET", "/users/me")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("X-RateLimit-Limit", "150")
+            .with_header("X-RateLimit-Remaining", "0")
+            .with_header("X-RateLimit-Reset", "1")
+            .with_body(r#"{ "data": { "name": "Budget User" } }"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let build = || {
+            ApiClient::builder(AuthToken::new(SecretString::new("budget-token".into())))
+                .base_url(server.url())
+                .cache_dir(tmp.path().join("cache"))
+                .build()
+                .unwrap()
+        };
+
+        build().get_current_user().await.unwrap();
+        let started = Instant::now();
+        build()
+            .get_json::<Value>("/users/me", &[("opt_fields", "name")])
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(500));
+    }
+
     #[tokio::test]
     async fn rate_limit_headers_captured_on_429() {
         let mut server = Server::new_async().await;
@@ -1140,6 +2305,7 @@ mod tests {
         let mut server = Server::new_async().await;
         let _first = server
             .mock("GET", "/items")
+            .match_query(Matcher::UrlEncoded("limit".into(), "1".into()))
             .with_status(200)
             .with_body(
                 r#"{
@@ -1184,10 +2350,11 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn paginate_handles_offset_expired() {
+    async fn paginate_restarts_when_offset_expires() {
         let mut server = Server::new_async().await;
         let _first = server
             .mock("GET", "/items")
+            .match_query(Matcher::Missing)
             .with_status(200)
             .with_body(
                 r#"{
@@ -1195,9 +2362,10 @@ mod tests {
                     "next_page": { "offset": "abc", "path": "/items" }
                 }"#,
             )
+            .expect(2)
             .create_async()
             .await;
-        let _second = server
+        let _expired = server
             .mock("GET", "/items")
             .match_query(Matcher::UrlEncoded("offset".into(), "abc".into()))
             .with_status(400)
@@ -1206,19 +2374,28 @@ mod tests {
                     "errors": [ { "message": "offset is invalid or expired" } ]
                 }"#,
             )
+            .expect(1)
+            .create_async()
+            .await;
+        let _second = server
+            .mock("GET", "/items")
+            .match_query(Matcher::UrlEncoded("offset".into(), "abc".into()))
+            .with_status(200)
+            .with_body(r#"{ "data": [ { "gid": "1" }, { "gid": "2" } ] }"#)
             .create_async()
             .await;
 
         let tmp = TempDir::new().unwrap();
         let token = AuthToken::new(SecretString::new("paginate-offset".into()));
-        let base_url = server.url();
         let client = ApiClient::builder(token)
-            .base_url(base_url)
+            .base_url(server.url())
             .cache_dir(tmp.path().join("cache"))
             .build()
             .unwrap();
 
-        let stream = client.paginate::<Value>("/items", vec![]);
+        let listing = ListingState::default();
+        let tracked = client.with_listing_state(&listing);
+        let stream = tracked.paginate::<Value>("/items", vec![]);
         tokio::pin!(stream);
         let mut ids = Vec::new();
         while let Some(page) = stream.next().await {
@@ -1230,7 +2407,86 @@ mod tests {
                     .map(ToString::to_string),
             );
         }
-        assert_eq!(ids, vec!["1".to_string()]);
+        assert_eq!(ids, vec!["1".to_string(), "2".to_string()]);
+        assert_eq!(listing.restarts(), 1);
+        assert_eq!(listing.next_offset(), None);
+
+        // A listing resumed from a caller-supplied offset cannot restart.
+        let resumed = ApiClient::builder(AuthToken::new(SecretString::new("resume".into())))
+            .base_url(server.url())
+            .cache_dir(tmp.path().join("cache"))
+            .build()
+            .unwrap();
+        let _gone = server
+            .mock("GET", "/items")
+            .match_query(Matcher::UrlEncoded("offset".into(), "gone".into()))
+            .with_status(400)
+            .with_body(r#"{ "errors": [ { "message": "offset is invalid or expired" } ] }"#)
+            .create_async()
+            .await;
+        let stream = resumed.paginate::<Value>("/items", vec![("offset".into(), "gone".into())]);
+        tokio::pin!(stream);
+        let err = stream.next().await.expect("item").expect_err("expired");
+        assert_eq!(
+            err.pagination_expired().map(|expired| expired.emitted),
+            Some(0)
+        );
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn batch_chunks_actions_and_maps_errors() {
+        let mut server = Server::new_async().await;
+        let ten_ok: Vec<Value> = (0..10)
+            .map(|i| serde_json::json!({ "status_code": 200, "body": { "data": { "gid": i.to_string() } } }))
+            .collect();
+        let _first = server
+            .mock("POST", "/batch")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "data": { "actions": [ { "relative_path": "/tasks/0" } ] }
+            })))
+            .with_status(200)
+            .with_body(serde_json::json!({ "data": ten_ok }).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        let _second = server
+            .mock("POST", "/batch")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "data": { "actions": [ { "relative_path": "/tasks/10" } ] }
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "data": [
+                        { "status_code": 404, "body": { "errors": [ { "message": "task: Unknown object" } ] } },
+                        { "status_code": 403, "body": { "errors": [ { "message": "Forbidden" } ] } }
+                    ]
+                }"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        let token = AuthToken::new(SecretString::new("batch-token".into()));
+        let client = ApiClient::builder(token)
+            .base_url(server.url())
+            .cache_dir(tmp.path().join("cache"))
+            .build()
+            .unwrap();
+
+        let actions: Vec<BatchAction> = (0..12)
+            .map(|i| BatchAction::new(&Method::GET, format!("/tasks/{i}")))
+            .collect();
+        let results = client.batch(&actions).await.expect("batch succeeds");
+        assert_eq!(results.len(), 12);
+        assert!(results[..10].iter().all(Result::is_ok));
+        assert!(matches!(
+            &results[10],
+            Err(ApiError::Http { status, .. }) if *status == StatusCode::NOT_FOUND
+        ));
+        assert!(matches!(&results[11], Err(ApiError::Authentication(_))));
         drop(server);
     }
 
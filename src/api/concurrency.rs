@@ -0,0 +1,75 @@
+//! Bounded concurrent execution for bulk operations built on [`ApiClient`].
+//!
+//! Every request still flows through [`ApiClient`], so retries and 429
+//! back-off apply per call; these helpers only cap how many run at once.
+//!
+//! [`ApiClient`]: crate::api::ApiClient
+
+use futures_core::Stream;
+use futures_util::{StreamExt, stream};
+use std::future::Future;
+
+/// Number of requests kept in flight when no explicit limit is supplied.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Run `operation` for every item with at most `limit` futures in flight.
+///
+/// Results are yielded in input order regardless of completion order, which
+/// lets callers report progress or stop early by dropping the stream. A
+/// `limit` of zero is treated as one.
+pub fn bounded<I, F, Fut>(items: I, limit: usize, operation: F) -> impl Stream<Item = Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    stream::iter(items).map(operation).buffered(limit.max(1))
+}
+
+/// Collect the results of [`bounded`] into a vector in input order.
+pub async fn map_bounded<I, F, Fut>(items: I, limit: usize, operation: F) -> Vec<Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    bounded(items, limit, operation).collect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn preserves_input_order() {
+        let results = map_bounded(0..6u64, 3, |value| async move {
+            tokio::time::sleep(Duration::from_millis(30 - value * 5)).await;
+            value
+        })
+        .await;
+        assert_eq!(results, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn caps_in_flight_operations() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        map_bounded(0..10, 3, |_| {
+            let active = Arc::clone(&active);
+            let peak = Arc::clone(&peak);
+            async move {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+            }
+        })
+        .await;
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,8 +1,9 @@
 //! Error types for the Asana API client.
 
 use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 use thiserror::Error;
 
 /// Structured information about Asana rate-limit headers.
@@ -18,6 +19,108 @@ pub struct RateLimitInfo {
     pub retry_after: Option<Duration>,
 }
 
+/// One entry of the `errors` array Asana returns with failed requests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AsanaErrorDetail {
+    /// Human-readable description of the problem.
+    pub message: String,
+    /// Suggestion for resolving the problem, when Asana provides one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Unique phrase identifying a server error in Asana's logs; quote it
+    /// when contacting Asana support.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phrase: Option<String>,
+}
+
+impl AsanaErrorDetail {
+    /// Parse the `errors` array from a response body, returning an empty list
+    /// when the body is not an Asana error payload.
+    #[must_use]
+    pub fn parse_body(body: &str) -> Vec<Self> {
+        serde_json::from_str::<Value>(body)
+            .map(|value| Self::from_value(&value))
+            .unwrap_or_default()
+    }
+
+    /// Extract the `errors` array from an already parsed response body.
+    #[must_use]
+    pub fn from_value(value: &Value) -> Vec<Self> {
+        value
+            .get("errors")
+            .cloned()
+            .and_then(|errors| serde_json::from_value(errors).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Join the messages of Asana error details, or return `body` unchanged when
+/// it carries none.
+fn summarize(body: &str) -> String {
+    let details = AsanaErrorDetail::parse_body(body);
+    if details.is_empty() {
+        body.to_string()
+    } else {
+        join_messages(&details)
+    }
+}
+
+fn join_messages(details: &[AsanaErrorDetail]) -> String {
+    details
+        .iter()
+        .map(|detail| detail.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Replay mode received a request with no recorded response.
+///
+/// Reported as [`ApiError::Cache`] with a [`std::io::ErrorKind::NotFound`]
+/// error wrapping this value.
+#[derive(Debug, Error)]
+#[error("no recorded response for {request} in {}", dir.display())]
+pub struct ReplayMiss {
+    /// Method, path, and query of the unmatched request.
+    pub request: String,
+    /// Fixture directory that was searched.
+    pub dir: PathBuf,
+}
+
+impl From<ReplayMiss> for ApiError {
+    fn from(miss: ReplayMiss) -> Self {
+        Self::Cache(std::io::Error::new(std::io::ErrorKind::NotFound, miss))
+    }
+}
+
+/// A listing's offset token expired and the listing could not restart.
+///
+/// Reported as [`ApiError::Cache`] with an error wrapping this value.
+#[derive(Debug, Error)]
+#[error("pagination offset for {path} expired after {emitted} items; the listing is incomplete")]
+pub struct PaginationExpired {
+    /// Path of the paginated endpoint.
+    pub path: String,
+    /// Items returned before the offset expired.
+    pub emitted: usize,
+}
+
+impl From<PaginationExpired> for ApiError {
+    fn from(expired: PaginationExpired) -> Self {
+        Self::Cache(std::io::Error::other(expired))
+    }
+}
+
+/// Display typed failures carried by [`ApiError::Cache`] without the prefix
+/// describing plain I/O errors.
+fn describe_cache(err: &std::io::Error) -> String {
+    if matches!(err.get_ref(), Some(source) if source.is::<ReplayMiss>() || source.is::<PaginationExpired>())
+    {
+        err.to_string()
+    } else {
+        format!("cache error: {err}")
+    }
+}
+
 /// Errors that can occur while interacting with the Asana API.
 #[derive(Debug, Error)]
 pub enum ApiError {
@@ -38,18 +141,20 @@ pub enum ApiError {
         details: Option<Value>,
     },
     /// Authentication failed (401/403).
-    #[error("authentication failed: {0}")]
+    #[error("authentication failed: {}", summarize(.0))]
     Authentication(String),
     /// Rate limit was hit and retries exhausted.
-    #[error("rate limited after {retry_after:?}: {body}")]
+    #[error("rate limited after {retry_after:?}: {}", summarize(body))]
     RateLimited {
         /// Recommended wait duration before retrying.
         retry_after: Duration,
         /// Raw response body returned with the 429.
         body: String,
     },
-    /// Cache layer failure.
-    #[error("cache error: {0}")]
+    /// Cache layer failure, or a typed failure of the response sources
+    /// layered over it ([`ReplayMiss`], [`PaginationExpired`]); see
+    /// [`ApiError::replay_miss`] and [`ApiError::pagination_expired`].
+    #[error("{}", describe_cache(.0))]
     Cache(#[from] std::io::Error),
     /// Offline mode requested data that was not cached.
     #[error("offline mode enabled and no cached response available for {resource}")]
@@ -75,4 +180,116 @@ impl ApiError {
             details,
         }
     }
+
+    /// Asana's structured error details for this failure, if it carried any.
+    #[must_use]
+    pub fn asana_errors(&self) -> Vec<AsanaErrorDetail> {
+        match self {
+            Self::Http { details, .. } => details
+                .as_ref()
+                .map(AsanaErrorDetail::from_value)
+                .unwrap_or_default(),
+            Self::Authentication(body) | Self::RateLimited { body, .. } => {
+                AsanaErrorDetail::parse_body(body)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The replay miss this error reports, if it is one.
+    #[must_use]
+    pub fn replay_miss(&self) -> Option<&ReplayMiss> {
+        self.cache_source()
+    }
+
+    /// The expired pagination this error reports, if it is one.
+    #[must_use]
+    pub fn pagination_expired(&self) -> Option<&PaginationExpired> {
+        self.cache_source()
+    }
+
+    fn cache_source<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        match self {
+            Self::Cache(err) => err.get_ref()?.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// HTTP status associated with this failure, if Asana answered.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Http { status, .. } => Some(*status),
+            Self::Authentication(_) => Some(StatusCode::UNAUTHORIZED),
+            Self::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            Self::Network(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    /// Map a non-success status and its response body onto the matching variant.
+    ///
+    /// Authentication failures (401/403) become [`ApiError::Authentication`];
+    /// everything else becomes [`ApiError::Http`] with the body parsed as JSON
+    /// details when possible. The message is taken from Asana's
+    /// `errors[].message` entries, falling back to the raw body.
+    #[must_use]
+    pub fn from_status(status: StatusCode, body: String) -> Self {
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            return Self::Authentication(body);
+        }
+
+        let details = serde_json::from_str::<Value>(&body).ok();
+        let errors = details
+            .as_ref()
+            .map(AsanaErrorDetail::from_value)
+            .unwrap_or_default();
+        let message = if !errors.is_empty() {
+            join_messages(&errors)
+        } else if body.is_empty() {
+            status
+                .canonical_reason()
+                .unwrap_or("unknown error")
+                .to_string()
+        } else {
+            body
+        };
+        Self::Http {
+            status,
+            message,
+            details,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asana_error_payloads_are_parsed() {
+        let body = r#"{"errors":[{"message":"project: Missing input","help":"See the docs","phrase":"6 sad squid snuggle softly"}]}"#;
+        let err = ApiError::from_status(StatusCode::BAD_REQUEST, body.to_string());
+        assert_eq!(
+            err.to_string(),
+            "HTTP 400 Bad Request: project: Missing input"
+        );
+        let details = err.asana_errors();
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].help.as_deref(), Some("See the docs"));
+        assert_eq!(
+            details[0].phrase.as_deref(),
+            Some("6 sad squid snuggle softly")
+        );
+
+        let auth = ApiError::from_status(
+            StatusCode::UNAUTHORIZED,
+            r#"{"errors":[{"message":"Not Authorized"}]}"#.to_string(),
+        );
+        assert_eq!(auth.to_string(), "authentication failed: Not Authorized");
+
+        let plain = ApiError::from_status(StatusCode::BAD_GATEWAY, "upstream down".into());
+        assert!(plain.asana_errors().is_empty());
+        assert!(plain.to_string().ends_with("upstream down"));
+    }
 }
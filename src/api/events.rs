@@ -0,0 +1,152 @@
+//! Events API access with persisted sync tokens.
+//!
+//! Asana reports changes to a resource through `GET /events`. The first
+//! request without a sync token (or with an expired one) is answered with
+//! `412 Precondition Failed` and a fresh token; later requests with that token
+//! return the events that happened since it was issued.
+
+use crate::{
+    api::{ApiClient, ApiError},
+    models::Event,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Events returned for one request, together with the token to resume from.
+#[derive(Debug, Clone)]
+pub struct EventBatch {
+    /// Changes since the previous sync token, oldest first.
+    pub events: Vec<Event>,
+    /// Token to pass on the next request.
+    pub sync: String,
+    /// Whether more events are immediately available.
+    pub has_more: bool,
+    /// Whether the previous token had expired, so events may have been missed.
+    pub resynced: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventsResponse {
+    #[serde(default)]
+    data: Vec<Event>,
+    sync: String,
+    #[serde(default)]
+    has_more: bool,
+}
+
+/// Fetch events for `resource` since `sync`.
+///
+/// Without a token, or when the token has expired, the returned batch is
+/// empty and carries a fresh token; `resynced` reports the expired case.
+///
+/// # Errors
+/// Returns [`ApiError`] if the API request fails or network errors occur.
+pub async fn get_events(
+    client: &ApiClient,
+    resource: &str,
+    sync: Option<&str>,
+) -> Result<EventBatch, ApiError> {
+    let mut query = vec![("resource".to_string(), resource.to_string())];
+    if let Some(sync) = sync {
+        query.push(("sync".to_string(), sync.to_string()));
+    }
+
+    match client
+        .get_json_with_pairs::<EventsResponse>("/events", query)
+        .await
+    {
+        Ok(response) => Ok(EventBatch {
+            events: response.data,
+            sync: response.sync,
+            has_more: response.has_more,
+            resynced: false,
+        }),
+        Err(ApiError::Http {
+            status: StatusCode::PRECONDITION_FAILED,
+            details: Some(details),
+            ..
+        }) if details.get("sync").and_then(|v| v.as_str()).is_some() => Ok(EventBatch {
+            events: Vec::new(),
+            sync: details["sync"].as_str().unwrap_or_default().to_string(),
+            has_more: false,
+            resynced: sync.is_some(),
+        }),
+        Err(err) => Err(err),
+    }
+}
+
+/// Sync tokens persisted per watched resource, so a watch resumes where the
+/// previous run stopped.
+#[derive(Debug, Clone)]
+pub struct SyncTokenStore {
+    dir: PathBuf,
+}
+
+impl SyncTokenStore {
+    /// Store tokens as one file per resource gid inside `dir`; anything but a
+    /// numeric gid is refused so it cannot name a path outside `dir`.
+    #[must_use]
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Token saved for `resource`, if any.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Cache`] if `resource` is not a gid or the token
+    /// file exists but cannot be read.
+    pub async fn load(&self, resource: &str) -> Result<Option<String>, ApiError> {
+        match fs::read_to_string(self.path(resource)?).await {
+            Ok(token) => Ok(Some(token.trim().to_string()).filter(|token| !token.is_empty())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Remember `token` for `resource`.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Cache`] if `resource` is not a gid or the token
+    /// cannot be written.
+    pub async fn save(&self, resource: &str, token: &str) -> Result<(), ApiError> {
+        let path = self.path(resource)?;
+        fs::create_dir_all(&self.dir).await?;
+        let tmp = self.dir.join(format!(".{resource}.tmp"));
+        fs::write(&tmp, token).await?;
+        fs::rename(&tmp, path).await?;
+        Ok(())
+    }
+
+    /// Forget the token for `resource`.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Cache`] if `resource` is not a gid or an existing
+    /// token cannot be removed.
+    pub async fn clear(&self, resource: &str) -> Result<(), ApiError> {
+        match fs::remove_file(self.path(resource)?).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, resource: &str) -> Result<PathBuf, ApiError> {
+        if !is_gid(resource) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("`{resource}` is not a resource gid"),
+            )
+            .into());
+        }
+        Ok(self.dir.join(resource))
+    }
+}
+
+/// Whether `value` looks like an Asana gid (a non-empty run of digits).
+#[must_use]
+pub fn is_gid(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+}
@@ -0,0 +1,1741 @@
+//! In-memory [`AsanaApi`] implementation for tests (`fake` feature).
+//!
+//! [`FakeAsana`] keeps every resource in maps behind a mutex, so code written
+//! against [`AsanaApi`] can run without a network or recorded fixtures. Seed
+//! it with the `insert_*` methods, exercise the code under test, and read the
+//! outcome back through the trait.
+//!
+//! Created resources get increasing numeric gids, unknown gids fail with
+//! `404`, and every create, update, and delete made through the trait is
+//! logged for [`AsanaApi::get_events`], whose sync tokens are positions in
+//! that log. The fake stores what the request payloads say and no more:
+//! `opt_fields` are ignored because records are always complete, references
+//! to other resources carry only their gid, custom field values sent with
+//! tasks are not applied, section insertion hints and search sort orders are
+//! ignored, and `me` resolves to the user passed to
+//! [`FakeAsana::set_current_user`].
+
+use crate::{
+    api::{ApiError, AsanaApi, EventBatch, projects::sort_projects, tasks::sort_tasks},
+    models::{
+        Attachment, AttachmentListParams, CustomField, Event, EventResource, MemberPermission,
+        Project, ProjectCreateData, ProjectCreateRequest, ProjectListParams, ProjectMember,
+        ProjectMembers, ProjectStatus, ProjectUpdateRequest, Section, SectionCreateRequest,
+        SectionProjectReference, Story, StoryCreateData, StoryCreateRequest, StoryListParams,
+        StoryType, StoryUpdateData, StoryUpdateRequest, Tag, TagCreateData, TagCreateRequest,
+        TagListParams, TagUpdateData, TagUpdateRequest, Task, TaskCreateData, TaskCreateRequest,
+        TaskListParams, TaskMembership, TaskProjectReference, TaskReference, TaskSearchParams,
+        TaskSectionReference, TaskTagReference, TaskUpdateData, TaskUpdateRequest, User,
+        UserListParams, UserReference, Webhook, WebhookCreateData, WebhookCreateRequest,
+        WebhookListParams, Workspace, WorkspaceListParams, WorkspaceReference,
+    },
+};
+use chrono::{SecondsFormat, Utc};
+use futures_util::future::{self, BoxFuture};
+use reqwest::StatusCode;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// Gid assigned to the first resource created through the fake.
+const FIRST_GID: u64 = 1_200_000_000_000_000;
+
+/// Task payload fields holding gids of other resources.
+const TASK_REFERENCES: &[&str] = &[
+    "workspace",
+    "projects",
+    "parent",
+    "assignee",
+    "tags",
+    "followers",
+];
+
+/// In-memory stand-in for Asana implementing [`AsanaApi`].
+#[derive(Debug, Default)]
+pub struct FakeAsana {
+    state: Mutex<State>,
+}
+
+impl FakeAsana {
+    /// Empty fake without a current user.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a workspace.
+    pub fn insert_workspace(&self, workspace: Workspace) {
+        self.state()
+            .workspaces
+            .insert(workspace.gid.clone(), workspace);
+    }
+
+    /// Add or replace a user.
+    pub fn insert_user(&self, user: User) {
+        self.state().users.insert(user.gid.clone(), user);
+    }
+
+    /// Add `user` and answer [`AsanaApi::get_current_user`] and `me` with it.
+    pub fn set_current_user(&self, user: User) {
+        let mut state = self.state();
+        state.current_user = Some(user.gid.clone());
+        state.users.insert(user.gid.clone(), user);
+    }
+
+    /// Add or replace a project.
+    pub fn insert_project(&self, project: Project) {
+        self.state().projects.insert(project.gid.clone(), project);
+    }
+
+    /// Add or replace a section.
+    pub fn insert_section(&self, section: Section) {
+        self.state().sections.insert(section.gid.clone(), section);
+    }
+
+    /// Add or replace a task.
+    pub fn insert_task(&self, task: Task) {
+        self.state().tasks.insert(task.gid.clone(), task);
+    }
+
+    /// Add or replace a story on a task.
+    pub fn insert_story(&self, task_gid: &str, story: Story) {
+        self.state()
+            .stories
+            .insert(story.gid.clone(), (task_gid.to_string(), story));
+    }
+
+    /// Add or replace an attachment on a task.
+    pub fn insert_attachment(&self, task_gid: &str, attachment: Attachment) {
+        self.state()
+            .attachments
+            .insert(attachment.gid.clone(), (task_gid.to_string(), attachment));
+    }
+
+    /// Add or replace a tag.
+    pub fn insert_tag(&self, tag: Tag) {
+        self.state().tags.insert(tag.gid.clone(), tag);
+    }
+
+    /// Add or replace a custom field defined in a workspace.
+    pub fn insert_custom_field(&self, workspace_gid: &str, field: CustomField) {
+        self.state()
+            .custom_fields
+            .insert(field.gid.clone(), (workspace_gid.to_string(), field));
+    }
+
+    /// Add or replace a webhook registered in a workspace.
+    pub fn insert_webhook(&self, workspace_gid: &str, webhook: Webhook) {
+        self.state().webhooks.insert(
+            webhook.gid.clone(),
+            (Some(workspace_gid.to_string()), webhook),
+        );
+    }
+
+    /// Every event logged so far, oldest first.
+    #[must_use]
+    pub fn events(&self) -> Vec<Event> {
+        self.state().events.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    created: u64,
+    current_user: Option<String>,
+    workspaces: BTreeMap<String, Workspace>,
+    users: BTreeMap<String, User>,
+    projects: BTreeMap<String, Project>,
+    sections: BTreeMap<String, Section>,
+    tasks: BTreeMap<String, Task>,
+    /// Stories keyed by gid, with the gid of their task.
+    stories: BTreeMap<String, (String, Story)>,
+    /// Attachments keyed by gid, with the gid of their task.
+    attachments: BTreeMap<String, (String, Attachment)>,
+    tags: BTreeMap<String, Tag>,
+    /// Custom fields keyed by gid, with the gid of their workspace.
+    custom_fields: BTreeMap<String, (String, CustomField)>,
+    /// Webhooks keyed by gid, with the gid of their workspace when known.
+    webhooks: BTreeMap<String, (Option<String>, Webhook)>,
+    events: Vec<Event>,
+}
+
+impl State {
+    fn next_gid(&mut self) -> String {
+        self.created += 1;
+        (FIRST_GID + self.created).to_string()
+    }
+
+    /// Resolve `me` to the current user.
+    fn user_gid<'a>(&'a self, gid: &'a str) -> &'a str {
+        if gid == "me" {
+            self.current_user.as_deref().unwrap_or(gid)
+        } else {
+            gid
+        }
+    }
+
+    fn record(
+        &mut self,
+        action: &str,
+        resource_type: &str,
+        gid: &str,
+        parents: Vec<EventResource>,
+    ) {
+        let resource = event_resource(gid, resource_type);
+        let user = self.current_user.as_deref().map(user_reference);
+        let created_at = Some(now());
+        let parents = if parents.is_empty() {
+            vec![None]
+        } else {
+            parents.into_iter().map(Some).collect()
+        };
+        for parent in parents {
+            self.events.push(Event {
+                action: action.to_string(),
+                resource: resource.clone(),
+                parent,
+                change: None,
+                user: user.clone(),
+                created_at: created_at.clone(),
+            });
+        }
+    }
+
+    /// Log a task event once per project (and parent task) it belongs to.
+    fn record_task(&mut self, action: &str, task: &Task) {
+        let parents = task
+            .projects
+            .iter()
+            .map(|project| event_resource(&project.gid, "project"))
+            .chain(
+                task.parent
+                    .iter()
+                    .map(|parent| event_resource(&parent.gid, "task")),
+            )
+            .collect();
+        self.record(action, "task", &task.gid, parents);
+    }
+
+    fn events(&self, resource: &str, sync: Option<&str>) -> EventBatch {
+        let start = sync
+            .and_then(|token| token.parse::<usize>().ok())
+            .filter(|start| *start <= self.events.len());
+        let events = start
+            .map(|start| {
+                self.events[start..]
+                    .iter()
+                    .filter(|event| {
+                        event.resource.gid == resource
+                            || event
+                                .parent
+                                .as_ref()
+                                .is_some_and(|parent| parent.gid == resource)
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        EventBatch {
+            events,
+            sync: self.events.len().to_string(),
+            has_more: false,
+            resynced: sync.is_some() && start.is_none(),
+        }
+    }
+
+    fn current_user(&self) -> Result<User, ApiError> {
+        let gid = self.current_user.as_deref().ok_or_else(|| {
+            ApiError::Authentication("no current user; call FakeAsana::set_current_user".into())
+        })?;
+        find(&self.users, "user", gid).cloned()
+    }
+
+    fn list_projects(&self, params: &ProjectListParams) -> Vec<Project> {
+        let mut projects: Vec<Project> =
+            self.projects
+                .values()
+                .filter(|project| {
+                    params.workspace.as_ref().is_none_or(|gid| {
+                        project
+                            .workspace
+                            .as_ref()
+                            .is_some_and(|workspace| &workspace.gid == gid)
+                    }) && params.team.as_ref().is_none_or(|gid| {
+                        project.team.as_ref().is_some_and(|team| &team.gid == gid)
+                    }) && params
+                        .archived
+                        .is_none_or(|archived| project.archived == archived)
+                })
+                .take(params.limit.unwrap_or(usize::MAX))
+                .filter(|project| project.matches(&params.filters))
+                .cloned()
+                .collect();
+        if let Some(sort) = params.sort {
+            sort_projects(&mut projects, sort);
+        }
+        projects
+    }
+
+    fn create_project(&mut self, data: &ProjectCreateData) -> Result<Project, ApiError> {
+        let gid = self.next_gid();
+        let now = now();
+        let mut patch = without(serde_json::to_value(data)?, &["members"]);
+        self.resolve_me(&mut patch, &["owner"]);
+        as_references(&mut patch, &["workspace", "team", "owner"]);
+        let base = json!({
+            "gid": gid,
+            "resource_type": "project",
+            "created_at": now,
+            "modified_at": now,
+        });
+        self.projects.insert(gid.clone(), merge(base, patch)?);
+        self.add_members(&gid, &data.members, None)?;
+        self.record("added", "project", &gid, Vec::new());
+        find(&self.projects, "project", &gid).cloned()
+    }
+
+    fn update_project(&mut self, gid: &str, patch: Value) -> Result<Project, ApiError> {
+        let mut patch = patch;
+        self.resolve_me(&mut patch, &["owner"]);
+        as_references(&mut patch, &["owner"]);
+        let mut project: Project = patched(find(&self.projects, "project", gid)?, patch)?;
+        project.modified_at = Some(now());
+        self.projects.insert(gid.to_string(), project.clone());
+        self.record("changed", "project", gid, Vec::new());
+        Ok(project)
+    }
+
+    fn delete_project(&mut self, gid: &str) -> Result<(), ApiError> {
+        self.projects
+            .remove(gid)
+            .ok_or_else(|| not_found("project", gid))?;
+        self.sections.retain(|_, section| {
+            section
+                .project
+                .as_ref()
+                .is_none_or(|project| project.gid != gid)
+        });
+        for task in self.tasks.values_mut() {
+            task.projects.retain(|project| project.gid != gid);
+            sync_memberships(task);
+        }
+        self.record("deleted", "project", gid, Vec::new());
+        Ok(())
+    }
+
+    fn add_members(
+        &mut self,
+        gid: &str,
+        users: &[String],
+        role: Option<&MemberPermission>,
+    ) -> Result<(), ApiError> {
+        let mut current: Vec<String> = find(&self.projects, "project", gid)?
+            .members
+            .iter()
+            .map(|member| member.user.gid.clone())
+            .collect();
+        let mut added = Vec::new();
+        for user in users {
+            let user = self.user_gid(user).to_string();
+            if !current.contains(&user) {
+                added.push(ProjectMember {
+                    gid: self.next_gid(),
+                    user: user_reference(&user),
+                    role: role.cloned(),
+                });
+                current.push(user);
+            }
+        }
+        find_mut(&mut self.projects, "project", gid)?
+            .members
+            .extend(added);
+        Ok(())
+    }
+
+    fn remove_members(&mut self, gid: &str, users: &[String]) -> Result<(), ApiError> {
+        let users: Vec<String> = users
+            .iter()
+            .map(|user| self.user_gid(user).to_string())
+            .collect();
+        find_mut(&mut self.projects, "project", gid)?
+            .members
+            .retain(|member| !users.contains(&member.user.gid));
+        Ok(())
+    }
+
+    fn update_member(
+        &mut self,
+        membership_gid: &str,
+        role: MemberPermission,
+    ) -> Result<ProjectMember, ApiError> {
+        let member = self
+            .projects
+            .values_mut()
+            .flat_map(|project| project.members.iter_mut())
+            .find(|member| member.gid == membership_gid)
+            .ok_or_else(|| not_found("membership", membership_gid))?;
+        member.role = Some(role);
+        Ok(member.clone())
+    }
+
+    fn list_sections(&self, project_gid: &str) -> Result<Vec<Section>, ApiError> {
+        find(&self.projects, "project", project_gid)?;
+        Ok(self
+            .sections
+            .values()
+            .filter(|section| section_project(section) == Some(project_gid))
+            .cloned()
+            .collect())
+    }
+
+    fn create_section(&mut self, project_gid: &str, name: String) -> Result<Section, ApiError> {
+        find(&self.projects, "project", project_gid)?;
+        let section = Section {
+            gid: self.next_gid(),
+            name,
+            resource_type: Some("section".into()),
+            created_at: Some(now()),
+            project: Some(SectionProjectReference {
+                gid: project_gid.to_string(),
+                name: None,
+                resource_type: None,
+            }),
+            projects: Vec::new(),
+        };
+        self.sections.insert(section.gid.clone(), section.clone());
+        self.record(
+            "added",
+            "section",
+            &section.gid,
+            vec![event_resource(project_gid, "project")],
+        );
+        Ok(section)
+    }
+
+    fn section_tasks(&self, gid: &str) -> Result<Vec<Task>, ApiError> {
+        find(&self.sections, "section", gid)?;
+        Ok(self
+            .tasks
+            .values()
+            .filter(|task| {
+                task.memberships.iter().any(|membership| {
+                    membership
+                        .section
+                        .as_ref()
+                        .is_some_and(|section| section.gid == gid)
+                })
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Put `task` into a section, adding it to the section's project.
+    fn place(&self, task: &mut Task, section_gid: &str) -> Result<(), ApiError> {
+        let section = find(&self.sections, "section", section_gid)?;
+        let project_gid = section_project(section).ok_or_else(|| {
+            invalid(format!(
+                "section {section_gid} does not belong to a project"
+            ))
+        })?;
+        add_project_reference(task, project_gid);
+        if let Some(membership) = task.memberships.iter_mut().find(|membership| {
+            membership
+                .project
+                .as_ref()
+                .is_some_and(|project| project.gid == project_gid)
+        }) {
+            membership.section = Some(TaskSectionReference {
+                gid: section_gid.to_string(),
+                name: None,
+                resource_type: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn list_stories(&self, params: &StoryListParams) -> Result<Vec<Story>, ApiError> {
+        find(&self.tasks, "task", &params.task_gid)?;
+        Ok(self
+            .stories
+            .values()
+            .filter(|(task_gid, _)| *task_gid == params.task_gid)
+            .map(|(_, story)| story.clone())
+            .take(params.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    fn create_story(&mut self, task_gid: &str, data: StoryCreateData) -> Result<Story, ApiError> {
+        find(&self.tasks, "task", task_gid)?;
+        let story = Story {
+            gid: self.next_gid(),
+            resource_type: Some("story".into()),
+            story_type: StoryType::Comment,
+            text: data.text,
+            html_text: data.html_text,
+            is_pinned: data.is_pinned.unwrap_or(false),
+            is_editable: true,
+            is_edited: false,
+            created_by: self.current_user.as_deref().map(user_reference),
+            created_at: Some(now()),
+        };
+        self.stories
+            .insert(story.gid.clone(), (task_gid.to_string(), story.clone()));
+        self.record(
+            "added",
+            "story",
+            &story.gid,
+            vec![event_resource(task_gid, "task")],
+        );
+        Ok(story)
+    }
+
+    fn update_story(&mut self, gid: &str, data: StoryUpdateData) -> Result<Story, ApiError> {
+        let (task_gid, story) = find_mut(&mut self.stories, "story", gid)?;
+        if data.text.is_some() || data.html_text.is_some() {
+            story.is_edited = true;
+        }
+        if let Some(text) = data.text {
+            story.text = Some(text);
+        }
+        if let Some(html_text) = data.html_text {
+            story.html_text = Some(html_text);
+        }
+        if let Some(is_pinned) = data.is_pinned {
+            story.is_pinned = is_pinned;
+        }
+        let (task_gid, story) = (task_gid.clone(), story.clone());
+        self.record(
+            "changed",
+            "story",
+            gid,
+            vec![event_resource(&task_gid, "task")],
+        );
+        Ok(story)
+    }
+
+    fn delete_story(&mut self, gid: &str) -> Result<(), ApiError> {
+        let (task_gid, _) = self
+            .stories
+            .remove(gid)
+            .ok_or_else(|| not_found("story", gid))?;
+        self.record(
+            "deleted",
+            "story",
+            gid,
+            vec![event_resource(&task_gid, "task")],
+        );
+        Ok(())
+    }
+
+    fn create_tag(&mut self, data: TagCreateData) -> Tag {
+        let tag = Tag {
+            gid: self.next_gid(),
+            name: data.name,
+            resource_type: Some("tag".into()),
+            color: data.color,
+            notes: data.notes,
+            created_at: Some(now()),
+            followers: data
+                .followers
+                .iter()
+                .map(|gid| user_reference(self.user_gid(gid)))
+                .collect(),
+            workspace: Some(WorkspaceReference {
+                gid: data.workspace,
+                name: None,
+                resource_type: None,
+            }),
+            permalink_url: None,
+        };
+        self.tags.insert(tag.gid.clone(), tag.clone());
+        self.record("added", "tag", &tag.gid, Vec::new());
+        tag
+    }
+
+    fn update_tag(&mut self, gid: &str, data: TagUpdateData) -> Result<Tag, ApiError> {
+        let followers = data.followers.map(|followers| {
+            followers
+                .iter()
+                .map(|gid| user_reference(self.user_gid(gid)))
+                .collect()
+        });
+        let tag = find_mut(&mut self.tags, "tag", gid)?;
+        if let Some(name) = data.name {
+            tag.name = name;
+        }
+        if let Some(color) = data.color {
+            tag.color = Some(color);
+        }
+        if let Some(notes) = data.notes {
+            tag.notes = notes;
+        }
+        if let Some(followers) = followers {
+            tag.followers = followers;
+        }
+        let tag = tag.clone();
+        self.record("changed", "tag", gid, Vec::new());
+        Ok(tag)
+    }
+
+    fn delete_tag(&mut self, gid: &str) -> Result<(), ApiError> {
+        self.tags.remove(gid).ok_or_else(|| not_found("tag", gid))?;
+        for task in self.tasks.values_mut() {
+            task.tags.retain(|tag| tag.gid != gid);
+        }
+        self.record("deleted", "tag", gid, Vec::new());
+        Ok(())
+    }
+
+    fn list_tasks(&self, params: &TaskListParams) -> Vec<Task> {
+        let mut tasks: Vec<Task> = self
+            .tasks
+            .values()
+            .filter(|task| self.listed(task, params))
+            .take(params.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        if params.include_subtasks {
+            let subtasks: Vec<Task> = tasks
+                .iter()
+                .flat_map(|parent| self.subtasks(&parent.gid))
+                .collect();
+            tasks.extend(subtasks);
+        }
+        params.apply_post_filters(&mut tasks);
+        if let Some(sort) = params.sort {
+            sort_tasks(&mut tasks, sort);
+        }
+        tasks
+    }
+
+    /// Whether `task` passes the filters `GET /tasks` applies server side.
+    fn listed(&self, task: &Task, params: &TaskListParams) -> bool {
+        params.workspace.as_ref().is_none_or(|gid| {
+            task.workspace
+                .as_ref()
+                .is_some_and(|workspace| &workspace.gid == gid)
+        }) && params
+            .project
+            .as_ref()
+            .is_none_or(|gid| task.projects.iter().any(|project| &project.gid == gid))
+            && params
+                .section
+                .as_ref()
+                .is_none_or(|gid| section_gids(task).any(|section| section == gid))
+            && params.assignee.as_ref().is_none_or(|gid| {
+                let gid = self.user_gid(gid);
+                task.assignee
+                    .as_ref()
+                    .is_some_and(|assignee| assignee.gid == gid)
+            })
+            && params.completed_since.as_ref().is_none_or(|since| {
+                !task.completed || task.completed_at.as_ref().is_some_and(|at| at >= since)
+            })
+            && params
+                .modified_since
+                .as_ref()
+                .is_none_or(|since| task.modified_at.as_ref().is_some_and(|at| at >= since))
+            && params
+                .due_on
+                .as_ref()
+                .is_none_or(|due_on| task.due_on.as_ref() == Some(due_on))
+    }
+
+    fn search_tasks(&self, params: &TaskSearchParams) -> Vec<Task> {
+        self.tasks
+            .values()
+            .filter(|task| self.found(task, params))
+            .take(params.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether `task` matches a workspace search.
+    fn found(&self, task: &Task, params: &TaskSearchParams) -> bool {
+        task.workspace
+            .as_ref()
+            .is_none_or(|workspace| workspace.gid == params.workspace)
+            && params.text.as_ref().is_none_or(|text| {
+                let text = text.to_lowercase();
+                task.name.to_lowercase().contains(&text)
+                    || task
+                        .notes
+                        .as_ref()
+                        .is_some_and(|notes| notes.to_lowercase().contains(&text))
+            })
+            && params
+                .resource_subtype
+                .as_ref()
+                .is_none_or(|subtype| task.resource_subtype.as_ref() == Some(subtype))
+            && params
+                .completed
+                .is_none_or(|completed| task.completed == completed)
+            && params
+                .is_subtask
+                .is_none_or(|is_subtask| task.parent.is_some() == is_subtask)
+            && params
+                .is_blocked
+                .is_none_or(|is_blocked| self.blocked(task) == is_blocked)
+            && params.has_attachment.is_none_or(|has_attachment| {
+                self.attachments
+                    .values()
+                    .any(|(task_gid, _)| *task_gid == task.gid)
+                    == has_attachment
+            })
+            && params.assignee.as_ref().is_none_or(|gid| {
+                let gid = self.user_gid(gid);
+                task.assignee
+                    .as_ref()
+                    .is_some_and(|assignee| assignee.gid == gid)
+            })
+            && any_gid(
+                &params.projects,
+                task.projects.iter().map(|project| project.gid.as_str()),
+            )
+            && any_gid(&params.sections, section_gids(task))
+            && any_gid(&params.tags, task.tags.iter().map(|tag| tag.gid.as_str()))
+            && within(
+                task.created_at.as_ref(),
+                params.created_after.as_ref(),
+                params.created_before.as_ref(),
+            )
+            && within(
+                task.modified_at.as_ref(),
+                params.modified_after.as_ref(),
+                params.modified_before.as_ref(),
+            )
+            && within(
+                task.due_on.as_ref(),
+                params.due_after.as_ref(),
+                params.due_before.as_ref(),
+            )
+    }
+
+    /// Whether any task `task` depends on is still incomplete.
+    fn blocked(&self, task: &Task) -> bool {
+        task.dependencies.iter().any(|dependency| {
+            self.tasks
+                .get(&dependency.gid)
+                .is_some_and(|dependency| !dependency.completed)
+        })
+    }
+
+    fn subtasks(&self, gid: &str) -> Vec<Task> {
+        self.tasks
+            .values()
+            .filter(|task| task.parent.as_ref().is_some_and(|parent| parent.gid == gid))
+            .cloned()
+            .collect()
+    }
+
+    fn task_by_external_id(&self, external_id: &str) -> Option<Task> {
+        self.tasks
+            .values()
+            .find(|task| {
+                task.external
+                    .as_ref()
+                    .is_some_and(|external| external.gid == external_id)
+            })
+            .cloned()
+    }
+
+    fn create_task(&mut self, data: &TaskCreateData) -> Result<Task, ApiError> {
+        if let Some(external) = &data.external {
+            if self.task_by_external_id(&external.gid).is_some() {
+                return Err(invalid(format!(
+                    "external id {} is already in use",
+                    external.gid
+                )));
+            }
+        }
+        let gid = self.next_gid();
+        let now = now();
+        let mut patch = without(serde_json::to_value(data)?, &["section", "custom_fields"]);
+        self.resolve_me(&mut patch, &["assignee", "followers"]);
+        as_references(&mut patch, TASK_REFERENCES);
+        let base = json!({
+            "gid": gid,
+            "resource_type": "task",
+            "resource_subtype": "default_task",
+            "created_at": now,
+            "modified_at": now,
+        });
+        let mut task: Task = merge(base, patch)?;
+        sync_memberships(&mut task);
+        if let Some(section) = &data.section {
+            self.place(&mut task, section)?;
+        }
+        self.tasks.insert(gid, task.clone());
+        self.record_task("added", &task);
+        Ok(task)
+    }
+
+    fn update_task(&mut self, gid: &str, data: &TaskUpdateData) -> Result<Task, ApiError> {
+        let current = find(&self.tasks, "task", gid)?.clone();
+        let mut patch = without(serde_json::to_value(data)?, &["custom_fields"]);
+        self.resolve_me(&mut patch, &["assignee", "followers"]);
+        as_references(&mut patch, TASK_REFERENCES);
+        let mut task: Task = patched(&current, patch)?;
+        sync_memberships(&mut task);
+        let now = now();
+        if task.completed != current.completed {
+            task.completed_at = task.completed.then(|| now.clone());
+        }
+        task.modified_at = Some(now);
+        self.tasks.insert(gid.to_string(), task.clone());
+        self.record_task("changed", &task);
+        Ok(task)
+    }
+
+    fn delete_task(&mut self, gid: &str) -> Result<(), ApiError> {
+        let task = self
+            .tasks
+            .remove(gid)
+            .ok_or_else(|| not_found("task", gid))?;
+        self.stories.retain(|_, (task_gid, _)| task_gid != gid);
+        self.attachments.retain(|_, (task_gid, _)| task_gid != gid);
+        for other in self.tasks.values_mut() {
+            other
+                .dependencies
+                .retain(|dependency| dependency.gid != gid);
+            other.dependents.retain(|dependent| dependent.gid != gid);
+        }
+        self.record_task("deleted", &task);
+        Ok(())
+    }
+
+    /// Apply `change` to a task and log it.
+    fn change_task(
+        &mut self,
+        gid: &str,
+        change: impl FnOnce(&mut Task) -> Result<(), ApiError>,
+    ) -> Result<(), ApiError> {
+        let mut task = find(&self.tasks, "task", gid)?.clone();
+        change(&mut task)?;
+        task.modified_at = Some(now());
+        self.tasks.insert(gid.to_string(), task.clone());
+        self.record_task("changed", &task);
+        Ok(())
+    }
+
+    /// Add or remove `(dependent, dependency)` links on both tasks.
+    fn link(&mut self, pairs: &[(String, String)], linked: bool) -> Result<(), ApiError> {
+        for (dependent, dependency) in pairs {
+            find(&self.tasks, "task", dependent)?;
+            find(&self.tasks, "task", dependency)?;
+        }
+        for (dependent, dependency) in pairs {
+            self.change_task(dependent, |task| {
+                set_reference(&mut task.dependencies, dependency, linked);
+                Ok(())
+            })?;
+            self.change_task(dependency, |task| {
+                set_reference(&mut task.dependents, dependent, linked);
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn add_project(
+        &mut self,
+        gid: &str,
+        project: &str,
+        section: Option<&str>,
+    ) -> Result<(), ApiError> {
+        find(&self.projects, "project", project)?;
+        let mut task = find(&self.tasks, "task", gid)?.clone();
+        add_project_reference(&mut task, project);
+        if let Some(section) = section {
+            self.place(&mut task, section)?;
+        }
+        self.change_task(gid, |current| {
+            *current = task;
+            Ok(())
+        })
+    }
+
+    fn set_followers(
+        &mut self,
+        gid: &str,
+        followers: &[String],
+        added: bool,
+    ) -> Result<(), ApiError> {
+        let followers: Vec<String> = followers
+            .iter()
+            .map(|follower| self.user_gid(follower).to_string())
+            .collect();
+        self.change_task(gid, |task| {
+            for follower in &followers {
+                let present = task.followers.iter().any(|user| user.gid == *follower);
+                if added && !present {
+                    task.followers.push(user_reference(follower));
+                } else if !added {
+                    task.followers.retain(|user| user.gid != *follower);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn set_tag(&mut self, gid: &str, tag: &str, added: bool) -> Result<(), ApiError> {
+        find(&self.tags, "tag", tag)?;
+        self.change_task(gid, |task| {
+            task.tags.retain(|existing| existing.gid != tag);
+            if added {
+                task.tags.push(TaskTagReference {
+                    gid: tag.to_string(),
+                    name: None,
+                    resource_type: None,
+                });
+            }
+            Ok(())
+        })
+    }
+
+    fn list_webhooks(&self, params: &WebhookListParams) -> Vec<Webhook> {
+        self.webhooks
+            .values()
+            .filter(|(workspace, webhook)| {
+                workspace
+                    .as_ref()
+                    .is_none_or(|workspace| *workspace == params.workspace)
+                    && params.resource.as_ref().is_none_or(|resource| {
+                        webhook
+                            .resource
+                            .as_ref()
+                            .is_some_and(|watched| watched.gid == *resource)
+                    })
+            })
+            .map(|(_, webhook)| webhook.clone())
+            .take(params.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    fn create_webhook(&mut self, data: WebhookCreateData) -> Webhook {
+        let workspace = self.workspace_of(&data.resource);
+        let webhook = Webhook {
+            gid: self.next_gid(),
+            resource_type: Some("webhook".into()),
+            active: true,
+            resource: Some(EventResource {
+                gid: data.resource,
+                resource_type: None,
+                resource_subtype: None,
+                name: None,
+            }),
+            target: Some(data.target),
+            created_at: Some(now()),
+            last_success_at: None,
+            last_failure_at: None,
+            last_failure_content: None,
+        };
+        self.webhooks
+            .insert(webhook.gid.clone(), (workspace, webhook.clone()));
+        webhook
+    }
+
+    /// Workspace containing the resource `gid`, when the fake knows it.
+    fn workspace_of(&self, gid: &str) -> Option<String> {
+        if self.workspaces.contains_key(gid) {
+            return Some(gid.to_string());
+        }
+        self.tasks
+            .get(gid)
+            .and_then(|task| task.workspace.as_ref())
+            .or_else(|| {
+                self.projects
+                    .get(gid)
+                    .and_then(|project| project.workspace.as_ref())
+            })
+            .or_else(|| self.tags.get(gid).and_then(|tag| tag.workspace.as_ref()))
+            .map(|workspace| workspace.gid.clone())
+    }
+
+    /// Replace `me` under `keys` of a payload with the current user's gid.
+    fn resolve_me(&self, patch: &mut Value, keys: &[&str]) {
+        let Some(current) = &self.current_user else {
+            return;
+        };
+        let Value::Object(map) = patch else {
+            return;
+        };
+        for key in keys {
+            match map.get_mut(*key) {
+                Some(Value::String(gid)) if gid == "me" => current.clone_into(gid),
+                Some(Value::Array(items)) => {
+                    for item in items.iter_mut().filter(|item| *item == "me") {
+                        *item = Value::String(current.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn not_found(kind: &str, gid: &str) -> ApiError {
+    ApiError::http(
+        StatusCode::NOT_FOUND,
+        format!("{kind} {gid} not found"),
+        None,
+    )
+}
+
+fn invalid(message: String) -> ApiError {
+    ApiError::http(StatusCode::BAD_REQUEST, message, None)
+}
+
+fn find<'a, T>(map: &'a BTreeMap<String, T>, kind: &str, gid: &str) -> Result<&'a T, ApiError> {
+    map.get(gid).ok_or_else(|| not_found(kind, gid))
+}
+
+fn find_mut<'a, T>(
+    map: &'a mut BTreeMap<String, T>,
+    kind: &str,
+    gid: &str,
+) -> Result<&'a mut T, ApiError> {
+    map.get_mut(gid).ok_or_else(|| not_found(kind, gid))
+}
+
+fn found<T: Clone>(map: &BTreeMap<String, T>, kind: &str, gid: &str) -> Result<T, ApiError> {
+    find(map, kind, gid).cloned()
+}
+
+/// Overlay the fields of `patch` on `record` and parse the result.
+fn merge<T: DeserializeOwned>(mut record: Value, patch: Value) -> Result<T, ApiError> {
+    if let (Value::Object(record), Value::Object(patch)) = (&mut record, patch) {
+        record.extend(patch);
+    }
+    Ok(serde_json::from_value(record)?)
+}
+
+/// Overlay the fields of `patch` on an existing record.
+fn patched<T: Serialize + DeserializeOwned>(record: &T, patch: Value) -> Result<T, ApiError> {
+    merge(serde_json::to_value(record)?, patch)
+}
+
+fn without(mut patch: Value, keys: &[&str]) -> Value {
+    if let Value::Object(map) = &mut patch {
+        for key in keys {
+            map.remove(*key);
+        }
+    }
+    patch
+}
+
+/// Turn gid strings (or arrays of them) under `keys` into `{"gid": ..}`
+/// references.
+fn as_references(patch: &mut Value, keys: &[&str]) {
+    if let Value::Object(map) = patch {
+        for key in keys {
+            if let Some(value) = map.get_mut(*key) {
+                *value = reference(value.take());
+            }
+        }
+    }
+}
+
+fn reference(value: Value) -> Value {
+    match value {
+        Value::String(gid) => json!({ "gid": gid }),
+        Value::Array(items) => Value::Array(items.into_iter().map(reference).collect()),
+        other => other,
+    }
+}
+
+fn user_reference(gid: &str) -> UserReference {
+    UserReference {
+        gid: gid.to_string(),
+        name: None,
+        resource_type: None,
+        email: None,
+    }
+}
+
+fn event_resource(gid: &str, resource_type: &str) -> EventResource {
+    EventResource {
+        gid: gid.to_string(),
+        resource_type: Some(resource_type.to_string()),
+        resource_subtype: None,
+        name: None,
+    }
+}
+
+fn section_project(section: &Section) -> Option<&str> {
+    section
+        .project
+        .as_ref()
+        .or_else(|| section.projects.first())
+        .map(|project| project.gid.as_str())
+}
+
+fn section_gids(task: &Task) -> impl Iterator<Item = &str> {
+    task.memberships
+        .iter()
+        .filter_map(|membership| membership.section.as_ref())
+        .map(|section| section.gid.as_str())
+}
+
+fn add_project_reference(task: &mut Task, project_gid: &str) {
+    if !task
+        .projects
+        .iter()
+        .any(|project| project.gid == project_gid)
+    {
+        task.projects.push(TaskProjectReference {
+            gid: project_gid.to_string(),
+            name: None,
+            resource_type: None,
+        });
+    }
+    sync_memberships(task);
+}
+
+/// Keep one membership per project the task belongs to.
+fn sync_memberships(task: &mut Task) {
+    let projects = &task.projects;
+    task.memberships.retain(|membership| {
+        membership
+            .project
+            .as_ref()
+            .is_some_and(|project| projects.iter().any(|kept| kept.gid == project.gid))
+    });
+    for project in projects {
+        let present = task.memberships.iter().any(|membership| {
+            membership
+                .project
+                .as_ref()
+                .is_some_and(|existing| existing.gid == project.gid)
+        });
+        if !present {
+            task.memberships.push(TaskMembership {
+                project: Some(project.clone()),
+                section: None,
+            });
+        }
+    }
+}
+
+fn set_reference(references: &mut Vec<TaskReference>, gid: &str, present: bool) {
+    let exists = references.iter().any(|reference| reference.gid == gid);
+    if present && !exists {
+        references.push(TaskReference {
+            gid: gid.to_string(),
+            name: None,
+            resource_type: None,
+        });
+    } else if !present {
+        references.retain(|reference| reference.gid != gid);
+    }
+}
+
+fn any_gid<'a>(wanted: &[String], mut gids: impl Iterator<Item = &'a str>) -> bool {
+    wanted.is_empty() || gids.any(|gid| wanted.iter().any(|wanted| wanted == gid))
+}
+
+fn within(value: Option<&String>, after: Option<&String>, before: Option<&String>) -> bool {
+    after.is_none_or(|after| value.is_some_and(|value| value > after))
+        && before.is_none_or(|before| value.is_some_and(|value| value < before))
+}
+
+fn done<'a, T: Send + 'a>(result: Result<T, ApiError>) -> BoxFuture<'a, Result<T, ApiError>> {
+    Box::pin(future::ready(result))
+}
+
+impl AsanaApi for FakeAsana {
+    fn list_attachments(
+        &self,
+        params: AttachmentListParams,
+    ) -> BoxFuture<'_, Result<Vec<Attachment>, ApiError>> {
+        let state = self.state();
+        done(find(&state.tasks, "task", &params.task_gid).map(|_| {
+            state
+                .attachments
+                .values()
+                .filter(|(task_gid, _)| *task_gid == params.task_gid)
+                .map(|(_, attachment)| attachment.clone())
+                .take(params.limit.unwrap_or(usize::MAX))
+                .collect()
+        }))
+    }
+
+    fn get_attachment<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<Attachment, ApiError>> {
+        done(
+            find(&self.state().attachments, "attachment", gid)
+                .map(|(_, attachment)| attachment.clone()),
+        )
+    }
+
+    fn delete_attachment<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(
+            self.state()
+                .attachments
+                .remove(gid)
+                .map(|_| ())
+                .ok_or_else(|| not_found("attachment", gid)),
+        )
+    }
+
+    fn list_custom_fields<'a>(
+        &'a self,
+        workspace_gid: &'a str,
+        limit: Option<usize>,
+    ) -> BoxFuture<'a, Result<Vec<CustomField>, ApiError>> {
+        done(Ok(self
+            .state()
+            .custom_fields
+            .values()
+            .filter(|(workspace, _)| workspace == workspace_gid)
+            .map(|(_, field)| field.clone())
+            .take(limit.unwrap_or(usize::MAX))
+            .collect()))
+    }
+
+    fn get_custom_field<'a>(
+        &'a self,
+        gid: &'a str,
+    ) -> BoxFuture<'a, Result<CustomField, ApiError>> {
+        done(find(&self.state().custom_fields, "custom field", gid).map(|(_, field)| field.clone()))
+    }
+
+    fn get_events<'a>(
+        &'a self,
+        resource: &'a str,
+        sync: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<EventBatch, ApiError>> {
+        done(Ok(self.state().events(resource, sync)))
+    }
+
+    fn list_projects(
+        &self,
+        params: ProjectListParams,
+    ) -> BoxFuture<'_, Result<Vec<Project>, ApiError>> {
+        done(Ok(self.state().list_projects(&params)))
+    }
+
+    fn get_project<'a>(
+        &'a self,
+        gid: &'a str,
+        _fields: Vec<String>,
+    ) -> BoxFuture<'a, Result<Project, ApiError>> {
+        done(found(&self.state().projects, "project", gid))
+    }
+
+    fn create_project(
+        &self,
+        request: ProjectCreateRequest,
+    ) -> BoxFuture<'_, Result<Project, ApiError>> {
+        done(self.state().create_project(&request.data))
+    }
+
+    fn update_project<'a>(
+        &'a self,
+        gid: &'a str,
+        request: ProjectUpdateRequest,
+    ) -> BoxFuture<'a, Result<Project, ApiError>> {
+        done(
+            serde_json::to_value(request.data)
+                .map_err(ApiError::from)
+                .and_then(|patch| self.state().update_project(gid, patch)),
+        )
+    }
+
+    fn delete_project<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().delete_project(gid))
+    }
+
+    fn list_members<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<ProjectMembers, ApiError>> {
+        done(
+            find(&self.state().projects, "project", gid).map(|project| ProjectMembers {
+                project_gid: gid.to_string(),
+                members: project.members.clone(),
+            }),
+        )
+    }
+
+    fn list_statuses<'a>(
+        &'a self,
+        gid: &'a str,
+        limit: Option<usize>,
+    ) -> BoxFuture<'a, Result<Vec<ProjectStatus>, ApiError>> {
+        done(find(&self.state().projects, "project", gid).map(|project| {
+            project
+                .statuses
+                .iter()
+                .take(limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect()
+        }))
+    }
+
+    fn add_members<'a>(
+        &'a self,
+        gid: &'a str,
+        members: Vec<String>,
+        role: Option<MemberPermission>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().add_members(gid, &members, role.as_ref()))
+    }
+
+    fn remove_members<'a>(
+        &'a self,
+        gid: &'a str,
+        members: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().remove_members(gid, &members))
+    }
+
+    fn update_member<'a>(
+        &'a self,
+        membership_gid: &'a str,
+        role: MemberPermission,
+    ) -> BoxFuture<'a, Result<ProjectMember, ApiError>> {
+        done(self.state().update_member(membership_gid, role))
+    }
+
+    fn list_sections<'a>(
+        &'a self,
+        project_gid: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Section>, ApiError>> {
+        done(self.state().list_sections(project_gid))
+    }
+
+    fn get_section<'a>(
+        &'a self,
+        gid: &'a str,
+        _fields: Vec<String>,
+    ) -> BoxFuture<'a, Result<Section, ApiError>> {
+        done(found(&self.state().sections, "section", gid))
+    }
+
+    fn create_section<'a>(
+        &'a self,
+        project_gid: &'a str,
+        request: SectionCreateRequest,
+    ) -> BoxFuture<'a, Result<Section, ApiError>> {
+        done(self.state().create_section(project_gid, request.data.name))
+    }
+
+    fn get_section_tasks<'a>(
+        &'a self,
+        gid: &'a str,
+        _fields: Vec<String>,
+    ) -> BoxFuture<'a, Result<Vec<Task>, ApiError>> {
+        done(self.state().section_tasks(gid))
+    }
+
+    fn add_task_to_section<'a>(
+        &'a self,
+        section_gid: &'a str,
+        task_gid: String,
+        _insert_before: Option<String>,
+        _insert_after: Option<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        let mut state = self.state();
+        let result = find(&state.tasks, "task", &task_gid)
+            .cloned()
+            .and_then(|mut task| {
+                state.place(&mut task, section_gid)?;
+                state.change_task(&task_gid, |current| {
+                    *current = task;
+                    Ok(())
+                })
+            });
+        done(result)
+    }
+
+    fn list_stories(&self, params: StoryListParams) -> BoxFuture<'_, Result<Vec<Story>, ApiError>> {
+        done(self.state().list_stories(&params))
+    }
+
+    fn get_story<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<Story, ApiError>> {
+        done(find(&self.state().stories, "story", gid).map(|(_, story)| story.clone()))
+    }
+
+    fn create_story<'a>(
+        &'a self,
+        task_gid: &'a str,
+        request: StoryCreateRequest,
+    ) -> BoxFuture<'a, Result<Story, ApiError>> {
+        done(self.state().create_story(task_gid, request.data))
+    }
+
+    fn update_story<'a>(
+        &'a self,
+        gid: &'a str,
+        request: StoryUpdateRequest,
+    ) -> BoxFuture<'a, Result<Story, ApiError>> {
+        done(self.state().update_story(gid, request.data))
+    }
+
+    fn delete_story<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().delete_story(gid))
+    }
+
+    fn list_tags(&self, params: TagListParams) -> BoxFuture<'_, Result<Vec<Tag>, ApiError>> {
+        done(Ok(self
+            .state()
+            .tags
+            .values()
+            .filter(|tag| {
+                tag.workspace
+                    .as_ref()
+                    .is_some_and(|workspace| workspace.gid == params.workspace)
+            })
+            .take(params.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()))
+    }
+
+    fn get_tag<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<Tag, ApiError>> {
+        done(found(&self.state().tags, "tag", gid))
+    }
+
+    fn create_tag(&self, request: TagCreateRequest) -> BoxFuture<'_, Result<Tag, ApiError>> {
+        done(Ok(self.state().create_tag(request.data)))
+    }
+
+    fn update_tag<'a>(
+        &'a self,
+        gid: &'a str,
+        request: TagUpdateRequest,
+    ) -> BoxFuture<'a, Result<Tag, ApiError>> {
+        done(self.state().update_tag(gid, request.data))
+    }
+
+    fn delete_tag<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().delete_tag(gid))
+    }
+
+    fn list_tasks(&self, params: TaskListParams) -> BoxFuture<'_, Result<Vec<Task>, ApiError>> {
+        done(Ok(self.state().list_tasks(&params)))
+    }
+
+    fn search_tasks(&self, params: TaskSearchParams) -> BoxFuture<'_, Result<Vec<Task>, ApiError>> {
+        done(Ok(self.state().search_tasks(&params)))
+    }
+
+    fn get_task<'a>(
+        &'a self,
+        gid: &'a str,
+        _fields: Vec<String>,
+    ) -> BoxFuture<'a, Result<Task, ApiError>> {
+        done(found(&self.state().tasks, "task", gid))
+    }
+
+    fn create_task(&self, request: TaskCreateRequest) -> BoxFuture<'_, Result<Task, ApiError>> {
+        done(self.state().create_task(&request.data))
+    }
+
+    fn find_task_by_external_id<'a>(
+        &'a self,
+        external_id: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Task>, ApiError>> {
+        done(Ok(self.state().task_by_external_id(external_id)))
+    }
+
+    fn update_task<'a>(
+        &'a self,
+        gid: &'a str,
+        request: TaskUpdateRequest,
+    ) -> BoxFuture<'a, Result<Task, ApiError>> {
+        done(self.state().update_task(gid, &request.data))
+    }
+
+    fn delete_task<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().delete_task(gid))
+    }
+
+    fn list_subtasks<'a>(
+        &'a self,
+        gid: &'a str,
+        _fields: Vec<String>,
+    ) -> BoxFuture<'a, Result<Vec<Task>, ApiError>> {
+        let state = self.state();
+        done(find(&state.tasks, "task", gid).map(|_| state.subtasks(gid)))
+    }
+
+    fn list_dependencies<'a>(
+        &'a self,
+        gid: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<TaskReference>, ApiError>> {
+        done(find(&self.state().tasks, "task", gid).map(|task| task.dependencies.clone()))
+    }
+
+    fn list_dependents<'a>(
+        &'a self,
+        gid: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<TaskReference>, ApiError>> {
+        done(find(&self.state().tasks, "task", gid).map(|task| task.dependents.clone()))
+    }
+
+    fn add_dependencies<'a>(
+        &'a self,
+        gid: &'a str,
+        dependencies: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        let pairs = dependencies
+            .into_iter()
+            .map(|dependency| (gid.to_string(), dependency))
+            .collect::<Vec<_>>();
+        done(self.state().link(&pairs, true))
+    }
+
+    fn remove_dependencies<'a>(
+        &'a self,
+        gid: &'a str,
+        dependencies: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        let pairs = dependencies
+            .into_iter()
+            .map(|dependency| (gid.to_string(), dependency))
+            .collect::<Vec<_>>();
+        done(self.state().link(&pairs, false))
+    }
+
+    fn add_dependents<'a>(
+        &'a self,
+        gid: &'a str,
+        dependents: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        let pairs = dependents
+            .into_iter()
+            .map(|dependent| (dependent, gid.to_string()))
+            .collect::<Vec<_>>();
+        done(self.state().link(&pairs, true))
+    }
+
+    fn remove_dependents<'a>(
+        &'a self,
+        gid: &'a str,
+        dependents: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        let pairs = dependents
+            .into_iter()
+            .map(|dependent| (dependent, gid.to_string()))
+            .collect::<Vec<_>>();
+        done(self.state().link(&pairs, false))
+    }
+
+    fn add_project<'a>(
+        &'a self,
+        gid: &'a str,
+        project: String,
+        section: Option<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().add_project(gid, &project, section.as_deref()))
+    }
+
+    fn remove_project<'a>(
+        &'a self,
+        gid: &'a str,
+        project: String,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().change_task(gid, |task| {
+            task.projects.retain(|existing| existing.gid != project);
+            sync_memberships(task);
+            Ok(())
+        }))
+    }
+
+    fn add_followers<'a>(
+        &'a self,
+        gid: &'a str,
+        followers: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().set_followers(gid, &followers, true))
+    }
+
+    fn remove_followers<'a>(
+        &'a self,
+        gid: &'a str,
+        followers: Vec<String>,
+    ) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().set_followers(gid, &followers, false))
+    }
+
+    fn add_tag<'a>(&'a self, gid: &'a str, tag: String) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().set_tag(gid, &tag, true))
+    }
+
+    fn remove_tag<'a>(&'a self, gid: &'a str, tag: String) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(self.state().set_tag(gid, &tag, false))
+    }
+
+    fn list_users(&self, params: UserListParams) -> BoxFuture<'_, Result<Vec<User>, ApiError>> {
+        done(Ok(self
+            .state()
+            .users
+            .values()
+            .filter(|user| {
+                user.workspaces
+                    .iter()
+                    .any(|workspace| workspace.gid == params.workspace_gid)
+            })
+            .take(params.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()))
+    }
+
+    fn get_user<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<User, ApiError>> {
+        let state = self.state();
+        done(found(&state.users, "user", state.user_gid(gid)))
+    }
+
+    fn get_current_user(&self) -> BoxFuture<'_, Result<User, ApiError>> {
+        done(self.state().current_user())
+    }
+
+    fn list_webhooks(
+        &self,
+        params: WebhookListParams,
+    ) -> BoxFuture<'_, Result<Vec<Webhook>, ApiError>> {
+        done(Ok(self.state().list_webhooks(&params)))
+    }
+
+    fn get_webhook<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<Webhook, ApiError>> {
+        done(find(&self.state().webhooks, "webhook", gid).map(|(_, webhook)| webhook.clone()))
+    }
+
+    fn create_webhook(
+        &self,
+        request: WebhookCreateRequest,
+    ) -> BoxFuture<'_, Result<Webhook, ApiError>> {
+        done(Ok(self.state().create_webhook(request.data)))
+    }
+
+    fn delete_webhook<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), ApiError>> {
+        done(
+            self.state()
+                .webhooks
+                .remove(gid)
+                .map(|_| ())
+                .ok_or_else(|| not_found("webhook", gid)),
+        )
+    }
+
+    fn list_workspaces(
+        &self,
+        params: WorkspaceListParams,
+    ) -> BoxFuture<'_, Result<Vec<Workspace>, ApiError>> {
+        done(Ok(self
+            .state()
+            .workspaces
+            .values()
+            .take(params.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()))
+    }
+
+    fn get_workspace<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<Workspace, ApiError>> {
+        done(found(&self.state().workspaces, "workspace", gid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::TaskUpsert,
+        models::{SectionCreateData, TaskCreateBuilder, TaskUpdateBuilder},
+    };
+
+    #[tokio::test]
+    async fn tasks_created_through_the_trait_are_listed_and_reported() {
+        let fake = FakeAsana::new();
+        fake.set_current_user(User {
+            gid: "42".into(),
+            name: "Ada".into(),
+            email: None,
+            resource_type: None,
+            photo: None,
+            workspaces: Vec::new(),
+        });
+        let api: &dyn AsanaApi = &fake;
+
+        let project = api
+            .create_project(ProjectCreateRequest {
+                data: ProjectCreateData {
+                    name: "Launch".into(),
+                    workspace: Some("1".into()),
+                    ..ProjectCreateData::default()
+                },
+            })
+            .await
+            .unwrap();
+        let section = api
+            .create_section(
+                &project.gid,
+                SectionCreateRequest {
+                    data: SectionCreateData {
+                        name: "Doing".into(),
+                        insert_before: None,
+                        insert_after: None,
+                    },
+                },
+            )
+            .await
+            .unwrap();
+        let sync = api.get_events(&project.gid, None).await.unwrap().sync;
+
+        let request = TaskCreateBuilder::new("Write docs")
+            .workspace("1")
+            .section(section.gid.clone())
+            .assignee("me")
+            .external_id("docs")
+            .build()
+            .unwrap();
+        let (task, outcome) = api.create_or_update_task(request.clone()).await.unwrap();
+        assert_eq!(outcome, TaskUpsert::Created);
+        assert_eq!(task.assignee.unwrap().gid, "42");
+        assert_eq!(task.projects[0].gid, project.gid);
+        let (again, outcome) = api.create_or_update_task(request).await.unwrap();
+        assert_eq!(
+            (again.gid.as_str(), outcome),
+            (task.gid.as_str(), TaskUpsert::Updated)
+        );
+
+        let update = TaskUpdateBuilder::new().completed(true).build().unwrap();
+        let done = api.update_task(&task.gid, update).await.unwrap();
+        assert!(done.completed_at.is_some());
+        let in_section = api
+            .get_section_tasks(&section.gid, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(in_section.len(), 1);
+
+        let open = api
+            .list_tasks(TaskListParams {
+                project: Some(project.gid.clone()),
+                completed: Some(false),
+                ..TaskListParams::default()
+            })
+            .await
+            .unwrap();
+        assert!(open.is_empty());
+
+        let batch = api.get_events(&project.gid, Some(&sync)).await.unwrap();
+        let actions: Vec<&str> = batch
+            .events
+            .iter()
+            .map(|event| event.action.as_str())
+            .collect();
+        assert_eq!(actions, ["added", "changed", "changed"]);
+        assert!(!batch.resynced);
+
+        let missing = api.get_task("0", Vec::new()).await.unwrap_err();
+        assert_eq!(missing.status(), Some(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,75 @@
+//! Lock files serialising updates to state shared between `asana-cli`
+//! processes.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tokio::{fs, io::AsyncWriteExt, time::sleep};
+
+/// Locks older than this are assumed to belong to a crashed process.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(5);
+/// Pause between attempts while another process holds the lock.
+const RETRY_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Held lock file, removed again when dropped.
+#[derive(Debug)]
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Create `path` exclusively, waiting up to `timeout` while another
+    /// process holds it.
+    ///
+    /// # Errors
+    /// Returns [`io::ErrorKind::TimedOut`] if the lock stays taken, or the
+    /// error from creating the lock file.
+    pub async fn acquire(path: &Path, timeout: Duration) -> io::Result<Self> {
+        let started = SystemTime::now();
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .await
+            {
+                Ok(mut file) => {
+                    file.write_all(std::process::id().to_string().as_bytes())
+                        .await
+                        .ok();
+                    return Ok(Self {
+                        path: path.to_path_buf(),
+                    });
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(path)
+                        .await
+                        .ok()
+                        .and_then(|meta| meta.modified().ok())
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > STALE_LOCK_AGE);
+                    if stale {
+                        fs::remove_file(path).await.ok();
+                        continue;
+                    }
+                    if started.elapsed().unwrap_or_default() > timeout {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("{} is locked by another process", path.display()),
+                        ));
+                    }
+                    sleep(RETRY_INTERVAL).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path).ok();
+    }
+}
@@ -0,0 +1,506 @@
+//! HTTP traces in HAR 1.2 format.
+//!
+//! With tracing enabled every HTTP exchange made by `ApiClient` is appended to
+//! a HAR log, including retried attempts, failed requests, and responses
+//! answered from the cache (marked with the `_fromCache` extension field).
+//! Authorization headers are redacted. Each entry is appended in place and
+//! only the closing brackets after it are rewritten, so the trace stays valid
+//! JSON when the process is interrupted.
+
+use crate::api::error::ApiError;
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::{SecondsFormat, Utc};
+use reqwest::{
+    Method, Request, StatusCode, Version,
+    header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap},
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex, PoisonError},
+    time::Duration,
+};
+use tracing::warn;
+
+/// Placeholder written in place of credentials.
+const REDACTED: &str = "Bearer [REDACTED]";
+
+/// Recorders shared by every client tracing to the same file, so that
+/// several clients in one process append to one log instead of truncating it.
+static RECORDERS: LazyLock<Mutex<HashMap<PathBuf, Arc<HarRecorder>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Appends HAR entries to a trace file.
+#[derive(Debug)]
+pub(crate) struct HarRecorder {
+    file: Mutex<HarFile>,
+}
+
+#[derive(Debug)]
+struct HarFile {
+    file: fs::File,
+    /// Offset where the closing brackets after the last entry start.
+    entries_end: u64,
+    entries: usize,
+    /// Brackets closing the document after at least one entry.
+    tail: String,
+}
+
+impl HarRecorder {
+    /// Recorder for `path`, creating (or truncating) the file on first use.
+    pub(crate) fn shared(path: &Path) -> Result<Arc<Self>, ApiError> {
+        let mut recorders = RECORDERS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(recorder) = recorders.get(path) {
+            return Ok(Arc::clone(recorder));
+        }
+        let recorder = Arc::new(Self::create(path)?);
+        recorders.insert(path.to_path_buf(), Arc::clone(&recorder));
+        Ok(recorder)
+    }
+
+    /// Write an empty log to `path`, remembering where entries go.
+    fn create(path: &Path) -> Result<Self, ApiError> {
+        let empty = serde_json::to_string_pretty(&HarDocument {
+            log: HarLog {
+                version: "1.2",
+                creator: HarCreator {
+                    name: "asana-cli",
+                    version: env!("CARGO_PKG_VERSION"),
+                },
+                entries: &[],
+            },
+        })?;
+        let split = empty.find("[]").map_or(empty.len(), |index| index + 1);
+        let (head, empty_tail) = empty.split_at(split);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        file.write_all(empty.as_bytes())?;
+        Ok(Self {
+            file: Mutex::new(HarFile {
+                file,
+                entries_end: head.len() as u64,
+                entries: 0,
+                tail: format!("\n{ENTRY_INDENT_OUTER}{empty_tail}"),
+            }),
+        })
+    }
+
+    /// Append `entry` to the trace file.
+    pub(crate) fn record(&self, entry: &HarEntry) {
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Err(err) = file.append(entry) {
+            warn!("failed to write HTTP trace: {err}");
+        }
+    }
+}
+
+/// Indentation of the `entries` array's closing bracket in the pretty log.
+const ENTRY_INDENT_OUTER: &str = "    ";
+
+/// Indentation of each entry in the pretty log.
+const ENTRY_INDENT: &str = "      ";
+
+impl HarFile {
+    /// Overwrite the closing brackets with `entry` followed by new ones.
+    fn append(&mut self, entry: &HarEntry) -> Result<(), ApiError> {
+        let serialized = serde_json::to_string_pretty(entry)?;
+        let mut chunk = String::from(if self.entries == 0 { "\n" } else { ",\n" });
+        for (index, line) in serialized.lines().enumerate() {
+            if index > 0 {
+                chunk.push('\n');
+            }
+            chunk.push_str(ENTRY_INDENT);
+            chunk.push_str(line);
+        }
+        self.file.seek(SeekFrom::Start(self.entries_end))?;
+        self.file.write_all(chunk.as_bytes())?;
+        self.file.write_all(self.tail.as_bytes())?;
+        self.file.flush()?;
+        self.entries_end += chunk.len() as u64;
+        self.entries += 1;
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct HarDocument<'a> {
+    log: HarLog<'a>,
+}
+
+#[derive(Serialize)]
+struct HarLog<'a> {
+    version: &'static str,
+    creator: HarCreator,
+    entries: &'a [HarEntry],
+}
+
+#[derive(Serialize)]
+struct HarCreator {
+    name: &'static str,
+    version: &'static str,
+}
+
+/// One request/response pair.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarEntry {
+    started_date_time: String,
+    time: f64,
+    request: HarRequest,
+    response: HarResponse,
+    cache: HarCache,
+    timings: HarTimings,
+    #[serde(rename = "_fromCache", skip_serializing_if = "Option::is_none")]
+    from_cache: Option<&'static str>,
+    #[serde(rename = "_error", skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    http_version: String,
+    cookies: [(); 0],
+    headers: Vec<HarHeader>,
+    query_string: Vec<HarHeader>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    post_data: Option<HarPostData>,
+    headers_size: i64,
+    body_size: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarPostData {
+    mime_type: String,
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarResponse {
+    status: u16,
+    status_text: String,
+    http_version: String,
+    cookies: [(); 0],
+    headers: Vec<HarHeader>,
+    content: HarContent,
+    #[serde(rename = "redirectURL")]
+    redirect_url: String,
+    headers_size: i64,
+    body_size: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HarContent {
+    size: i64,
+    mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct HarCache {}
+
+#[derive(Debug, Serialize)]
+struct HarTimings {
+    send: f64,
+    wait: f64,
+    receive: f64,
+}
+
+/// Request half of an entry, captured before the request is sent.
+#[derive(Debug)]
+pub(crate) struct PendingEntry {
+    started_date_time: String,
+    request: HarRequest,
+}
+
+impl PendingEntry {
+    /// Capture `request`, redacting credentials. Streamed bodies (multipart
+    /// uploads) are noted but not copied.
+    pub(crate) fn new(request: &Request) -> Self {
+        let post_data = request.body().map(|body| {
+            let mime_type = header_value(request.headers(), CONTENT_TYPE.as_str());
+            match body.as_bytes() {
+                Some(bytes) => HarPostData {
+                    mime_type,
+                    text: String::from_utf8_lossy(bytes).into_owned(),
+                    comment: None,
+                },
+                None => HarPostData {
+                    mime_type,
+                    text: String::new(),
+                    comment: Some("streamed body not captured"),
+                },
+            }
+        });
+        let body_size = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map_or(-1, |bytes| len_i64(bytes.len()));
+        Self {
+            started_date_time: now(),
+            request: HarRequest {
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                http_version: http_version(request.version()),
+                cookies: [],
+                headers: har_headers(request.headers()),
+                query_string: request
+                    .url()
+                    .query_pairs()
+                    .map(|(name, value)| HarHeader {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect(),
+                post_data,
+                headers_size: -1,
+                body_size,
+            },
+        }
+    }
+
+    /// Complete the entry with a response. `body` is `None` when the caller
+    /// streams the body elsewhere (downloads).
+    pub(crate) fn respond(
+        self,
+        status: StatusCode,
+        version: Version,
+        headers: &HeaderMap,
+        body: Option<&[u8]>,
+        wait: Duration,
+        receive: Duration,
+    ) -> HarEntry {
+        let mime_type = header_value(headers, CONTENT_TYPE.as_str());
+        let content = match body {
+            Some(bytes) => {
+                let (text, encoding) = std::str::from_utf8(bytes).map_or_else(
+                    |_| (STANDARD.encode(bytes), Some("base64")),
+                    |text| (text.to_string(), None),
+                );
+                HarContent {
+                    size: len_i64(bytes.len()),
+                    mime_type,
+                    text: Some(text),
+                    encoding,
+                    comment: None,
+                }
+            }
+            None => HarContent {
+                size: -1,
+                mime_type,
+                text: None,
+                encoding: None,
+                comment: Some("streamed body not captured"),
+            },
+        };
+        let body_size = body.map_or(-1, |bytes| len_i64(bytes.len()));
+        HarEntry {
+            started_date_time: self.started_date_time,
+            time: millis(wait + receive),
+            request: self.request,
+            response: HarResponse {
+                status: status.as_u16(),
+                status_text: status.canonical_reason().unwrap_or_default().to_string(),
+                http_version: http_version(version),
+                cookies: [],
+                headers: har_headers(headers),
+                content,
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size,
+            },
+            cache: HarCache::default(),
+            timings: HarTimings {
+                send: 0.0,
+                wait: millis(wait),
+                receive: millis(receive),
+            },
+            from_cache: None,
+            error: None,
+        }
+    }
+
+    /// Complete the entry for a request that never produced a response.
+    pub(crate) fn fail(self, error: &reqwest::Error, elapsed: Duration) -> HarEntry {
+        HarEntry {
+            started_date_time: self.started_date_time,
+            time: millis(elapsed),
+            request: self.request,
+            response: HarResponse {
+                status: 0,
+                status_text: String::new(),
+                http_version: String::new(),
+                cookies: [],
+                headers: Vec::new(),
+                content: HarContent {
+                    size: 0,
+                    mime_type: String::new(),
+                    text: None,
+                    encoding: None,
+                    comment: None,
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            cache: HarCache::default(),
+            timings: HarTimings {
+                send: 0.0,
+                wait: millis(elapsed),
+                receive: 0.0,
+            },
+            from_cache: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+impl HarEntry {
+    /// Entry for a GET answered without contacting Asana. `source` is
+    /// `"cache"` for fresh entries and `"stale"` for expired ones.
+    pub(crate) fn cache_hit(
+        method: &Method,
+        url: &str,
+        query: &[(String, String)],
+        body: &[u8],
+        source: &'static str,
+    ) -> Self {
+        let url = reqwest::Url::parse_with_params(url, query)
+            .map_or_else(|_| url.to_string(), String::from);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+        let mut entry = PendingEntry {
+            started_date_time: now(),
+            request: HarRequest {
+                method: method.to_string(),
+                url,
+                http_version: http_version(Version::HTTP_11),
+                cookies: [],
+                headers: Vec::new(),
+                query_string: query
+                    .iter()
+                    .map(|(name, value)| HarHeader {
+                        name: name.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+                post_data: None,
+                headers_size: -1,
+                body_size: 0,
+            },
+        }
+        .respond(
+            StatusCode::OK,
+            Version::HTTP_11,
+            &headers,
+            Some(body),
+            Duration::ZERO,
+            Duration::ZERO,
+        );
+        entry.from_cache = Some(source);
+        entry
+    }
+}
+
+fn har_headers(headers: &HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.to_string(),
+            value: if name == AUTHORIZATION {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            },
+        })
+        .collect()
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn http_version(version: Version) -> String {
+    format!("{version:?}")
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn len_i64(len: usize) -> i64 {
+    i64::try_from(len).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appended_entries_match_the_whole_document() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("trace.har");
+        let recorder = HarRecorder::create(&path).expect("create");
+        let entries: Vec<HarEntry> = ["1", "2"]
+            .iter()
+            .map(|gid| {
+                HarEntry::cache_hit(
+                    &Method::GET,
+                    &format!("https://app.asana.com/api/1.0/tasks/{gid}"),
+                    &[],
+                    b"{}",
+                    "cache",
+                )
+            })
+            .collect();
+        for (count, entry) in entries.iter().enumerate() {
+            recorder.record(entry);
+            let expected = serde_json::to_string_pretty(&HarDocument {
+                log: HarLog {
+                    version: "1.2",
+                    creator: HarCreator {
+                        name: "asana-cli",
+                        version: env!("CARGO_PKG_VERSION"),
+                    },
+                    entries: &entries[..=count],
+                },
+            })
+            .expect("json");
+            assert_eq!(fs::read_to_string(&path).expect("read"), expected);
+        }
+    }
+}
@@ -1,42 +1,77 @@
 //! Asana API client module providing authenticated HTTP access, pagination,
 //! and rate-limit aware retry logic.
 
+pub mod asana_api;
 pub mod attachments;
 pub mod auth;
+pub mod batch;
+pub mod cache;
+pub mod cassette;
 pub mod client;
+pub mod concurrency;
 pub mod custom_fields;
 pub mod error;
+pub mod events;
+#[cfg(any(test, feature = "fake"))]
+pub mod fake;
+mod file_lock;
+pub mod har;
+pub mod network;
+pub mod oauth;
 pub mod pagination;
+pub mod progress;
 pub mod projects;
+pub mod rate_limit;
+pub mod raw;
 pub mod sections;
 pub mod stories;
 pub mod tags;
 pub mod tasks;
 pub mod users;
+pub mod webhooks;
 pub mod workspaces;
 
+pub use asana_api::AsanaApi;
 pub use attachments::{
-    delete_attachment, download_attachment, get_attachment, list_attachments, upload_attachment,
+    delete_attachment, download_attachment, download_attachment_with_progress, get_attachment,
+    list_attachments, upload_attachment, upload_attachment_with_progress,
 };
-pub use auth::{AuthToken, StaticTokenProvider, TokenProvider};
+pub use auth::{
+    AccessTokenProvider, AuthToken, CommandTokenProvider, StaticTokenProvider, TokenProvider,
+};
+pub use batch::{BatchAction, MAX_BATCH_ACTIONS};
+pub use cassette::CassetteMode;
 pub use client::{ApiClient, ApiClientBuilder, ApiClientOptions};
+pub use concurrency::{DEFAULT_CONCURRENCY, bounded, map_bounded};
 pub use custom_fields::{get_custom_field, list_custom_fields};
-pub use error::{ApiError, RateLimitInfo};
-pub use pagination::{ListResponse, PaginationInfo};
+pub use error::{ApiError, AsanaErrorDetail, PaginationExpired, RateLimitInfo, ReplayMiss};
+pub use events::{EventBatch, SyncTokenStore, get_events};
+pub use network::{HandshakeOutcome, NetworkOptions, check_handshake};
+pub use oauth::{OAuthApp, OAuthTokenProvider, OAuthTokens};
+pub use pagination::{ListResponse, ListingState, PaginationInfo};
+pub use progress::TransferProgress;
 pub use projects::{
     add_members, create_project, delete_project, get_project, list_members, list_projects,
-    list_statuses, remove_members, update_member, update_project,
+    list_projects_stream, list_statuses, remove_members, update_member, update_project,
 };
+pub use rate_limit::RateLimiter;
+pub use raw::get_all_pages;
 pub use sections::{
     add_task_to_section, create_section, get_section, get_section_tasks, list_sections,
 };
 pub use stories::{create_story, delete_story, get_story, list_stories, update_story};
 pub use tags::{create_tag, delete_tag, get_tag, list_tags, update_tag};
 pub use tasks::{
-    add_dependencies, add_dependents, add_followers, add_project, add_tag, create_task,
-    delete_task, get_task, list_dependencies, list_dependents, list_subtasks, list_tasks,
-    remove_dependencies, remove_dependents, remove_followers, remove_project, remove_tag,
-    search_tasks, update_task,
+    TaskUpsert, add_dependencies, add_dependents, add_followers, add_project, add_tag,
+    create_or_update_task, create_task, create_tasks_batch, delete_task, find_task_by_external_id,
+    get_task, get_task_with_exact_fields, list_dependencies, list_dependents, list_subtasks,
+    list_tasks, list_tasks_stream, remove_dependencies, remove_dependents, remove_followers,
+    remove_project, remove_tag, search_tasks, search_tasks_with_exact_fields, update_task,
+    update_tasks_batch,
+};
+pub use users::{get_current_user, get_user, list_users, list_users_stream};
+pub use webhooks::{
+    WebhookRequest, WebhookSecretStore, create_webhook, delete_webhook, get_webhook,
+    handle_webhook_request, list_webhooks,
 };
-pub use users::{get_current_user, get_user, list_users};
 pub use workspaces::{get_workspace, list_workspaces};
@@ -0,0 +1,168 @@
+//! TLS trust, client identity, and proxy settings for the HTTP client.
+//!
+//! Networks that intercept TLS need their own CA certificate trusted next to
+//! the bundled roots, some require a client certificate, and some only allow
+//! traffic through an explicit proxy. [`NetworkOptions`] carries those
+//! settings into the `reqwest` client; [`check_handshake`] lets `doctor`
+//! explain why a connection to Asana fails.
+
+use crate::api::ApiError;
+use reqwest::{Certificate, ClientBuilder, Identity, NoProxy, Proxy};
+use std::{error::Error as _, path::PathBuf, time::Duration};
+
+/// TLS and proxy settings applied to every HTTP client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkOptions {
+    /// PEM file with extra CA certificates to trust, e.g. an intercepting
+    /// proxy's root.
+    pub ca_bundle: Option<PathBuf>,
+    /// PEM file holding a client certificate chain and its private key.
+    pub client_cert: Option<PathBuf>,
+    /// Proxy URL for all requests, replacing `HTTPS_PROXY` and friends.
+    pub proxy: Option<String>,
+    /// Comma separated hosts that bypass `proxy`.
+    pub no_proxy: Option<String>,
+}
+
+impl NetworkOptions {
+    /// Add the configured trust roots, identity, and proxy to `builder`.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Other`] if a certificate file cannot be read or
+    /// parsed, or the proxy URL is invalid.
+    pub fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder, ApiError> {
+        if let Some(path) = &self.ca_bundle {
+            let pem = read_pem(path, "CA bundle")?;
+            let certs = Certificate::from_pem_bundle(&pem).map_err(|err| {
+                ApiError::Other(format!("invalid CA bundle {}: {err}", path.display()))
+            })?;
+            if certs.is_empty() {
+                return Err(ApiError::Other(format!(
+                    "CA bundle {} contains no PEM certificates",
+                    path.display()
+                )));
+            }
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        if let Some(path) = &self.client_cert {
+            let pem = read_pem(path, "client certificate")?;
+            let identity = Identity::from_pem(&pem).map_err(|err| {
+                ApiError::Other(format!(
+                    "invalid client certificate {} (expected certificate and private key in PEM): {err}",
+                    path.display()
+                ))
+            })?;
+            builder = builder.identity(identity);
+        }
+        if let Some(url) = &self.proxy {
+            let proxy = Proxy::all(url)
+                .map_err(|err| ApiError::Other(format!("invalid proxy URL `{url}`: {err}")))?
+                .no_proxy(self.no_proxy.as_deref().and_then(NoProxy::from_string));
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+}
+
+fn read_pem(path: &PathBuf, what: &str) -> Result<Vec<u8>, ApiError> {
+    std::fs::read(path)
+        .map_err(|err| ApiError::Other(format!("failed to read {what} {}: {err}", path.display())))
+}
+
+/// Result of [`check_handshake`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeOutcome {
+    /// The TLS session was established (any HTTP status counts).
+    Established,
+    /// The host could not be reached at all, so TLS was never attempted.
+    Unreachable(String),
+    /// The connection failed during TLS or at the proxy.
+    Failed(String),
+}
+
+/// Connect to `url` with `options` and report whether TLS succeeds.
+///
+/// # Errors
+/// Returns [`ApiError::Other`] if `options` cannot be applied.
+pub async fn check_handshake(
+    options: &NetworkOptions,
+    url: &str,
+) -> Result<HandshakeOutcome, ApiError> {
+    let client = options
+        .apply(reqwest::Client::builder().timeout(Duration::from_secs(10)))?
+        .build()?;
+    match client.head(url).send().await {
+        Ok(_) => Ok(HandshakeOutcome::Established),
+        Err(err) => {
+            let chain = error_chain(&err);
+            Ok(explain_failure(&chain).map_or_else(
+                || HandshakeOutcome::Unreachable(chain.clone()),
+                |hint| HandshakeOutcome::Failed(format!("{chain}\n{hint}")),
+            ))
+        }
+    }
+}
+
+/// Join `err` and its sources, since `reqwest` keeps the TLS cause nested.
+fn error_chain(err: &reqwest::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Hint for a TLS or proxy failure, or `None` when the message describes a
+/// plain connectivity problem.
+fn explain_failure(message: &str) -> Option<&'static str> {
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("unknownissuer") || lower.contains("unknown issuer") {
+        Some(
+            "The server certificate is signed by an unknown CA. If a proxy intercepts TLS, \
+             set `ca_bundle` to its CA certificate (PEM).",
+        )
+    } else if lower.contains("notvalidforname") || lower.contains("not valid for name") {
+        Some(
+            "The certificate does not match the host name; check `api_base_url` or the \
+             intercepting proxy's certificate.",
+        )
+    } else if lower.contains("expired") || lower.contains("notvalidyet") {
+        Some("The certificate is expired or not yet valid; check the system clock.")
+    } else if lower.contains("certificate required") || lower.contains("certificaterequired") {
+        Some("The server requires a client certificate; set `client_cert`.")
+    } else if lower.contains("certificate") || lower.contains("handshake") || lower.contains("tls")
+    {
+        Some("The TLS handshake failed; check `ca_bundle` and `client_cert`.")
+    } else if lower.contains("proxy") || lower.contains("tunnel") {
+        Some("The proxy refused the connection; check `proxy` and `no_proxy`.")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn certificate_failures_are_explained() {
+        let hint = explain_failure(
+            "error sending request: client error (Connect): invalid peer certificate: UnknownIssuer",
+        )
+        .expect("certificate failure");
+        assert!(hint.contains("ca_bundle"));
+        assert!(explain_failure("dns error: failed to lookup address information").is_none());
+
+        let missing = NetworkOptions {
+            ca_bundle: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..NetworkOptions::default()
+        };
+        let err = missing.apply(reqwest::Client::builder()).unwrap_err();
+        assert!(err.to_string().contains("failed to read CA bundle"));
+    }
+}
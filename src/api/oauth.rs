@@ -0,0 +1,500 @@
+//! OAuth 2.0 authorization-code flow with PKCE and refresh tokens.
+//!
+//! `auth login` opens the Asana consent page, receives the authorization code
+//! on a loopback listener, and exchanges it (together with the PKCE verifier)
+//! for an access and refresh token. [`OAuthTokenProvider`] then keeps the
+//! access token fresh for [`ApiClient`](crate::api::ApiClient), refreshing it
+//! shortly before expiry and whenever Asana rejects it.
+
+use crate::api::{
+    auth::{AccessTokenProvider, AuthToken},
+    error::ApiError,
+    network::NetworkOptions,
+};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use futures_util::future::BoxFuture;
+use reqwest::Url;
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+    sync::Mutex,
+};
+use tracing::{debug, warn};
+
+/// Path of the consent page relative to the Asana web origin.
+const AUTHORIZE_PATH: &str = "/-/oauth_authorize";
+/// Path of the token endpoint relative to the Asana web origin.
+const TOKEN_PATH: &str = "/-/oauth_token";
+/// API path suffix stripped from the base URL to find the web origin.
+const API_PATH_SUFFIX: &str = "/api/1.0";
+/// Access tokens are refreshed when they expire within this window.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Access and refresh tokens issued by Asana.
+#[derive(Clone)]
+pub struct OAuthTokens {
+    /// Short-lived bearer token.
+    pub access_token: SecretString,
+    /// Long-lived token used to obtain new access tokens.
+    pub refresh_token: SecretString,
+    /// Expiry of the access token in seconds since the Unix epoch.
+    pub expires_at: u64,
+}
+
+impl OAuthTokens {
+    fn expires_soon(&self) -> bool {
+        let deadline = SystemTime::now() + REFRESH_MARGIN;
+        deadline
+            .duration_since(UNIX_EPOCH)
+            .is_ok_and(|now| now.as_secs() >= self.expires_at)
+    }
+}
+
+impl fmt::Debug for OAuthTokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthTokens")
+            .field("access_token", &"REDACTED")
+            .field("refresh_token", &"REDACTED")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// PKCE verifier and its S256 challenge.
+pub struct Pkce {
+    verifier: SecretString,
+    challenge: String,
+}
+
+impl Pkce {
+    /// Generate a fresh random verifier.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Other`] if the system random source is unavailable.
+    pub fn generate() -> Result<Self, ApiError> {
+        let verifier = random_token(32)?;
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        Ok(Self {
+            verifier: SecretString::new(verifier.into()),
+            challenge,
+        })
+    }
+
+    /// The `code_challenge` sent with the authorization request.
+    #[must_use]
+    pub fn challenge(&self) -> &str {
+        &self.challenge
+    }
+}
+
+/// Generate an unguessable URL-safe token, used for PKCE verifiers and the
+/// `state` parameter.
+///
+/// # Errors
+/// Returns [`ApiError::Other`] if the system random source is unavailable.
+pub fn random_token(bytes: usize) -> Result<String, ApiError> {
+    let mut buffer = vec![0u8; bytes];
+    getrandom::fill(&mut buffer)
+        .map_err(|err| ApiError::Other(format!("failed to gather randomness: {err}")))?;
+    Ok(URL_SAFE_NO_PAD.encode(buffer))
+}
+
+/// Registered OAuth application and the Asana endpoints it talks to.
+#[derive(Clone)]
+pub struct OAuthApp {
+    /// Application client identifier.
+    pub client_id: String,
+    /// Client secret, when the application was issued one.
+    pub client_secret: Option<SecretString>,
+    /// Consent page URL.
+    pub authorize_url: String,
+    /// Token endpoint URL.
+    pub token_url: String,
+    http: reqwest::Client,
+}
+
+impl fmt::Debug for OAuthApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthApp")
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| "REDACTED"),
+            )
+            .field("authorize_url", &self.authorize_url)
+            .field("token_url", &self.token_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OAuthApp {
+    /// Describe an application whose endpoints live on the same origin as
+    /// `api_base_url` (for example `https://app.asana.com/api/1.0`).
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Network`] if the HTTP client cannot be created.
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: Option<SecretString>,
+        api_base_url: &str,
+    ) -> Result<Self, ApiError> {
+        let trimmed = api_base_url.trim_end_matches('/');
+        let origin = trimmed.strip_suffix(API_PATH_SUFFIX).unwrap_or(trimmed);
+        Ok(Self {
+            client_id: client_id.into(),
+            client_secret,
+            authorize_url: format!("{origin}{AUTHORIZE_PATH}"),
+            token_url: format!("{origin}{TOKEN_PATH}"),
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()?,
+        })
+    }
+
+    /// Send token requests with the CA bundle, client certificate, and proxy
+    /// used for API calls.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Other`] if the settings cannot be applied, or
+    /// [`ApiError::Network`] if the HTTP client cannot be created.
+    pub fn with_network(mut self, network: &NetworkOptions) -> Result<Self, ApiError> {
+        self.http = network
+            .apply(reqwest::Client::builder().timeout(Duration::from_secs(30)))?
+            .build()?;
+        Ok(self)
+    }
+
+    /// Consent page URL the user opens in a browser.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Other`] if the configured authorize URL is invalid.
+    pub fn authorization_url(
+        &self,
+        redirect_uri: &str,
+        state: &str,
+        pkce: &Pkce,
+    ) -> Result<String, ApiError> {
+        let url = Url::parse_with_params(
+            &self.authorize_url,
+            &[
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", redirect_uri),
+                ("response_type", "code"),
+                ("state", state),
+                ("code_challenge_method", "S256"),
+                ("code_challenge", pkce.challenge()),
+            ],
+        )
+        .map_err(|err| ApiError::Other(format!("invalid authorize URL: {err}")))?;
+        Ok(url.into())
+    }
+
+    /// Exchange an authorization code for tokens.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Authentication`] if Asana rejects the code, or a
+    /// network or parse error.
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        pkce: &Pkce,
+    ) -> Result<OAuthTokens, ApiError> {
+        self.request_tokens(
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("code_verifier", pkce.verifier.expose_secret()),
+            ],
+            None,
+        )
+        .await
+    }
+
+    /// Obtain a new access token using a refresh token.
+    ///
+    /// # Errors
+    /// Returns [`ApiError::Authentication`] if the refresh token was revoked,
+    /// or a network or parse error.
+    pub async fn refresh(&self, refresh_token: &SecretString) -> Result<OAuthTokens, ApiError> {
+        self.request_tokens(
+            &[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.expose_secret()),
+            ],
+            Some(refresh_token),
+        )
+        .await
+    }
+
+    async fn request_tokens(
+        &self,
+        grant: &[(&str, &str)],
+        previous_refresh: Option<&SecretString>,
+    ) -> Result<OAuthTokens, ApiError> {
+        let mut form: Vec<(&str, &str)> = vec![("client_id", &self.client_id)];
+        if let Some(secret) = &self.client_secret {
+            form.push(("client_secret", secret.expose_secret()));
+        }
+        form.extend_from_slice(grant);
+
+        let response = self.http.post(&self.token_url).form(&form).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(ApiError::Authentication(format!(
+                "token request failed ({status}): {body}"
+            )));
+        }
+
+        let parsed: TokenResponse = serde_json::from_str(&body)?;
+        let refresh_token = match (parsed.refresh_token, previous_refresh) {
+            (Some(token), _) => SecretString::new(token.into()),
+            (None, Some(previous)) => previous.clone(),
+            (None, None) => {
+                return Err(ApiError::Authentication(
+                    "token response did not include a refresh token".into(),
+                ));
+            }
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Ok(OAuthTokens {
+            access_token: SecretString::new(parsed.access_token.into()),
+            refresh_token,
+            expires_at: now.saturating_add(parsed.expires_in.unwrap_or(3600)),
+        })
+    }
+}
+
+/// Wait for the browser to be redirected to the loopback listener and return
+/// the authorization code.
+///
+/// Requests without a `code` or `error` parameter (such as favicon lookups)
+/// are answered with `404` and ignored.
+///
+/// # Errors
+/// Returns [`ApiError::Authentication`] if the user denied access or the
+/// `state` parameter does not match, and [`ApiError::Cache`] for socket errors.
+pub async fn receive_authorization_code(
+    listener: &TcpListener,
+    expected_state: &str,
+) -> Result<String, ApiError> {
+    loop {
+        let (mut socket, _) = listener.accept().await?;
+        let (reader, mut writer) = socket.split();
+        let mut request_line = String::new();
+        BufReader::new(reader).read_line(&mut request_line).await?;
+
+        let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+        let url = Url::parse(&format!("http://localhost{target}"))
+            .map_err(|err| ApiError::Other(format!("invalid redirect request: {err}")))?;
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        let outcome = match (param("code"), param("error")) {
+            (_, Some(error)) => Err(ApiError::Authentication(format!(
+                "authorization denied: {error}"
+            ))),
+            (Some(code), None) if param("state").as_deref() == Some(expected_state) => Ok(code),
+            (Some(_), None) => Err(ApiError::Authentication(
+                "authorization response had an unexpected state parameter".into(),
+            )),
+            (None, None) => {
+                writer
+                    .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                    .await?;
+                continue;
+            }
+        };
+
+        let message = if outcome.is_ok() {
+            "asana-cli is now authorized. You can close this window."
+        } else {
+            "asana-cli authorization failed. Return to the terminal for details."
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{message}",
+            message.len()
+        );
+        writer.write_all(response.as_bytes()).await?;
+        return outcome;
+    }
+}
+
+/// Callback invoked with newly refreshed tokens so they can be persisted.
+pub type RefreshHook = Box<dyn Fn(&OAuthTokens) + Send + Sync>;
+
+/// Token provider backed by OAuth access and refresh tokens.
+pub struct OAuthTokenProvider {
+    app: OAuthApp,
+    tokens: Mutex<OAuthTokens>,
+    on_refresh: Option<RefreshHook>,
+}
+
+impl OAuthTokenProvider {
+    /// Create a provider starting from previously issued tokens.
+    #[must_use]
+    pub fn new(app: OAuthApp, tokens: OAuthTokens) -> Self {
+        Self {
+            app,
+            tokens: Mutex::new(tokens),
+            on_refresh: None,
+        }
+    }
+
+    /// Persist tokens whenever they are refreshed.
+    #[must_use]
+    pub fn on_refresh(mut self, hook: impl Fn(&OAuthTokens) + Send + Sync + 'static) -> Self {
+        self.on_refresh = Some(Box::new(hook));
+        self
+    }
+
+    async fn refresh_locked(&self, tokens: &mut OAuthTokens) -> Result<(), ApiError> {
+        debug!("refreshing OAuth access token");
+        *tokens = self.app.refresh(&tokens.refresh_token).await?;
+        if let Some(hook) = &self.on_refresh {
+            hook(tokens);
+        }
+        Ok(())
+    }
+}
+
+impl AccessTokenProvider for OAuthTokenProvider {
+    fn access_token(&self) -> BoxFuture<'_, Result<AuthToken, ApiError>> {
+        Box::pin(async move {
+            let mut tokens = self.tokens.lock().await;
+            if tokens.expires_soon() {
+                if let Err(err) = self.refresh_locked(&mut tokens).await {
+                    // Let Asana decide whether the current token still works.
+                    warn!("failed to refresh OAuth access token: {err}");
+                }
+            }
+            Ok(AuthToken::new(tokens.access_token.clone()))
+        })
+    }
+
+    fn refresh<'a>(&'a self, rejected: &'a AuthToken) -> BoxFuture<'a, Result<bool, ApiError>> {
+        Box::pin(async move {
+            let mut tokens = self.tokens.lock().await;
+            if tokens.access_token.expose_secret() != rejected.expose() {
+                // Another request already refreshed the token.
+                return Ok(true);
+            }
+            self.refresh_locked(&mut tokens).await?;
+            Ok(true)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn tokens(access: &str, expires_at: u64) -> OAuthTokens {
+        OAuthTokens {
+            access_token: SecretString::new(access.into()),
+            refresh_token: SecretString::new("refresh-1".into()),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn authorization_url_carries_pkce_challenge() {
+        let app = OAuthApp::new("client", None, "https://app.asana.com/api/1.0").unwrap();
+        let pkce = Pkce::generate().unwrap();
+        let url = app
+            .authorization_url("http://localhost:8765/callback", "xyz", &pkce)
+            .unwrap();
+        assert!(url.starts_with("https://app.asana.com/-/oauth_authorize?client_id=client"));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains(&format!("code_challenge={}", pkce.challenge())));
+        assert_eq!(app.token_url, "https://app.asana.com/-/oauth_token");
+    }
+
+    #[tokio::test]
+    async fn provider_refreshes_expired_and_rejected_tokens() {
+        let mut server = Server::new_async().await;
+        let refresh = server
+            .mock("POST", "/-/oauth_token")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("grant_type".into(), "refresh_token".into()),
+                Matcher::UrlEncoded("refresh_token".into(), "refresh-1".into()),
+            ]))
+            .with_body(r#"{ "access_token": "access-2", "expires_in": 3600 }"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let app = OAuthApp::new("client", None, &format!("{}/api/1.0", server.url())).unwrap();
+        let stored = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = stored.clone();
+        let provider = OAuthTokenProvider::new(app, tokens("access-1", 0)).on_refresh(
+            move |tokens: &OAuthTokens| {
+                sink.lock()
+                    .unwrap()
+                    .push(tokens.access_token.expose_secret().to_string());
+            },
+        );
+
+        let token = provider.access_token().await.unwrap();
+        assert_eq!(token.expose(), "access-2");
+
+        // A stale rejection is answered without another refresh.
+        let stale = AuthToken::new(SecretString::new("access-1".into()));
+        assert!(provider.refresh(&stale).await.unwrap());
+        assert!(provider.refresh(&token).await.unwrap());
+
+        refresh.assert_async().await;
+        assert_eq!(*stored.lock().unwrap(), ["access-2", "access-2"]);
+    }
+
+    #[tokio::test]
+    async fn loopback_listener_returns_code_and_checks_state() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+
+        let client = tokio::spawn(async move {
+            let http = reqwest::Client::new();
+            let favicon = http
+                .get(format!("{base}/favicon.ico"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(favicon.status(), reqwest::StatusCode::NOT_FOUND);
+            http.get(format!("{base}/callback?code=abc&state=s1"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        });
+
+        let code = receive_authorization_code(&listener, "s1").await.unwrap();
+        assert_eq!(code, "abc");
+        assert!(client.await.unwrap().contains("authorized"));
+    }
+}
@@ -1,6 +1,56 @@
 //! Pagination helpers matching Asana's REST API structure.
 
 use serde::Deserialize;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Largest page size Asana accepts.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Paging state recorded by a listing.
+///
+/// Listings run through a client from
+/// [`ApiClient::with_listing_state`](crate::api::ApiClient::with_listing_state)
+/// record the offset their last page continues from, and how often they had
+/// to restart because Asana expired their offset token. Clones share the same state, so the handle kept by the caller sees what
+/// the listing recorded.
+#[derive(Debug, Clone, Default)]
+pub struct ListingState {
+    inner: Arc<Mutex<ListingProgress>>,
+}
+
+#[derive(Debug, Default)]
+struct ListingProgress {
+    next_offset: Option<String>,
+    restarts: usize,
+}
+
+impl ListingState {
+    /// Offset token continuing the most recently fetched page, or `None` when
+    /// the listing reached the end.
+    #[must_use]
+    pub fn next_offset(&self) -> Option<String> {
+        self.progress().next_offset.clone()
+    }
+
+    /// Number of times a listing restarted from the first page after its
+    /// offset token expired.
+    #[must_use]
+    pub fn restarts(&self) -> usize {
+        self.progress().restarts
+    }
+
+    pub(crate) fn note_next_offset(&self, offset: Option<String>) {
+        self.progress().next_offset = offset;
+    }
+
+    pub(crate) fn note_restart(&self) {
+        self.progress().restarts += 1;
+    }
+
+    fn progress(&self) -> std::sync::MutexGuard<'_, ListingProgress> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
 
 /// Metadata describing the next page of a list response.
 #[derive(Debug, Clone, Deserialize)]
@@ -0,0 +1,17 @@
+//! Progress reporting for file uploads and downloads.
+
+/// Observer notified as attachment bytes are transferred.
+///
+/// Implementations must be cheap to call; `advance` runs once per chunk.
+pub trait TransferProgress: Send + Sync {
+    /// A transfer attempt is starting. Called again when a retry restarts the
+    /// transfer, so implementations should reset their position to
+    /// `position`.
+    fn start(&self, position: u64, total: Option<u64>);
+
+    /// `bytes` more bytes were sent or received.
+    fn advance(&self, bytes: u64);
+
+    /// The transfer completed successfully.
+    fn finish(&self);
+}
@@ -7,7 +7,9 @@ use crate::{
         ProjectMembers, ProjectSort, ProjectStatus, ProjectUpdateRequest,
     },
 };
-use futures_util::{StreamExt, pin_mut};
+use async_stream::try_stream;
+use futures_core::Stream;
+use futures_util::{StreamExt, TryStreamExt, pin_mut};
 use serde::{Deserialize, Serialize};
 
 /// Retrieve projects according to the supplied parameters.
@@ -19,18 +21,12 @@ pub async fn list_projects(
     client: &ApiClient,
     mut params: ProjectListParams,
 ) -> Result<Vec<Project>, ApiError> {
-    ensure_default_fields(&mut params);
+    ensure_list_fields(&mut params);
 
-    let query = params.to_query();
-    let max_items = params.limit;
-    let stream = client.paginate_with_limit::<Project>("/projects", query, max_items);
-    pin_mut!(stream);
-
-    let mut projects = Vec::new();
-    while let Some(page) = stream.next().await {
-        let mut page = page?;
-        projects.append(&mut page);
-    }
+    let mut projects: Vec<Project> = client
+        .paginate_items("/projects", params.to_query(), params.limit)
+        .try_collect()
+        .await?;
 
     if !params.filters.is_empty() {
         projects.retain(|project| project.matches(&params.filters));
@@ -43,6 +39,34 @@ pub async fn list_projects(
     Ok(projects)
 }
 
+/// Stream projects as pages arrive, applying filters to each project.
+///
+/// Sorting needs the whole listing, so with `sort` set the projects are
+/// collected through [`list_projects`] first and then yielded in order.
+pub fn list_projects_stream(
+    client: &ApiClient,
+    mut params: ProjectListParams,
+) -> impl Stream<Item = Result<Project, ApiError>> + '_ {
+    try_stream! {
+        if params.sort.is_some() {
+            for project in list_projects(client, params).await? {
+                yield project;
+            }
+        } else {
+            ensure_list_fields(&mut params);
+            let projects =
+                client.paginate_items::<Project>("/projects", params.to_query(), params.limit);
+            pin_mut!(projects);
+            while let Some(project) = projects.next().await {
+                let project = project?;
+                if project.matches(&params.filters) {
+                    yield project;
+                }
+            }
+        }
+    }
+}
+
 /// Retrieve a single project by gid.
 ///
 /// # Errors
@@ -53,10 +77,12 @@ pub async fn get_project(
     gid: &str,
     fields: Vec<String>,
 ) -> Result<Project, ApiError> {
-    let mut query = Vec::new();
-    if !fields.is_empty() {
-        query.push(("opt_fields".into(), fields.join(",")));
-    }
+    let fields = if fields.is_empty() {
+        Project::DETAIL_FIELDS.join(",")
+    } else {
+        fields.join(",")
+    };
+    let query = vec![("opt_fields".into(), fields)];
 
     let response: SingleProjectResponse = client
         .get_json_with_pairs(&format!("/projects/{gid}"), query)
@@ -198,29 +224,32 @@ pub async fn update_member(
     Ok(response.data)
 }
 
-fn ensure_default_fields(params: &mut ProjectListParams) {
-    let defaults = [
-        "gid",
-        "name",
-        "archived",
-        "color",
-        "created_at",
-        "modified_at",
-        "workspace.name",
-        "workspace.gid",
-        "team.name",
-        "team.gid",
-        "owner.name",
-        "owner.gid",
-        "due_on",
-        "start_on",
-    ];
-    for field in defaults {
-        params.fields.insert(field.to_string());
+/// Add [`Project::LIST_FIELDS`] (or just `name` for an exact request) and
+/// whatever the local filters and sort order read, so they never see fields
+/// the caller did not request.
+fn ensure_list_fields(params: &mut ProjectListParams) {
+    if params.exact_fields && !params.fields.is_empty() {
+        params.fields.insert("name".to_string());
+    } else {
+        params
+            .fields
+            .extend(Project::LIST_FIELDS.iter().map(ToString::to_string));
+    }
+    for filter in &params.filters {
+        params.fields.extend(filter.opt_fields());
+    }
+    match params.sort {
+        None | Some(ProjectSort::Name) => {}
+        Some(ProjectSort::CreatedAt) => {
+            params.fields.insert("created_at".to_string());
+        }
+        Some(ProjectSort::ModifiedAt) => {
+            params.fields.insert("modified_at".to_string());
+        }
     }
 }
 
-fn sort_projects(projects: &mut [Project], sort: ProjectSort) {
+pub(crate) fn sort_projects(projects: &mut [Project], sort: ProjectSort) {
     match sort {
         ProjectSort::Name => projects.sort_by(|a, b| {
             a.name
@@ -0,0 +1,383 @@
+//! Proactive client-side rate limiting shared across processes.
+//!
+//! Each limiter tracks the budget advertised by Asana's rate-limit headers in
+//! memory. Once it nears exhaustion the budget is persisted under the cache
+//! directory, so concurrent `asana-cli` invocations draw from the same pool
+//! while it matters without locking a shared file on every request. Requests
+//! are paced as the remaining budget approaches zero instead of waiting for
+//! the API to answer with `429 Too Many Requests`.
+
+use crate::api::{error::RateLimitInfo, file_lock::FileLock};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{fs, time::sleep};
+use tracing::{debug, warn};
+
+/// File name of the persisted budget inside the cache directory.
+pub const RATE_LIMIT_STATE_FILE: &str = "rate-limit.json";
+
+/// Remaining-request floor used when the API does not report a limit.
+const LOW_WATERMARK: u32 = 10;
+/// Upper bound on any single pacing delay, guarding against bogus headers.
+const MAX_PACING_DELAY: Duration = Duration::from_secs(60);
+/// How long to wait for another process to release the state lock.
+const LOCK_TIMEOUT: Duration = Duration::from_millis(500);
+/// Reset values below this are treated as relative seconds, not epoch seconds.
+const EPOCH_THRESHOLD_SECS: u64 = 1_000_000_000;
+
+/// Snapshot of the shared request budget.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BudgetState {
+    limit: Option<u32>,
+    remaining: Option<u32>,
+    reset_at_ms: Option<u64>,
+}
+
+impl BudgetState {
+    /// Delay to apply before the next request given the current budget.
+    ///
+    /// Once `remaining` drops to a tenth of the limit, the leftover requests
+    /// are spread evenly over the time until the window resets.
+    fn pacing_delay(&self, now_ms: u64) -> Duration {
+        let (Some(reset_at), Some(remaining)) = (self.reset_at_ms, self.remaining) else {
+            return Duration::ZERO;
+        };
+        if reset_at <= now_ms {
+            return Duration::ZERO;
+        }
+
+        let window = reset_at - now_ms;
+        let delay = match remaining {
+            0 => window,
+            n if n <= self.threshold() => window / u64::from(n),
+            _ => 0,
+        };
+        Duration::from_millis(delay).min(MAX_PACING_DELAY)
+    }
+
+    /// Whether the budget is low enough that other processes need to see it.
+    fn near_exhaustion(&self, now_ms: u64) -> bool {
+        self.reset_at_ms.is_some_and(|reset| reset > now_ms)
+            && self
+                .remaining
+                .is_some_and(|remaining| remaining <= self.threshold())
+    }
+
+    fn threshold(&self) -> u32 {
+        self.limit
+            .map_or(LOW_WATERMARK, |limit| (limit / 10).max(1))
+    }
+
+    fn consume(&mut self, now_ms: u64) {
+        if self.reset_at_ms.is_some_and(|reset| reset <= now_ms) {
+            *self = Self::default();
+            return;
+        }
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    fn observe(&mut self, info: &RateLimitInfo, now_ms: u64) {
+        if info.limit.is_some() {
+            self.limit = info.limit;
+        }
+        if info.remaining.is_some() {
+            self.remaining = info.remaining;
+        }
+        if let Some(reset) = info.reset {
+            let reset_ms = reset.saturating_mul(1000);
+            self.reset_at_ms = Some(if reset < EPOCH_THRESHOLD_SECS {
+                now_ms.saturating_add(reset_ms)
+            } else {
+                reset_ms
+            });
+        }
+        if let Some(retry_after) = info.retry_after {
+            let retry_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+            self.remaining = Some(0);
+            self.reset_at_ms = Some(now_ms.saturating_add(retry_ms));
+        }
+    }
+}
+
+/// Token bucket shared through the cache directory by every client using it
+/// once the budget runs low.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    state_path: PathBuf,
+    lock_path: PathBuf,
+    local: Arc<Mutex<LocalBudget>>,
+}
+
+/// Budget as this process last saw it.
+#[derive(Debug, Default)]
+struct LocalBudget {
+    state: BudgetState,
+    /// Modification time of the shared file when it was last read.
+    seen: Option<SystemTime>,
+}
+
+impl RateLimiter {
+    /// Create a limiter storing its state inside `cache_dir`, creating the
+    /// directory if needed.
+    #[must_use]
+    pub fn new(cache_dir: &Path) -> Self {
+        if let Err(err) = std::fs::create_dir_all(cache_dir) {
+            warn!(
+                "cannot create {}: {err}; rate limit budget will not be shared",
+                cache_dir.display()
+            );
+        }
+        Self {
+            state_path: cache_dir.join(RATE_LIMIT_STATE_FILE),
+            lock_path: cache_dir.join(format!("{RATE_LIMIT_STATE_FILE}.lock")),
+            local: Arc::default(),
+        }
+    }
+
+    /// Wait until the budget allows another request, then claim it.
+    ///
+    /// While the budget is healthy only this process's copy is updated; the
+    /// shared state is locked and rewritten once it nears exhaustion.
+    /// Failures to read or write the shared state are logged and ignored so
+    /// rate limiting never blocks a request outright.
+    pub async fn acquire(&self) {
+        let claim = |state: &mut BudgetState, now_ms: u64| {
+            let delay = state.pacing_delay(now_ms);
+            state.consume(now_ms.saturating_add(millis(delay)));
+            delay
+        };
+
+        self.refresh().await;
+        let delay = match self.apply_locally(claim) {
+            Some(delay) => delay,
+            None => self.update(claim).await.unwrap_or_default(),
+        };
+
+        if !delay.is_zero() {
+            debug!("rate limit budget low; pacing request by {delay:?}");
+            sleep(delay).await;
+        }
+    }
+
+    /// Record rate-limit headers observed on a response.
+    pub async fn observe(&self, info: &RateLimitInfo) {
+        let observe = |state: &mut BudgetState, now_ms: u64| state.observe(info, now_ms);
+        if self.apply_locally(observe).is_none() {
+            let _ = self.update(observe).await;
+        }
+    }
+
+    /// Apply `change` to this process's budget unless the budget is, or would
+    /// become, low enough to share. Returns `None` to defer to [`Self::update`].
+    fn apply_locally<R>(&self, change: impl Fn(&mut BudgetState, u64) -> R) -> Option<R> {
+        let now_ms = now_millis();
+        let mut local = self.local();
+        if local.state.near_exhaustion(now_ms) {
+            return None;
+        }
+        let mut state = local.state.clone();
+        let result = change(&mut state, now_ms);
+        if state.near_exhaustion(now_ms) {
+            return None;
+        }
+        local.state = state;
+        Some(result)
+    }
+
+    /// Adopt the shared budget if another process changed it since the last
+    /// look. The file is replaced atomically, so it is read without the lock.
+    async fn refresh(&self) {
+        let Some(modified) = fs::metadata(&self.state_path)
+            .await
+            .ok()
+            .and_then(|meta| meta.modified().ok())
+        else {
+            return;
+        };
+        if self.local().seen == Some(modified) {
+            return;
+        }
+        let shared = fs::read(&self.state_path)
+            .await
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<BudgetState>(&bytes).ok());
+        let mut local = self.local();
+        local.seen = Some(modified);
+        if let Some(shared) = shared {
+            local.state = shared;
+        }
+    }
+
+    fn local(&self) -> MutexGuard<'_, LocalBudget> {
+        self.local.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn update<R>(&self, apply: impl FnOnce(&mut BudgetState, u64) -> R) -> Option<R> {
+        let Ok(_lock) = FileLock::acquire(&self.lock_path, LOCK_TIMEOUT).await else {
+            debug!("rate limit state lock unavailable; skipping shared budget");
+            return None;
+        };
+
+        let mut state: BudgetState = fs::read(&self.state_path)
+            .await
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        let result = apply(&mut state, now_millis());
+
+        if let Ok(serialized) = serde_json::to_vec(&state) {
+            let tmp = self.state_path.with_extension("json.tmp");
+            if fs::write(&tmp, serialized).await.is_ok() {
+                fs::rename(&tmp, &self.state_path).await.ok();
+            }
+        }
+        let modified = fs::metadata(&self.state_path)
+            .await
+            .ok()
+            .and_then(|meta| meta.modified().ok());
+        let mut local = self.local();
+        local.state = state;
+        local.seen = modified;
+        Some(result)
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, millis)
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn no_delay_while_budget_is_healthy() {
+        let state = BudgetState {
+            limit: Some(150),
+            remaining: Some(100),
+            reset_at_ms: Some(60_000),
+        };
+        assert_eq!(state.pacing_delay(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn spreads_remaining_requests_over_window() {
+        let state = BudgetState {
+            limit: Some(150),
+            remaining: Some(10),
+            reset_at_ms: Some(20_000),
+        };
+        assert_eq!(state.pacing_delay(0), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn exhausted_budget_waits_for_reset() {
+        let state = BudgetState {
+            limit: Some(150),
+            remaining: Some(0),
+            reset_at_ms: Some(3_000),
+        };
+        assert_eq!(state.pacing_delay(1_000), Duration::from_secs(2));
+        assert_eq!(state.pacing_delay(5_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn retry_after_exhausts_budget() {
+        let mut state = BudgetState::default();
+        state.observe(
+            &RateLimitInfo {
+                limit: None,
+                remaining: None,
+                reset: None,
+                retry_after: Some(Duration::from_secs(3)),
+            },
+            1_000,
+        );
+        assert_eq!(state.remaining, Some(0));
+        assert_eq!(state.reset_at_ms, Some(4_000));
+    }
+
+    #[tokio::test]
+    async fn limiters_share_state_through_cache_dir() {
+        let tmp = TempDir::new().unwrap();
+        let first = RateLimiter::new(tmp.path());
+        let second = RateLimiter::new(tmp.path());
+
+        first
+            .observe(&RateLimitInfo {
+                limit: Some(150),
+                remaining: Some(5),
+                reset: Some(30),
+                retry_after: None,
+            })
+            .await;
+
+        let claimed = second
+            .update(|state, _| {
+                state.consume(0);
+                state.remaining
+            })
+            .await;
+        assert_eq!(claimed, Some(Some(4)));
+        assert!(
+            !tmp.path()
+                .join(format!("{RATE_LIMIT_STATE_FILE}.lock"))
+                .exists()
+        );
+    }
+
+    #[tokio::test]
+    async fn healthy_budget_stays_in_memory() {
+        let tmp = TempDir::new().unwrap();
+        let cache_dir = tmp.path().join("cache");
+        let limiter = RateLimiter::new(&cache_dir);
+        assert!(cache_dir.is_dir());
+
+        limiter
+            .observe(&RateLimitInfo {
+                limit: Some(150),
+                remaining: Some(100),
+                reset: Some(30),
+                retry_after: None,
+            })
+            .await;
+        limiter.acquire().await;
+
+        assert_eq!(limiter.local().state.remaining, Some(99));
+        assert!(!cache_dir.join(RATE_LIMIT_STATE_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn low_budget_is_shared_with_other_limiters() {
+        let tmp = TempDir::new().unwrap();
+        let first = RateLimiter::new(tmp.path());
+        let second = RateLimiter::new(tmp.path());
+
+        first
+            .observe(&RateLimitInfo {
+                limit: Some(150),
+                remaining: Some(5),
+                reset: Some(30),
+                retry_after: None,
+            })
+            .await;
+        assert!(tmp.path().join(RATE_LIMIT_STATE_FILE).exists());
+
+        second.refresh().await;
+        assert_eq!(second.local().state.remaining, Some(5));
+        assert!(second.apply_locally(BudgetState::consume).is_none());
+    }
+}
@@ -0,0 +1,35 @@
+//! Untyped access to endpoints that have no dedicated helper.
+
+use crate::api::{ApiClient, ApiError, pagination::MAX_PAGE_SIZE};
+use futures_util::{StreamExt, pin_mut};
+use serde_json::{Value, json};
+
+/// Fetch every page of a list endpoint and merge them into a single
+/// `{"data": [...]}` document.
+///
+/// A `limit` in `query` sets the page size unless the client has one
+/// configured; without either, pages of [`MAX_PAGE_SIZE`] are requested
+/// because Asana only paginates when a limit is given.
+///
+/// # Errors
+/// Returns an error if any page request fails.
+pub async fn get_all_pages(
+    client: &ApiClient,
+    path: &str,
+    mut query: Vec<(String, String)>,
+) -> Result<Value, ApiError> {
+    let has_limit = query.iter().any(|(key, _)| key == "limit");
+    if client.page_size().is_some() {
+        query.retain(|(key, _)| key != "limit");
+    } else if !has_limit {
+        query.push(("limit".into(), MAX_PAGE_SIZE.to_string()));
+    }
+
+    let stream = client.paginate::<Value>(path, query);
+    pin_mut!(stream);
+    let mut items = Vec::new();
+    while let Some(page) = stream.next().await {
+        items.extend(page?);
+    }
+    Ok(json!({ "data": items }))
+}
@@ -16,7 +16,7 @@ pub async fn list_stories(
     params: StoryListParams,
 ) -> Result<Vec<Story>, ApiError> {
     let endpoint = format!("/tasks/{}/stories", params.task_gid);
-    let stream = client.paginate_with_limit::<Story>(&endpoint, vec![], params.limit);
+    let stream = client.paginate_with_limit::<Story>(&endpoint, params.to_query(), params.limit);
     pin_mut!(stream);
 
     let mut stories = Vec::new();
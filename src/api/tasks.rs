@@ -1,14 +1,18 @@
 //! High level task operations built on the core API client.
 
 use crate::{
-    api::{ApiClient, ApiError},
+    api::{ApiClient, ApiError, BatchAction, DEFAULT_CONCURRENCY, map_bounded},
     models::{
         Task, TaskCreateRequest, TaskListParams, TaskReference, TaskSearchParams, TaskSort,
-        TaskUpdateRequest,
+        TaskUpdateData, TaskUpdateRequest,
     },
 };
-use futures_util::{StreamExt, pin_mut};
+use async_stream::try_stream;
+use futures_core::Stream;
+use futures_util::{StreamExt, TryStreamExt, pin_mut};
+use reqwest::{Method, StatusCode};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::BTreeSet;
 use tracing::debug;
 
@@ -21,18 +25,12 @@ pub async fn list_tasks(
     client: &ApiClient,
     mut params: TaskListParams,
 ) -> Result<Vec<Task>, ApiError> {
-    ensure_default_fields(&mut params);
+    ensure_list_fields(&mut params);
 
-    let query = params.to_query();
-    let max_items = params.limit;
-    let stream = client.paginate_with_limit::<Task>("/tasks", query, max_items);
-    pin_mut!(stream);
-
-    let mut tasks = Vec::new();
-    while let Some(page) = stream.next().await {
-        let mut page = page?;
-        tasks.append(&mut page);
-    }
+    let mut tasks: Vec<Task> = client
+        .paginate_items("/tasks", params.to_query(), params.limit)
+        .try_collect()
+        .await?;
 
     // Fetch subtasks if requested. This makes separate API calls for each task
     // to get subtasks with complete field data. The deprecated opt_expand=subtasks
@@ -40,18 +38,34 @@ pub async fn list_tasks(
     // populated by the API, so we attempt to fetch subtasks for all tasks.
     if params.include_subtasks {
         debug!("Fetching subtasks for {} parent tasks", tasks.len());
+        let fields: Vec<String> = params.fields.iter().cloned().collect();
+        // Owned gids keep the returned future `Send`, which borrowing the
+        // tasks inside the closure does not.
+        let gids: Vec<String> = tasks.iter().map(|task| task.gid.clone()).collect();
+        // Subtask listings must not overwrite where the task listing resumes.
+        let subtask_client = client.without_listing_state();
+        let fetched = map_bounded(gids, DEFAULT_CONCURRENCY, |gid| {
+            let fields = fields.clone();
+            let client = &subtask_client;
+            async move {
+                let result = list_subtasks(client, &gid, fields).await;
+                (gid, result)
+            }
+        })
+        .await;
+
         let mut all_subtasks = Vec::new();
-        for task in &tasks {
+        for (gid, result) in fetched {
             // Continue on error - tasks without subtasks may return empty results or errors
-            match list_subtasks(client, &task.gid, params.fields.iter().cloned().collect()).await {
+            match result {
                 Ok(subtasks) => {
                     if !subtasks.is_empty() {
-                        debug!("Found {} subtasks for task {}", subtasks.len(), task.gid);
+                        debug!("Found {} subtasks for task {}", subtasks.len(), gid);
                     }
                     all_subtasks.extend(subtasks);
                 }
                 Err(e) => {
-                    debug!("Failed to fetch subtasks for task {}: {}", task.gid, e);
+                    debug!("Failed to fetch subtasks for task {}: {}", gid, e);
                     // Task has no subtasks or fetch failed, skip it
                 }
             }
@@ -69,16 +83,65 @@ pub async fn list_tasks(
     Ok(tasks)
 }
 
+/// Stream tasks as pages arrive, applying the client-side filters to each
+/// task.
+///
+/// Sorting and `include_subtasks` need the whole listing, so with either set
+/// the tasks are collected through [`list_tasks`] first and then yielded.
+pub fn list_tasks_stream(
+    client: &ApiClient,
+    mut params: TaskListParams,
+) -> impl Stream<Item = Result<Task, ApiError>> + '_ {
+    try_stream! {
+        if params.sort.is_some() || params.include_subtasks {
+            for task in list_tasks(client, params).await? {
+                yield task;
+            }
+        } else {
+            ensure_list_fields(&mut params);
+            let tasks = client.paginate_items::<Task>("/tasks", params.to_query(), params.limit);
+            pin_mut!(tasks);
+            while let Some(task) = tasks.next().await {
+                let task = task?;
+                if params.matches_post_filters(&task) {
+                    yield task;
+                }
+            }
+        }
+    }
+}
+
 /// Search tasks in a workspace.
 ///
 /// # Errors
 ///
 /// Returns an error if the API request fails, if deserialization fails, or if the response is invalid.
 pub async fn search_tasks(
+    client: &ApiClient,
+    params: TaskSearchParams,
+) -> Result<Vec<Task>, ApiError> {
+    run_search(client, params, false).await
+}
+
+/// Search tasks in a workspace, requesting only `params.fields` (and `name`)
+/// instead of adding them to [`Task::LIST_FIELDS`].
+///
+/// # Errors
+///
+/// Returns an error if the API request fails, if deserialization fails, or if the response is invalid.
+pub async fn search_tasks_with_exact_fields(
+    client: &ApiClient,
+    params: TaskSearchParams,
+) -> Result<Vec<Task>, ApiError> {
+    run_search(client, params, true).await
+}
+
+async fn run_search(
     client: &ApiClient,
     mut params: TaskSearchParams,
+    exact: bool,
 ) -> Result<Vec<Task>, ApiError> {
-    ensure_default_fields_for_search(&mut params);
+    params.fields = request_fields(std::mem::take(&mut params.fields), Task::LIST_FIELDS, exact);
 
     let query = params.to_query();
     let max_items = params.limit;
@@ -95,55 +158,42 @@ pub async fn search_tasks(
     Ok(tasks)
 }
 
-fn ensure_default_fields_for_search(params: &mut TaskSearchParams) {
-    // Add same defaults as list_tasks
-    let defaults = [
-        "gid",
-        "name",
-        "completed",
-        "completed_at",
-        "due_on",
-        "due_at",
-        "start_on",
-        "start_at",
-        "assignee.name",
-        "assignee.gid",
-        "assignee.email",
-        "resource_type",
-        "resource_subtype",
-        "modified_at",
-        "workspace.name",
-        "workspace.gid",
-        "projects.name",
-        "projects.gid",
-        "tags.name",
-        "tags.gid",
-        "memberships.project.name",
-        "memberships.project.gid",
-        "memberships.section.name",
-        "memberships.section.gid",
-        "permalink_url",
-        "num_subtasks",
-    ];
-    for field in defaults {
-        params.fields.insert(field.to_string());
-    }
+/// Retrieve a single task by gid, requesting `fields` on top of
+/// [`Task::DETAIL_FIELDS`].
+///
+/// # Errors
+///
+/// Returns an error if the API request fails, if deserialization fails, or if the response is invalid.
+pub async fn get_task(
+    client: &ApiClient,
+    gid: &str,
+    fields: Vec<String>,
+) -> Result<Task, ApiError> {
+    fetch_task(client, gid, fields, false).await
 }
 
-/// Retrieve a single task by gid.
+/// Retrieve a single task by gid, requesting only `fields` (and `name`).
+///
+/// An empty `fields` falls back to [`Task::DETAIL_FIELDS`].
 ///
 /// # Errors
 ///
 /// Returns an error if the API request fails, if deserialization fails, or if the response is invalid.
-pub async fn get_task(
+pub async fn get_task_with_exact_fields(
     client: &ApiClient,
     gid: &str,
     fields: Vec<String>,
 ) -> Result<Task, ApiError> {
-    let mut field_set: BTreeSet<String> = fields.into_iter().collect();
-    for field in detail_defaults() {
-        field_set.insert((*field).to_string());
-    }
+    fetch_task(client, gid, fields, true).await
+}
+
+async fn fetch_task(
+    client: &ApiClient,
+    gid: &str,
+    fields: Vec<String>,
+    exact: bool,
+) -> Result<Task, ApiError> {
+    let field_set = request_fields(fields.into_iter().collect(), Task::DETAIL_FIELDS, exact);
 
     let mut query = Vec::new();
     if !field_set.is_empty() {
@@ -153,12 +203,20 @@ pub async fn get_task(
         ));
     }
 
-    let response: SingleTaskResponse = client
-        .get_json_with_pairs(&format!("/tasks/{gid}"), query)
-        .await?;
+    let response: SingleTaskResponse = client.get_json_with_pairs(&task_path(gid), query).await?;
     Ok(response.data)
 }
 
+/// Path of the task named by `gid`, which may be `external:<id>` with an id
+/// holding any characters; it is percent-encoded as one path segment.
+fn task_path(gid: &str) -> String {
+    let mut url = reqwest::Url::parse("http://localhost/tasks").expect("static URL parses");
+    url.path_segments_mut()
+        .expect("http URLs have path segments")
+        .push(gid);
+    url.path().to_string()
+}
+
 /// Create a task using the provided payload.
 ///
 /// # Errors
@@ -169,6 +227,75 @@ pub async fn create_task(client: &ApiClient, request: TaskCreateRequest) -> Resu
     Ok(response.data)
 }
 
+/// Outcome of [`create_or_update_task`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskUpsert {
+    /// No task carried the external id, so one was created.
+    Created,
+    /// The task already carrying the external id was updated.
+    Updated,
+}
+
+/// Create a task, or update the existing one when the payload's external id
+/// is already in use.
+///
+/// `POST /tasks` is retried on timeouts, so an attempt the server accepted can
+/// be repeated; Asana rejects the duplicate external id, and the task created
+/// by the first attempt is returned instead. Without an external id this is
+/// [`create_task`].
+///
+/// # Errors
+///
+/// Returns an error if the lookup, create, or update request fails.
+pub async fn create_or_update_task(
+    client: &ApiClient,
+    request: TaskCreateRequest,
+) -> Result<(Task, TaskUpsert), ApiError> {
+    let Some(external) = request.data.external.clone() else {
+        return Ok((create_task(client, request).await?, TaskUpsert::Created));
+    };
+
+    if let Some(existing) = find_task_by_external_id(client, &external.gid).await? {
+        let update = TaskUpdateRequest {
+            data: request.data.into(),
+        };
+        let task = update_task(client, &existing.gid, update).await?;
+        return Ok((task, TaskUpsert::Updated));
+    }
+
+    match create_task(client, request).await {
+        Ok(task) => Ok((task, TaskUpsert::Created)),
+        Err(err) => match find_task_by_external_id(client, &external.gid).await {
+            Ok(Some(task)) => Ok((task, TaskUpsert::Created)),
+            _ => Err(err),
+        },
+    }
+}
+
+/// Look a task up by the external id it was created with.
+///
+/// # Errors
+///
+/// Returns an error if the request fails for a reason other than the id being
+/// unknown.
+pub async fn find_task_by_external_id(
+    client: &ApiClient,
+    external_id: &str,
+) -> Result<Option<Task>, ApiError> {
+    let query = vec![("opt_fields".into(), "name,external".into())];
+    match client
+        .get_json_with_pairs::<SingleTaskResponse>(
+            &task_path(&format!("external:{external_id}")),
+            query,
+        )
+        .await
+    {
+        Ok(response) => Ok(Some(response.data)),
+        Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 /// Update a task using the given payload.
 ///
 /// # Errors
@@ -183,6 +310,97 @@ pub async fn update_task(
     Ok(response.data)
 }
 
+/// Create several tasks through the batch API.
+///
+/// Requests whose external id already belongs to a task update that task
+/// instead, as [`create_or_update_task`] does. A failed create carrying an
+/// external id is looked up again, since a retried batch the server already
+/// accepted fails as a duplicate; the task it created is returned. Returns one
+/// result per request, in input order.
+///
+/// # Errors
+///
+/// Returns an error if a payload cannot be serialized, an external id lookup
+/// fails, or a batch request as a whole fails.
+pub async fn create_tasks_batch(
+    client: &ApiClient,
+    requests: Vec<TaskCreateRequest>,
+) -> Result<Vec<Result<Task, ApiError>>, ApiError> {
+    let external_ids: Vec<Option<String>> = requests
+        .iter()
+        .map(|request| request.data.external.as_ref().map(|ext| ext.gid.clone()))
+        .collect();
+    let existing = map_bounded(external_ids.clone(), DEFAULT_CONCURRENCY, |id| async move {
+        match id {
+            Some(id) => find_task_by_external_id(client, &id).await,
+            None => Ok(None),
+        }
+    })
+    .await;
+
+    let mut created_ids = Vec::with_capacity(requests.len());
+    let actions = requests
+        .into_iter()
+        .zip(existing)
+        .zip(external_ids)
+        .map(|((request, existing), external_id)| {
+            let action = if let Some(task) = existing? {
+                created_ids.push(None);
+                BatchAction::new(&Method::PUT, format!("/tasks/{}", task.gid))
+                    .with_data(serde_json::to_value(TaskUpdateData::from(request.data))?)
+            } else {
+                created_ids.push(external_id);
+                BatchAction::new(&Method::POST, "/tasks")
+                    .with_data(serde_json::to_value(request.data)?)
+            };
+            Ok(action)
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+    let results = client.batch(&actions).await?;
+    let outcomes = results.into_iter().zip(created_ids);
+    Ok(map_bounded(
+        outcomes,
+        DEFAULT_CONCURRENCY,
+        |(result, external_id)| async move {
+            match (parse_batch_task(result), external_id) {
+                (Err(err), Some(id)) => match find_task_by_external_id(client, &id).await {
+                    Ok(Some(task)) => Ok(task),
+                    _ => Err(err),
+                },
+                (outcome, _) => outcome,
+            }
+        },
+    )
+    .await)
+}
+
+/// Update several tasks through the batch API.
+///
+/// Returns one result per `(gid, request)` pair, in input order.
+///
+/// # Errors
+///
+/// Returns an error if a payload cannot be serialized or a batch request as a whole fails.
+pub async fn update_tasks_batch(
+    client: &ApiClient,
+    updates: Vec<(String, TaskUpdateRequest)>,
+) -> Result<Vec<Result<Task, ApiError>>, ApiError> {
+    let actions = updates
+        .into_iter()
+        .map(|(gid, request)| {
+            Ok(BatchAction::new(&Method::PUT, format!("/tasks/{gid}"))
+                .with_data(serde_json::to_value(request.data)?))
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+    let results = client.batch(&actions).await?;
+    Ok(results.into_iter().map(parse_batch_task).collect())
+}
+
+fn parse_batch_task(result: Result<Value, ApiError>) -> Result<Task, ApiError> {
+    let response: SingleTaskResponse = serde_json::from_value(result?)?;
+    Ok(response.data)
+}
+
 /// Delete a task permanently.
 ///
 /// # Errors
@@ -452,79 +670,47 @@ pub async fn remove_tag(client: &ApiClient, gid: &str, tag: String) -> Result<()
         .await
 }
 
-fn ensure_default_fields(params: &mut TaskListParams) {
-    let defaults = [
-        "gid",
-        "name",
-        "completed",
-        "completed_at",
-        "due_on",
-        "due_at",
-        "start_on",
-        "start_at",
-        "assignee.name",
-        "assignee.gid",
-        "assignee.email",
-        "resource_type",
-        "resource_subtype",
-        "modified_at",
-        "workspace.name",
-        "workspace.gid",
-        "projects.name",
-        "projects.gid",
-        "tags.name",
-        "tags.gid",
-        "memberships.project.name",
-        "memberships.project.gid",
-        "memberships.section.name",
-        "memberships.section.gid",
-        "permalink_url",
-        "num_subtasks",
-    ];
-    for field in defaults {
-        params.fields.insert(field.to_string());
+/// Add [`Task::LIST_FIELDS`] (unless the request is exact) and whatever the
+/// local filters and sort order read, so they never see fields the caller did
+/// not request.
+fn ensure_list_fields(params: &mut TaskListParams) {
+    params.fields = request_fields(
+        std::mem::take(&mut params.fields),
+        Task::LIST_FIELDS,
+        params.exact_fields,
+    );
+    if params.completed.is_some() {
+        params.fields.insert("completed".to_string());
     }
-}
-
-const fn detail_defaults() -> &'static [&'static str] {
-    &[
-        "gid",
-        "name",
-        "completed",
-        "completed_at",
-        "due_on",
-        "due_at",
-        "start_on",
-        "start_at",
-        "notes",
-        "html_notes",
-        "assignee",
-        "assignee_status",
-        "assignee.name",
-        "assignee.email",
-        "completed_by",
-        "created_at",
-        "modified_at",
-        "workspace",
-        "workspace.name",
-        "workspace.gid",
-        "parent",
-        "projects",
-        "projects.name",
-        "projects.gid",
-        "memberships",
-        "tags",
-        "followers",
-        "followers.name",
-        "followers.email",
-        "dependencies",
-        "dependents",
-        "custom_fields",
-        "attachments",
-        "permalink_url",
-        "resource_subtype",
-        "num_subtasks",
-    ]
+    if params.due_before.is_some() || params.due_after.is_some() {
+        params.fields.insert("due_on".to_string());
+    }
+    let sort_fields: &[&str] = match params.sort {
+        None | Some(TaskSort::Name) => &[],
+        Some(TaskSort::DueOn) => &["due_on", "due_at"],
+        Some(TaskSort::CreatedAt) => &["created_at"],
+        Some(TaskSort::ModifiedAt) => &["modified_at"],
+        Some(TaskSort::Assignee) => &["assignee.name", "assignee.email"],
+    };
+    params
+        .fields
+        .extend(sort_fields.iter().map(ToString::to_string));
+}
+
+/// Add `fields` to `defaults`, or for an `exact` request keep them as they are
+/// apart from `name`, which every task payload must carry. An exact request
+/// with no fields still gets the defaults.
+fn request_fields(
+    mut fields: BTreeSet<String>,
+    defaults: &[&str],
+    exact: bool,
+) -> BTreeSet<String> {
+    if exact && !fields.is_empty() {
+        fields.insert("name".to_string());
+    } else {
+        fields.extend(defaults.iter().map(ToString::to_string));
+    }
+    fields
 }
 
 fn ensure_subtask_fields(fields: &mut BTreeSet<String>) {
@@ -542,7 +728,7 @@ fn ensure_subtask_fields(fields: &mut BTreeSet<String>) {
     }
 }
 
-fn sort_tasks(tasks: &mut [Task], sort: TaskSort) {
+pub(crate) fn sort_tasks(tasks: &mut [Task], sort: TaskSort) {
     match sort {
         TaskSort::Name => tasks.sort_by(|a, b| {
             a.name
@@ -4,7 +4,8 @@ use crate::{
     api::{ApiClient, ApiError},
     models::{User, UserListParams},
 };
-use futures_util::{StreamExt, pin_mut};
+use futures_core::Stream;
+use futures_util::TryStreamExt;
 use serde::Deserialize;
 
 /// List users in a workspace.
@@ -12,17 +13,21 @@ use serde::Deserialize;
 /// # Errors
 /// Returns [`ApiError`] if the API request fails or network errors occur.
 pub async fn list_users(client: &ApiClient, params: UserListParams) -> Result<Vec<User>, ApiError> {
-    let endpoint = format!("/workspaces/{}/users", params.workspace_gid);
-    let stream = client.paginate_with_limit::<User>(&endpoint, vec![], params.limit);
-    pin_mut!(stream);
-
-    let mut users = Vec::new();
-    while let Some(page) = stream.next().await {
-        let mut page = page?;
-        users.append(&mut page);
-    }
+    list_users_stream(client, params).try_collect().await
+}
 
-    Ok(users)
+/// Stream users in a workspace as pages arrive.
+pub fn list_users_stream(
+    client: &ApiClient,
+    params: UserListParams,
+) -> impl Stream<Item = Result<User, ApiError>> + '_ {
+    let query = params.to_query();
+    let UserListParams {
+        workspace_gid,
+        limit,
+        ..
+    } = params;
+    client.paginate_items::<User>(format!("/workspaces/{workspace_gid}/users"), query, limit)
 }
 
 /// Get a single user by GID.
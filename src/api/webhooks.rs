@@ -0,0 +1,522 @@
+//! Webhook subscriptions and a receiver for their deliveries.
+//!
+//! When a webhook is created Asana POSTs an `X-Hook-Secret` header to the
+//! target before `POST /webhooks` returns. The receiver echoes the secret to
+//! complete the handshake and stores it under the request path, so every
+//! webhook pointed at its own path keeps its own secret. Handshakes are only
+//! accepted for paths with a `webhook create` in flight; anything else could
+//! re-key the path and forge deliveries. Later deliveries carry
+//! `X-Hook-Signature`, the hex HMAC-SHA256 of the body keyed with that
+//! secret, and are rejected unless it matches.
+
+use crate::{
+    api::{ApiClient, ApiError, file_lock::FileLock},
+    models::{Event, Webhook, WebhookCreateRequest, WebhookListParams},
+};
+use futures_util::{StreamExt, pin_mut};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{
+    fs,
+    io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+};
+
+/// Largest delivery body the receiver accepts.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Seconds a path accepts a handshake after `webhook create` starts.
+const HANDSHAKE_WINDOW_SECS: i64 = 300;
+/// How long to wait for another process to finish updating the secrets.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// List webhooks in a workspace.
+///
+/// # Errors
+/// Returns [`ApiError`] if the API request fails or network errors occur.
+pub async fn list_webhooks(
+    client: &ApiClient,
+    params: WebhookListParams,
+) -> Result<Vec<Webhook>, ApiError> {
+    let stream =
+        client.paginate_with_limit::<Webhook>("/webhooks", params.to_query(), params.limit);
+    pin_mut!(stream);
+
+    let mut webhooks = Vec::new();
+    while let Some(page) = stream.next().await {
+        let mut page = page?;
+        webhooks.append(&mut page);
+    }
+    Ok(webhooks)
+}
+
+/// Get a single webhook.
+///
+/// # Errors
+/// Returns [`ApiError`] if the API request fails or network errors occur.
+pub async fn get_webhook(client: &ApiClient, gid: &str) -> Result<Webhook, ApiError> {
+    let response: SingleWebhookResponse = client
+        .get_json_with_pairs(&format!("/webhooks/{gid}"), vec![])
+        .await?;
+    Ok(response.data)
+}
+
+/// Create a webhook. The target must complete the handshake before Asana
+/// answers, so a receiver has to be running already.
+///
+/// # Errors
+/// Returns [`ApiError`] if the API request fails or network errors occur.
+pub async fn create_webhook(
+    client: &ApiClient,
+    request: WebhookCreateRequest,
+) -> Result<Webhook, ApiError> {
+    let response: SingleWebhookResponse = client.post_json("/webhooks", &request).await?;
+    Ok(response.data)
+}
+
+/// Delete a webhook.
+///
+/// # Errors
+/// Returns [`ApiError`] if the API request fails or network errors occur.
+pub async fn delete_webhook(client: &ApiClient, gid: &str) -> Result<(), ApiError> {
+    client.delete(&format!("/webhooks/{gid}"), Vec::new()).await
+}
+
+#[derive(Debug, Deserialize)]
+struct SingleWebhookResponse {
+    data: Webhook,
+}
+
+/// Handshake secrets persisted per target path.
+#[derive(Debug, Clone)]
+pub struct WebhookSecretStore {
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct StoredSecrets {
+    #[serde(default)]
+    secrets: BTreeMap<String, String>,
+    /// Paths expecting a handshake, with the unix time the window closes.
+    #[serde(default)]
+    pending: BTreeMap<String, i64>,
+}
+
+impl WebhookSecretStore {
+    /// Store secrets in the JSON file at `path`.
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Secret stored for `target_path`, if any.
+    ///
+    /// # Errors
+    /// Returns [`ApiError`] if the secrets file cannot be read or parsed.
+    pub async fn get(&self, target_path: &str) -> Result<Option<String>, ApiError> {
+        Ok(self.load().await?.secrets.remove(target_path))
+    }
+
+    /// Remember `secret` for `target_path`, replacing any previous secret.
+    ///
+    /// # Errors
+    /// Returns [`ApiError`] if the secrets file cannot be written.
+    pub async fn save(&self, target_path: &str, secret: &str) -> Result<(), ApiError> {
+        self.update(|stored| {
+            stored
+                .secrets
+                .insert(target_path.to_string(), secret.to_string());
+        })
+        .await
+    }
+
+    /// Forget the secret for `target_path`, returning whether one was stored.
+    ///
+    /// # Errors
+    /// Returns [`ApiError`] if the secrets file cannot be updated.
+    pub async fn remove(&self, target_path: &str) -> Result<bool, ApiError> {
+        self.update(|stored| stored.secrets.remove(target_path).is_some())
+            .await
+    }
+
+    /// Let the receiver accept one handshake on `target_path` for the next
+    /// few minutes, while a webhook for it is being created.
+    ///
+    /// # Errors
+    /// Returns [`ApiError`] if the secrets file cannot be updated.
+    pub async fn expect_handshake(&self, target_path: &str) -> Result<(), ApiError> {
+        let closes_at = chrono::Utc::now().timestamp() + HANDSHAKE_WINDOW_SECS;
+        self.update(|stored| {
+            stored.pending.insert(target_path.to_string(), closes_at);
+        })
+        .await
+    }
+
+    /// Stop accepting a handshake on `target_path`.
+    ///
+    /// # Errors
+    /// Returns [`ApiError`] if the secrets file cannot be updated.
+    pub async fn cancel_handshake(&self, target_path: &str) -> Result<(), ApiError> {
+        self.update(|stored| {
+            stored.pending.remove(target_path);
+        })
+        .await
+    }
+
+    /// Store `secret` if `target_path` is expecting a handshake, closing the
+    /// window. Returns whether the handshake was accepted.
+    async fn accept_handshake(&self, target_path: &str, secret: &str) -> Result<bool, ApiError> {
+        let now = chrono::Utc::now().timestamp();
+        self.update(|stored| {
+            let accepted = stored
+                .pending
+                .remove(target_path)
+                .is_some_and(|closes_at| closes_at >= now);
+            if accepted {
+                stored
+                    .secrets
+                    .insert(target_path.to_string(), secret.to_string());
+            }
+            stored.pending.retain(|_, closes_at| *closes_at >= now);
+            accepted
+        })
+        .await
+    }
+
+    async fn load(&self) -> Result<StoredSecrets, ApiError> {
+        match fs::read(&self.path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(StoredSecrets::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Apply `change` to the stored secrets under the file's lock, so
+    /// `webhook create` and a running receiver never drop each other's
+    /// updates. The file is only rewritten when `change` modified it.
+    async fn update<R>(&self, change: impl FnOnce(&mut StoredSecrets) -> R) -> Result<R, ApiError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let _lock = FileLock::acquire(&self.sibling("lock"), LOCK_TIMEOUT).await?;
+        let mut stored = self.load().await?;
+        let before = stored.clone();
+        let result = change(&mut stored);
+        if stored != before {
+            self.write(&stored).await?;
+        }
+        Ok(result)
+    }
+
+    /// Replace the secrets file with a temporary copy so readers never see
+    /// it half written.
+    async fn write(&self, stored: &StoredSecrets) -> Result<(), ApiError> {
+        let tmp = self.sibling("tmp");
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&tmp).await?;
+        file.write_all(&serde_json::to_vec_pretty(stored)?).await?;
+        file.sync_all().await?;
+        drop(file);
+        fs::rename(&tmp, &self.path).await?;
+        Ok(())
+    }
+
+    /// Path next to the secrets file with `extension` appended to its name.
+    fn sibling(&self, extension: &str) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".");
+        path.push(extension);
+        PathBuf::from(path)
+    }
+}
+
+/// Path component of a webhook target URL, used as the secret key.
+#[must_use]
+pub fn target_path(target: &str) -> String {
+    reqwest::Url::parse(target).map_or_else(|_| target.to_string(), |url| url.path().to_string())
+}
+
+/// Hex HMAC-SHA256 of `body` keyed with `secret`, as sent in
+/// `X-Hook-Signature`.
+#[must_use]
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    hex::encode(mac(secret, body).finalize().into_bytes())
+}
+
+/// Check an `X-Hook-Signature` value in constant time.
+#[must_use]
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    hex::decode(signature.trim())
+        .is_ok_and(|expected| mac(secret, body).verify_slice(&expected).is_ok())
+}
+
+fn mac(secret: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(body);
+    mac
+}
+
+/// Outcome of one request to the webhook receiver.
+#[derive(Debug)]
+pub enum WebhookRequest {
+    /// A new webhook completed the handshake; its secret was stored.
+    Handshake {
+        /// Request path the secret was stored under.
+        path: String,
+    },
+    /// A delivery with a valid signature.
+    Delivery {
+        /// Request path the delivery arrived on.
+        path: String,
+        /// Events in the delivery (empty for heartbeats).
+        events: Vec<Event>,
+    },
+    /// The request was refused.
+    Rejected {
+        /// Request path.
+        path: String,
+        /// Why the request was refused.
+        reason: &'static str,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct Delivery {
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+/// Answer one HTTP request on `stream`: complete expected handshakes and
+/// verify deliveries.
+///
+/// Handshakes are accepted only on paths passed to
+/// [`WebhookSecretStore::expect_handshake`]. The response is written before
+/// returning, so slow event handling does not delay Asana.
+///
+/// # Errors
+/// Returns [`ApiError`] if the connection fails or the secrets file cannot be
+/// accessed.
+pub async fn handle_webhook_request<S>(
+    stream: S,
+    store: &WebhookSecretStore,
+) -> Result<WebhookRequest, ApiError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts
+        .next()
+        .unwrap_or("/")
+        .split('?')
+        .next()
+        .unwrap_or("/")
+        .to_string();
+
+    let mut headers = BTreeMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let reject = |reason| WebhookRequest::Rejected {
+        path: path.clone(),
+        reason,
+    };
+    if method != "POST" {
+        respond(&mut reader, "405 Method Not Allowed", None).await?;
+        return Ok(reject("only POST is accepted"));
+    }
+    let length = headers
+        .get("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        respond(&mut reader, "413 Payload Too Large", None).await?;
+        return Ok(reject("body too large"));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+
+    if let Some(secret) = headers.get("x-hook-secret") {
+        if !store.accept_handshake(&path, secret).await? {
+            respond(&mut reader, "401 Unauthorized", None).await?;
+            return Ok(reject("no webhook create is pending for this path"));
+        }
+        respond(&mut reader, "200 OK", Some(secret)).await?;
+        return Ok(WebhookRequest::Handshake { path });
+    }
+
+    let Some(signature) = headers.get("x-hook-signature") else {
+        respond(&mut reader, "400 Bad Request", None).await?;
+        return Ok(reject("missing X-Hook-Signature"));
+    };
+    let Some(secret) = store.get(&path).await? else {
+        respond(&mut reader, "401 Unauthorized", None).await?;
+        return Ok(reject("no handshake secret stored for this path"));
+    };
+    if !verify_signature(&secret, &body, signature) {
+        respond(&mut reader, "401 Unauthorized", None).await?;
+        return Ok(reject("signature mismatch"));
+    }
+    let Ok(delivery) = serde_json::from_slice::<Delivery>(&body) else {
+        respond(&mut reader, "400 Bad Request", None).await?;
+        return Ok(reject("body is not an event delivery"));
+    };
+    respond(&mut reader, "200 OK", None).await?;
+    Ok(WebhookRequest::Delivery {
+        path,
+        events: delivery.events,
+    })
+}
+
+async fn respond<S>(stream: &mut S, status: &str, secret: Option<&str>) -> Result<(), ApiError>
+where
+    S: AsyncWrite + Unpin,
+{
+    let secret_header =
+        secret.map_or_else(String::new, |secret| format!("X-Hook-Secret: {secret}\r\n"));
+    let response = format!(
+        "HTTP/1.1 {status}\r\n{secret_header}Content-Length: 0\r\nConnection: close\r\n\r\n"
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio::io::duplex;
+
+    async fn exchange(store: &WebhookSecretStore, request: String) -> (WebhookRequest, String) {
+        let (mut client, server) = duplex(64 * 1024);
+        client.write_all(request.as_bytes()).await.expect("write");
+        let outcome = handle_webhook_request(server, store).await.expect("handle");
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.expect("read");
+        (outcome, response)
+    }
+
+    #[tokio::test]
+    async fn receiver_completes_handshakes_and_verifies_deliveries() {
+        let dir = TempDir::new().expect("temp dir");
+        let store = WebhookSecretStore::new(&dir.path().join("secrets.json"));
+        store.expect_handshake("/hooks/p1").await.expect("expect");
+
+        let (outcome, response) = exchange(
+            &store,
+            "POST /hooks/p1 HTTP/1.1\r\nX-Hook-Secret: s3cret\r\nContent-Length: 0\r\n\r\n".into(),
+        )
+        .await;
+        assert!(matches!(outcome, WebhookRequest::Handshake { path } if path == "/hooks/p1"));
+        assert!(response.contains("X-Hook-Secret: s3cret"));
+        assert_eq!(
+            store.get("/hooks/p1").await.expect("get").as_deref(),
+            Some("s3cret")
+        );
+
+        let body =
+            r#"{"events":[{"action":"added","resource":{"gid":"T1","resource_type":"task"}}]}"#;
+        let delivery = |signature: &str| {
+            format!(
+                "POST /hooks/p1 HTTP/1.1\r\nX-Hook-Signature: {signature}\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            )
+        };
+        let (outcome, response) =
+            exchange(&store, delivery(&sign("s3cret", body.as_bytes()))).await;
+        assert!(response.starts_with("HTTP/1.1 200"));
+        let WebhookRequest::Delivery { events, .. } = outcome else {
+            panic!("expected a delivery, got {outcome:?}");
+        };
+        assert_eq!(events[0].resource.gid, "T1");
+
+        let (outcome, response) = exchange(&store, delivery(&sign("wrong", body.as_bytes()))).await;
+        assert!(response.starts_with("HTTP/1.1 401"));
+        assert!(matches!(
+            outcome,
+            WebhookRequest::Rejected {
+                reason: "signature mismatch",
+                ..
+            }
+        ));
+
+        assert!(store.remove("/hooks/p1").await.expect("remove"));
+        assert!(store.get("/hooks/p1").await.expect("get").is_none());
+    }
+
+    #[tokio::test]
+    async fn receiver_rejects_handshakes_without_a_pending_create() {
+        let dir = TempDir::new().expect("temp dir");
+        let store = WebhookSecretStore::new(&dir.path().join("secrets.json"));
+        let handshake = |secret: &str| {
+            format!(
+                "POST /hooks/p1 HTTP/1.1\r\nX-Hook-Secret: {secret}\r\nContent-Length: 0\r\n\r\n"
+            )
+        };
+
+        let (outcome, response) = exchange(&store, handshake("unsolicited")).await;
+        assert!(response.starts_with("HTTP/1.1 401"));
+        assert!(matches!(outcome, WebhookRequest::Rejected { .. }));
+        assert!(store.get("/hooks/p1").await.expect("get").is_none());
+
+        store.expect_handshake("/hooks/p1").await.expect("expect");
+        let (outcome, _) = exchange(&store, handshake("s3cret")).await;
+        assert!(matches!(outcome, WebhookRequest::Handshake { .. }));
+
+        let (outcome, response) = exchange(&store, handshake("attacker")).await;
+        assert!(response.starts_with("HTTP/1.1 401"));
+        assert!(!response.contains("attacker"));
+        assert!(matches!(outcome, WebhookRequest::Rejected { .. }));
+        assert_eq!(
+            store.get("/hooks/p1").await.expect("get").as_deref(),
+            Some("s3cret")
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_updates_keep_every_secret() {
+        let dir = TempDir::new().expect("temp dir");
+        let store = WebhookSecretStore::new(&dir.path().join("secrets.json"));
+
+        let mut saves = Vec::new();
+        for n in 0..16 {
+            let store = store.clone();
+            let target = format!("/hooks/{n}");
+            saves.push(tokio::spawn(
+                async move { store.save(&target, "secret").await },
+            ));
+        }
+        for save in saves {
+            save.await.expect("join").expect("save");
+        }
+
+        for n in 0..16 {
+            let target = format!("/hooks/{n}");
+            assert!(store.get(&target).await.expect("get").is_some());
+        }
+        assert!(!store.sibling("lock").exists());
+        assert!(!store.sibling("tmp").exists());
+    }
+}
@@ -15,7 +15,8 @@ pub async fn list_workspaces(
     client: &ApiClient,
     params: WorkspaceListParams,
 ) -> Result<Vec<Workspace>, ApiError> {
-    let stream = client.paginate_with_limit::<Workspace>("/workspaces", vec![], params.limit);
+    let stream =
+        client.paginate_with_limit::<Workspace>("/workspaces", params.to_query(), params.limit);
     pin_mut!(stream);
 
     let mut workspaces = Vec::new();
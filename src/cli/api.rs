@@ -0,0 +1,129 @@
+//! `api` command: send raw requests to endpoints without a typed command.
+
+use super::build_api_client;
+use crate::{api, config::Config, error::Result, output::select::select};
+use anyhow::{Context, anyhow, bail};
+use clap::Args;
+use reqwest::Method;
+use serde_json::{Map, Value};
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+use tokio::runtime::Builder as RuntimeBuilder;
+
+/// Arguments for `api`.
+#[derive(Args, Debug)]
+pub struct ApiArgs {
+    /// HTTP method: GET, POST, PUT, or DELETE.
+    #[arg(value_parser = parse_method)]
+    pub method: Method,
+    /// Endpoint path relative to the API base URL, e.g. `/portfolios`.
+    pub path: String,
+    /// Add a parameter: a query parameter for GET and DELETE (or with
+    /// `--input`), otherwise a field of the request's `data` object.
+    #[arg(short = 'f', long = "field", value_name = "KEY=VALUE")]
+    pub fields: Vec<String>,
+    /// Send the contents of a JSON file (`-` for stdin) as the request body.
+    #[arg(long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+    /// Fetch every page and merge them into a single `data` array (GET only).
+    #[arg(long)]
+    pub paginate: bool,
+    /// Print only the values at a path such as `data[*].gid`.
+    #[arg(long, value_name = "PATH")]
+    pub select: Option<String>,
+}
+
+fn parse_method(value: &str) -> std::result::Result<Method, String> {
+    match value.to_ascii_uppercase().as_str() {
+        "GET" => Ok(Method::GET),
+        "POST" => Ok(Method::POST),
+        "PUT" => Ok(Method::PUT),
+        "DELETE" => Ok(Method::DELETE),
+        other => Err(format!(
+            "unsupported method '{other}'; expected GET, POST, PUT, or DELETE"
+        )),
+    }
+}
+
+/// Execute the `api` command, printing the response as JSON.
+///
+/// # Errors
+/// Returns an error when the arguments are invalid, the request fails, or the
+/// selector cannot be parsed.
+pub fn handle_api_command(args: ApiArgs, config: &Config) -> Result<()> {
+    if args.paginate && args.method != Method::GET {
+        bail!("--paginate only applies to GET requests");
+    }
+    let fields = args
+        .fields
+        .iter()
+        .map(|field| {
+            field
+                .split_once('=')
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| anyhow!("invalid field `{field}`; expected KEY=VALUE"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let body = args.input.as_ref().map(read_input).transpose()?;
+    let fields_in_query = body.is_some() || matches!(args.method, Method::GET | Method::DELETE);
+    let (query, body) = if fields_in_query {
+        (fields, body)
+    } else if fields.is_empty() {
+        (Vec::new(), None)
+    } else {
+        let data: Map<String, Value> = fields
+            .into_iter()
+            .map(|(key, value)| (key, Value::String(value)))
+            .collect();
+        (Vec::new(), Some(serde_json::json!({ "data": data })))
+    };
+    let path = if args.path.starts_with('/') {
+        args.path
+    } else {
+        format!("/{}", args.path)
+    };
+
+    let client = build_api_client(config)?;
+    let runtime = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to initialise async runtime")?;
+    let response = runtime.block_on(async {
+        if args.paginate {
+            api::get_all_pages(&client, &path, query).await
+        } else {
+            client.request_json(args.method, &path, query, body).await
+        }
+    })?;
+
+    let output = match &args.select {
+        Some(selector) => select(&response, selector)?,
+        None => response,
+    };
+    match output {
+        Value::String(text) => println!("{text}"),
+        other => println!(
+            "{}",
+            serde_json::to_string_pretty(&other).context("failed to serialize response")?
+        ),
+    }
+    Ok(())
+}
+
+fn read_input(path: &PathBuf) -> Result<Value> {
+    let contents = if path.as_os_str() == "-" {
+        let mut buffer = String::new();
+        io::stdin()
+            .read_to_string(&mut buffer)
+            .context("failed to read request body from stdin")?;
+        buffer
+    } else {
+        fs::read_to_string(path)
+            .with_context(|| format!("failed to read request body {}", path.display()))?
+    };
+    serde_json::from_str(&contents)
+        .with_context(|| format!("request body {} is not valid JSON", path.display()))
+}
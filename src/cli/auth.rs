@@ -0,0 +1,186 @@
+//! OAuth login and logout commands.
+
+use crate::{
+    api::{
+        ApiError, OAuthApp, OAuthTokenProvider, OAuthTokens,
+        oauth::{Pkce, random_token, receive_authorization_code},
+    },
+    config::{Config, OAuthConfig},
+    error::Result,
+};
+use anyhow::{Context, anyhow};
+use clap::{Args, Subcommand};
+use secrecy::{ExposeSecret, SecretString};
+use std::{process::Command, time::Duration};
+use tokio::{net::TcpListener, runtime::Builder as RuntimeBuilder, time::timeout};
+use tracing::warn;
+
+/// How long `auth login` waits for the browser to complete the consent flow.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Primary `auth` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum AuthCommand {
+    /// Sign in through the browser with OAuth (authorization code + PKCE).
+    Login(AuthLoginArgs),
+    /// Forget stored OAuth tokens.
+    Logout,
+}
+
+/// Arguments for `auth login`.
+#[derive(Args, Debug)]
+pub struct AuthLoginArgs {
+    /// OAuth application client id (remembered after the first login).
+    #[arg(long)]
+    pub client_id: Option<String>,
+    /// OAuth application client secret, if the application has one.
+    #[arg(long)]
+    pub client_secret: Option<String>,
+    /// Loopback port receiving the redirect; register
+    /// `http://localhost:<port>/callback` with the application.
+    #[arg(long, default_value_t = 8765)]
+    pub port: u16,
+    /// Print the authorization URL instead of opening a browser.
+    #[arg(long)]
+    pub no_browser: bool,
+}
+
+/// Execute auth subcommands.
+///
+/// # Errors
+/// Returns an error when the login flow fails or the configuration cannot be
+/// updated.
+pub fn handle_auth_command(command: AuthCommand, config: &mut Config) -> Result<()> {
+    match command {
+        AuthCommand::Login(args) => login(args, config),
+        AuthCommand::Logout => {
+            if config.delete_oauth()? {
+                println!("Removed stored OAuth tokens.");
+            } else {
+                println!("No OAuth tokens were stored.");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn login(args: AuthLoginArgs, config: &mut Config) -> Result<()> {
+    let stored = config.oauth().cloned().unwrap_or_default();
+    let client_id = args
+        .client_id
+        .or_else(|| Some(stored.client_id.clone()).filter(|id| !id.is_empty()))
+        .ok_or_else(|| anyhow!("no OAuth client id stored; pass --client-id"))?;
+    let client_secret = args.client_secret.or(stored.client_secret);
+
+    let app = OAuthApp::new(
+        client_id.clone(),
+        client_secret
+            .clone()
+            .map(|secret| SecretString::new(secret.into())),
+        config.effective_api_base_url(),
+    )?
+    .with_network(&config.network())?;
+
+    let runtime = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to initialise async runtime")?;
+    let tokens = runtime.block_on(async {
+        let listener = TcpListener::bind(("127.0.0.1", args.port))
+            .await
+            .with_context(|| format!("failed to listen on port {}", args.port))?;
+        let port = listener.local_addr()?.port();
+        let redirect_uri = format!("http://localhost:{port}/callback");
+
+        let pkce = Pkce::generate()?;
+        let state = random_token(16)?;
+        let url = app.authorization_url(&redirect_uri, &state, &pkce)?;
+        println!("Open this URL to authorize asana-cli:\n\n  {url}\n");
+        if !args.no_browser {
+            open_browser(&url);
+        }
+        println!("Waiting for the authorization redirect on {redirect_uri}...");
+
+        let code = timeout(LOGIN_TIMEOUT, receive_authorization_code(&listener, &state))
+            .await
+            .map_err(|_| anyhow!("timed out waiting for the authorization redirect"))??;
+        let tokens = app.exchange_code(&code, &redirect_uri, &pkce).await?;
+        Ok::<_, anyhow::Error>(tokens)
+    })?;
+
+    config.store_oauth(OAuthConfig {
+        client_id,
+        client_secret,
+        access_token: tokens.access_token.expose_secret().to_owned(),
+        refresh_token: tokens.refresh_token.expose_secret().to_owned(),
+        expires_at: tokens.expires_at,
+    })?;
+    println!(
+        "Logged in with OAuth; tokens stored in {}.",
+        config.path().display()
+    );
+    Ok(())
+}
+
+/// Build a provider for stored OAuth credentials that writes refreshed
+/// tokens back to the configuration file.
+///
+/// # Errors
+/// Returns an error if the OAuth HTTP client cannot be created.
+pub(super) fn oauth_token_provider(
+    config: &Config,
+    oauth: &OAuthConfig,
+) -> std::result::Result<OAuthTokenProvider, ApiError> {
+    let app = OAuthApp::new(
+        oauth.client_id.clone(),
+        oauth
+            .client_secret
+            .clone()
+            .map(|secret| SecretString::new(secret.into())),
+        config.effective_api_base_url(),
+    )?
+    .with_network(&config.network())?;
+    let tokens = OAuthTokens {
+        access_token: SecretString::new(oauth.access_token.clone().into()),
+        refresh_token: SecretString::new(oauth.refresh_token.clone().into()),
+        expires_at: oauth.expires_at,
+    };
+    let profile = config.profile_name().to_owned();
+    Ok(OAuthTokenProvider::new(app, tokens)
+        .on_refresh(move |tokens| persist_refreshed_tokens(&profile, tokens)))
+}
+
+fn persist_refreshed_tokens(profile: &str, tokens: &OAuthTokens) {
+    let result = Config::load().and_then(|mut config| {
+        config.set_profile(profile)?;
+        let mut oauth = config.oauth().cloned().unwrap_or_default();
+        tokens
+            .access_token
+            .expose_secret()
+            .clone_into(&mut oauth.access_token);
+        tokens
+            .refresh_token
+            .expose_secret()
+            .clone_into(&mut oauth.refresh_token);
+        oauth.expires_at = tokens.expires_at;
+        config.store_oauth(oauth)
+    });
+    if let Err(err) = result {
+        warn!("failed to persist refreshed OAuth tokens: {err:#}");
+    }
+}
+
+fn open_browser(url: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    if let Err(err) = command.arg(url).spawn() {
+        warn!("failed to open a browser: {err}");
+    }
+}
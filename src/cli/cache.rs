@@ -0,0 +1,247 @@
+//! Cache CLI command implementations.
+
+use super::{
+    api_client_builder,
+    task::{format_bytes, resolve_assignee},
+};
+use crate::{
+    api::{self, ApiClient, ApiError},
+    config::Config,
+    error::Result,
+    models::{
+        ProjectListParams, TagListParams, TaskListParams, UserListParams, WorkspaceListParams,
+    },
+    output::{
+        ProjectOutputFormat, TaskOutputFormat, project::project_list_fields, task::task_list_fields,
+    },
+};
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use std::{collections::BTreeSet, time::Duration};
+use tokio::runtime::Builder as RuntimeBuilder;
+use tracing::warn;
+
+/// Primary `cache` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Show entry count, size, hit rate, and the oldest entry.
+    Stats,
+    /// Remove every cached response.
+    Clear,
+    /// Remove expired cache entries.
+    Prune,
+    /// Prefetch workspaces, projects, your tasks, users, tags, and custom
+    /// fields.
+    Warm(CacheWarmArgs),
+}
+
+/// Arguments for `cache warm`.
+#[derive(Args, Debug)]
+pub struct CacheWarmArgs {
+    /// Restrict warming to a single workspace gid.
+    #[arg(long, value_name = "GID")]
+    pub workspace: Option<String>,
+    /// How long warmed entries stay fresh, in hours.
+    #[arg(long, value_name = "HOURS", default_value_t = 24)]
+    pub ttl_hours: u64,
+}
+
+/// Execute cache subcommands.
+///
+/// # Errors
+/// Returns an error when the cache cannot be read or modified, or when
+/// prefetching fails.
+pub fn handle_cache_command(command: CacheCommand, config: &Config) -> Result<()> {
+    let runtime = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to initialise async runtime")?;
+
+    runtime.block_on(async move {
+        let cache_dir = config.cache_dir();
+        match command {
+            CacheCommand::Stats => {
+                let stats = api::cache::stats(cache_dir).await?;
+                println!("Cache directory: {}", cache_dir.display());
+                println!("Entries: {} ({} expired)", stats.entries, stats.expired);
+                match config.cache_max_bytes() {
+                    Some(limit) => println!(
+                        "Size: {} (limit {})",
+                        format_bytes(stats.total_bytes),
+                        format_bytes(limit)
+                    ),
+                    None => println!("Size: {} (unlimited)", format_bytes(stats.total_bytes)),
+                }
+                match stats.hit_rate() {
+                    Some(rate) => println!(
+                        "Hit rate: {:.1}% ({} hits, {} misses)",
+                        rate * 100.0,
+                        stats.hits,
+                        stats.misses
+                    ),
+                    None => println!("Hit rate: n/a"),
+                }
+                match stats.oldest {
+                    Some(oldest) => println!(
+                        "Oldest entry: {}",
+                        DateTime::<Utc>::from(oldest).format("%Y-%m-%d %H:%M:%S UTC")
+                    ),
+                    None => println!("Oldest entry: n/a"),
+                }
+                Ok(())
+            }
+            CacheCommand::Clear => {
+                let removed = api::cache::clear(cache_dir).await?;
+                println!("Removed {removed} cache entries.");
+                Ok(())
+            }
+            CacheCommand::Prune => {
+                let removed = api::cache::prune(cache_dir).await?;
+                println!("Pruned {removed} expired cache entries.");
+                Ok(())
+            }
+            CacheCommand::Warm(args) => warm_cache(config, args).await,
+        }
+    })
+}
+
+async fn warm_cache(config: &Config, args: CacheWarmArgs) -> Result<()> {
+    if config.offline() {
+        return Err(anyhow!("cannot warm the cache in offline mode"));
+    }
+
+    let ttl = Duration::from_secs(args.ttl_hours.saturating_mul(3600));
+    let client = api_client_builder(config)?
+        .cache_ttl(ttl)
+        .refresh_cache(true)
+        .build()?;
+
+    api::get_current_user(&client).await?;
+    let mut workspaces = api::list_workspaces(&client, WorkspaceListParams::default()).await?;
+    if let Some(gid) = args.workspace.as_deref() {
+        workspaces.retain(|workspace| workspace.gid == gid);
+        if workspaces.is_empty() {
+            return Err(anyhow!("workspace {gid} not found"));
+        }
+    }
+
+    let mut totals = WarmTotals::default();
+    for workspace in &workspaces {
+        warm_workspace(&client, config, &workspace.gid, &mut totals).await;
+    }
+
+    println!(
+        "Warmed {} workspace(s): {} projects, {} tasks, {} users, {} tags, {} custom fields (fresh for {}h).",
+        workspaces.len(),
+        totals.projects,
+        totals.tasks,
+        totals.users,
+        totals.tags,
+        totals.custom_fields,
+        args.ttl_hours
+    );
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct WarmTotals {
+    projects: usize,
+    tasks: usize,
+    users: usize,
+    tags: usize,
+    custom_fields: usize,
+}
+
+/// Prefetch one workspace with the queries `project list` and `task list`
+/// send for it, once per distinct field set their output formats request, so
+/// each format can be answered offline. Individual resource failures (for
+/// example custom fields on workspaces without access to them) are logged
+/// and skipped.
+async fn warm_workspace(
+    client: &ApiClient,
+    config: &Config,
+    workspace: &str,
+    totals: &mut WarmTotals,
+) {
+    let mut projects = Ok(Vec::new());
+    for fields in field_sets(ProjectOutputFormat::value_variants(), project_list_fields) {
+        let params = ProjectListParams {
+            workspace: Some(workspace.to_string()),
+            fields,
+            exact_fields: true,
+            ..ProjectListParams::default()
+        };
+        projects = api::list_projects(client, params).await;
+        if projects.is_err() {
+            break;
+        }
+    }
+    tally(&mut totals.projects, workspace, "projects", projects);
+
+    let mut tasks = Ok(Vec::new());
+    for fields in field_sets(TaskOutputFormat::value_variants(), task_list_fields) {
+        let params = TaskListParams {
+            workspace: Some(workspace.to_string()),
+            assignee: resolve_assignee(None, config, true),
+            fields,
+            exact_fields: true,
+            ..TaskListParams::default()
+        };
+        tasks = api::list_tasks(client, params).await;
+        if tasks.is_err() {
+            break;
+        }
+    }
+    tally(&mut totals.tasks, workspace, "tasks", tasks);
+
+    let users = api::list_users(
+        client,
+        UserListParams {
+            workspace_gid: workspace.to_string(),
+            limit: None,
+            offset: None,
+        },
+    )
+    .await;
+    tally(&mut totals.users, workspace, "users", users);
+
+    let tags = api::list_tags(
+        client,
+        TagListParams {
+            workspace: workspace.to_string(),
+            limit: None,
+            offset: None,
+        },
+    )
+    .await;
+    tally(&mut totals.tags, workspace, "tags", tags);
+
+    let custom_fields = api::list_custom_fields(client, workspace, None).await;
+    tally(
+        &mut totals.custom_fields,
+        workspace,
+        "custom fields",
+        custom_fields,
+    );
+}
+
+/// Distinct `opt_fields` sets a list command requests across `formats`.
+fn field_sets<F: Copy>(
+    formats: &[F],
+    fields: impl Fn(F) -> BTreeSet<String>,
+) -> BTreeSet<BTreeSet<String>> {
+    formats.iter().map(|format| fields(*format)).collect()
+}
+
+fn tally<T>(
+    total: &mut usize,
+    workspace: &str,
+    resource: &str,
+    result: std::result::Result<Vec<T>, ApiError>,
+) {
+    match result {
+        Ok(items) => *total += items.len(),
+        Err(err) => warn!(workspace, resource, "failed to warm cache: {err}"),
+    }
+}
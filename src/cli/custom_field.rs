@@ -1,6 +1,6 @@
 //! Custom field CLI command implementations.
 
-use super::build_api_client;
+use super::{build_api_client, mark_if_stale};
 use crate::{api, config::Config, error::Result, models::CustomField};
 use anyhow::Context;
 use clap::{Args, Subcommand, ValueEnum};
@@ -132,8 +132,11 @@ async fn list_custom_fields_command(
             }
         }
         CustomFieldOutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&fields)
-                .context("failed to serialize custom fields to JSON")?;
+            let json = mark_if_stale(
+                client,
+                crate::output::to_json(&fields)
+                    .context("failed to serialize custom fields to JSON")?,
+            );
             println!("{json}");
         }
         CustomFieldOutputFormat::Detail => {
@@ -157,8 +160,11 @@ async fn show_custom_field_command(
 
     match args.format {
         CustomFieldOutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&field)
-                .context("failed to serialize custom field to JSON")?;
+            let json = mark_if_stale(
+                client,
+                crate::output::to_json(&field)
+                    .context("failed to serialize custom field to JSON")?,
+            );
             println!("{json}");
         }
         _ => {
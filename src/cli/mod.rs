@@ -1,18 +1,31 @@
 //! Command-line interface entry points for the Asana CLI.
 
+mod api;
+mod auth;
+mod cache;
 mod custom_field;
+mod progress;
 mod project;
 mod section;
 mod tag;
 mod task;
 mod user;
+mod watch;
+mod webhook;
 mod workspace;
 
-use crate::api::{ApiClient, ApiError, AuthToken};
-use crate::config::Config;
+use crate::api::{
+    ApiClient, ApiClientBuilder, ApiError, AuthToken, CassetteMode, CommandTokenProvider,
+    ListingState, NetworkOptions,
+};
+use crate::config::{Config, TokenSource};
 use crate::error::Result;
+use crate::secrets::{SealedToken, unlock_token};
 use anyhow::{Context, anyhow};
-use clap::{Parser, Subcommand};
+use api::ApiArgs;
+use auth::AuthCommand;
+use cache::CacheCommand;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use clap_complete::Shell;
 use colored::Colorize;
 use custom_field::CustomFieldCommand;
@@ -22,12 +35,19 @@ use section::SectionCommand;
 use serde_json::Value;
 use std::fs::{self, File};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::SystemTime;
 use tag::TagCommand;
 use task::TaskCommand;
 use tokio::runtime::Builder as RuntimeBuilder;
 use tracing::{debug, info};
 use user::UserCommand;
+use watch::WatchArgs;
+use webhook::WebhookCommand;
 use workhelix_cli_common::{DoctorCheck, DoctorChecks, LicenseType, RepoInfo};
 use workspace::WorkspaceCommand;
 
@@ -43,6 +63,42 @@ const VERSION: &str = match option_env!("CARGO_PKG_VERSION") {
 #[command(about = "An interface to the Asana API")]
 #[command(version = VERSION)]
 struct Cli {
+    /// Answer requests from the local cache without contacting Asana.
+    #[arg(long, global = true)]
+    offline: bool,
+    /// Save every API request and response as fixtures in this directory.
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+    /// Answer API requests from fixtures recorded with `--record`.
+    #[arg(long, global = true, value_name = "DIR")]
+    replay: Option<PathBuf>,
+    /// Write every HTTP request and response to this file in HAR format.
+    #[arg(long, global = true, value_name = "FILE")]
+    trace_http: Option<PathBuf>,
+    /// Items to request per page when listing (1-100).
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=100))]
+    page_size: Option<u8>,
+    /// Resume a list command from an offset printed by `--print-next-offset`.
+    #[arg(long, global = true, value_name = "TOKEN")]
+    start_offset: Option<String>,
+    /// Print the offset to resume the listing from on stderr.
+    #[arg(long, global = true)]
+    print_next_offset: bool,
+    /// PEM file with extra CA certificates to trust (e.g. a TLS-intercepting proxy).
+    #[arg(long, global = true, value_name = "FILE")]
+    ca_bundle: Option<PathBuf>,
+    /// PEM file with a client certificate and private key for mutual TLS.
+    #[arg(long, global = true, value_name = "FILE")]
+    client_cert: Option<PathBuf>,
+    /// Send API traffic through this proxy instead of `HTTPS_PROXY`.
+    #[arg(long, global = true, value_name = "URL")]
+    proxy: Option<String>,
+    /// Comma separated hosts that bypass `--proxy`.
+    #[arg(long, global = true, value_name = "HOSTS")]
+    no_proxy: Option<String>,
+    /// Configuration profile to use (also `ASANA_PROFILE`).
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
     /// Subcommand to execute.
     #[command(subcommand)]
     command: Commands,
@@ -59,6 +115,11 @@ enum Commands {
         #[command(subcommand)]
         command: ConfigCommand,
     },
+    /// Sign in with OAuth instead of a Personal Access Token.
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommand,
+    },
     /// Task operations.
     Task {
         #[command(subcommand)]
@@ -95,6 +156,20 @@ enum Commands {
         #[command(subcommand)]
         command: Box<UserCommand>,
     },
+    /// Tail changes to a project, task, or tag through the Events API.
+    Watch(WatchArgs),
+    /// Manage webhooks and receive their deliveries.
+    Webhook {
+        #[command(subcommand)]
+        command: WebhookCommand,
+    },
+    /// Send a raw request to any API endpoint.
+    Api(ApiArgs),
+    /// Inspect and manage the local response cache.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
     /// Generate shell completion scripts.
     Completions {
         /// Shell to generate completions for.
@@ -133,6 +208,29 @@ enum ConfigCommand {
     Get,
     /// Validate the stored Personal Access Token against the Asana API.
     Test,
+    /// Manage named configuration profiles.
+    Profile {
+        #[command(subcommand)]
+        command: ConfigProfileCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigProfileCommand {
+    /// List profiles, marking the active one.
+    List,
+    /// Use a profile by default when `--profile` and `ASANA_PROFILE` are unset.
+    Use {
+        /// Profile name (`default` selects the top-level settings).
+        name: String,
+    },
+    /// Create a profile from the settings of an existing one.
+    Copy {
+        /// Profile to copy from.
+        from: String,
+        /// Name of the new profile.
+        to: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -142,6 +240,18 @@ enum ConfigSetCommand {
         /// Personal Access Token value; omit to be prompted securely.
         #[arg(long)]
         token: Option<String>,
+        /// Encrypt the token with a passphrase instead of storing plain text.
+        #[arg(long)]
+        encrypt: bool,
+    },
+    /// Store a command that prints the token, e.g. `pass show asana`.
+    TokenCommand {
+        /// Shell command whose stdout is the token.
+        #[arg(long, value_name = "COMMAND")]
+        command: Option<String>,
+        /// Clear the stored token command.
+        #[arg(long)]
+        clear: bool,
     },
     /// Store the default workspace gid.
     Workspace {
@@ -172,15 +282,86 @@ enum ConfigSetCommand {
     },
 }
 
+/// Apply per-invocation global flags on top of the loaded configuration.
+fn apply_global_flags(cli: &Cli, config: &mut Config) -> Result<()> {
+    if let Some(profile) = cli.profile.as_deref() {
+        config.set_profile(profile)?;
+    }
+    if cli.offline {
+        config.set_offline(true);
+    }
+    if let Some(path) = cli.trace_http.clone() {
+        config.set_trace_http(path);
+    }
+    if let Some(size) = cli.page_size {
+        config.set_page_size(usize::from(size));
+    }
+    if let Some(offset) = cli.start_offset.clone() {
+        config.set_start_offset(offset);
+    }
+    config.set_print_next_offset(cli.print_next_offset);
+    config.set_network(NetworkOptions {
+        ca_bundle: cli.ca_bundle.clone(),
+        client_cert: cli.client_cert.clone(),
+        proxy: cli.proxy.clone(),
+        no_proxy: cli.no_proxy.clone(),
+    });
+    if let Some(dir) = cli.record.clone() {
+        config.set_cassette(CassetteMode::Record(dir));
+    } else if let Some(dir) = cli.replay.clone() {
+        config.set_cassette(CassetteMode::Replay(dir));
+    }
+    Ok(())
+}
+
 /// Parse and execute CLI commands, returning the desired process exit code.
 ///
 /// # Errors
 /// Returns an error when command execution fails prior to producing an exit code.
 pub fn run() -> Result<i32> {
-    let cli = Cli::parse();
+    execute(Cli::parse())
+}
+
+/// Parse and execute CLI commands, reporting a failure on stderr through
+/// [`crate::error::report`] and returning the process exit code either way.
+///
+/// Errors are reported as JSON when the invoked subcommand was given
+/// `--format json` or `--output json`.
+#[must_use]
+pub fn run_and_report() -> i32 {
+    let matches = Cli::command().get_matches();
+    let json_errors = json_output_requested(&matches);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    match execute(cli) {
+        Ok(code) => code,
+        Err(err) => {
+            debug!(error = %err, "command execution failed");
+            crate::error::report(&err, json_errors)
+        }
+    }
+}
+
+/// Whether the innermost parsed subcommand has `--format json` or
+/// `--output json`.
+fn json_output_requested(matches: &ArgMatches) -> bool {
+    let mut matches = matches;
+    while let Some((_, sub)) = matches.subcommand() {
+        matches = sub;
+    }
+    ["format", "output"].iter().any(|id| {
+        matches
+            .try_get_raw(id)
+            .ok()
+            .flatten()
+            .is_some_and(|mut values| values.any(|value| value == "json"))
+    })
+}
+
+fn execute(cli: Cli) -> Result<i32> {
     debug!(?cli, "parsed CLI arguments");
 
     let mut config = Config::load()?;
+    apply_global_flags(&cli, &mut config)?;
     debug!(
         config_path = %config.path().display(),
         "configuration handle prepared"
@@ -199,6 +380,10 @@ pub fn run() -> Result<i32> {
             handle_config_command(command, &mut config)?;
             0
         }
+        Commands::Auth { command } => {
+            auth::handle_auth_command(command, &mut config)?;
+            0
+        }
         Commands::Task { command } => {
             task::handle_task_command(*command, &config)?;
             0
@@ -227,6 +412,22 @@ pub fn run() -> Result<i32> {
             handle_user_command(*command, &config)?;
             0
         }
+        Commands::Watch(args) => {
+            watch::handle_watch_command(args, &config)?;
+            0
+        }
+        Commands::Webhook { command } => {
+            webhook::handle_webhook_command(command, &config)?;
+            0
+        }
+        Commands::Api(args) => {
+            api::handle_api_command(args, &config)?;
+            0
+        }
+        Commands::Cache { command } => {
+            cache::handle_cache_command(command, &config)?;
+            0
+        }
         Commands::Completions { shell } => {
             workhelix_cli_common::completions::generate_completions::<Cli>(shell);
             0
@@ -235,28 +436,7 @@ pub fn run() -> Result<i32> {
             write_manpage(dir)?;
             0
         }
-        Commands::Doctor => {
-            struct AsanaCliDoctor;
-
-            impl DoctorChecks for AsanaCliDoctor {
-                fn repo_info() -> RepoInfo {
-                    RepoInfo::new("tftio", "asana-cli")
-                }
-
-                fn current_version() -> &'static str {
-                    VERSION
-                }
-
-                fn tool_checks(&self) -> Vec<DoctorCheck> {
-                    crate::doctor::tool_specific_checks()
-                }
-            }
-
-            let tool = AsanaCliDoctor;
-            let exit = workhelix_cli_common::doctor::run_doctor(&tool);
-            info!(exit_code = exit, "doctor command completed");
-            exit
-        }
+        Commands::Doctor => run_doctor(&config),
         Commands::Update {
             version: _,
             force: _,
@@ -273,6 +453,41 @@ pub fn run() -> Result<i32> {
     Ok(exit_code)
 }
 
+/// Tell the user when a finished listing restarted, and where it can resume.
+pub(super) fn report_listing(config: &Config, listing: &ListingState) {
+    let restarts = listing.restarts();
+    if restarts > 0 {
+        eprintln!(
+            "{} pagination offset expired; restarted the listing {restarts} time(s) from the first page",
+            "warning:".yellow().bold(),
+        );
+    }
+    if config.print_next_offset() {
+        eprintln!("next_offset={}", listing.next_offset().unwrap_or_default());
+    }
+}
+
+/// Stale-cache hook that warns the first time the client serves an expired
+/// entry in place of live data.
+fn warn_when_stale() -> impl Fn(SystemTime) + Send + Sync + 'static {
+    let warned = AtomicBool::new(false);
+    move |as_of| {
+        if !warned.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "{} showing cached data, stale as of {}",
+                "warning:".yellow().bold(),
+                chrono::DateTime::<chrono::Local>::from(as_of).format("%Y-%m-%d %H:%M:%S %Z")
+            );
+        }
+    }
+}
+
+/// JSON output read through `client`, wrapped with stale-cache metadata when
+/// any of it came from expired cache entries.
+pub(super) fn mark_if_stale(client: &ApiClient, json: String) -> String {
+    crate::output::mark_stale(json, client.served_stale())
+}
+
 fn print_version() {
     println!("{} {}", "asana-cli".green().bold(), VERSION);
 }
@@ -312,27 +527,62 @@ fn handle_config_command(command: ConfigCommand, config: &mut Config) -> Result<
             Ok(())
         }
         ConfigCommand::Test => handle_config_test(config),
+        ConfigCommand::Profile { command } => handle_config_profile(command, config),
     }
 }
 
-fn handle_config_set(command: ConfigSetCommand, config: &mut Config) -> Result<()> {
+fn handle_config_profile(command: ConfigProfileCommand, config: &mut Config) -> Result<()> {
     match command {
-        ConfigSetCommand::Token { token } => {
-            let value = match token {
-                Some(value) => value,
-                None => rpassword::prompt_password("Enter Personal Access Token: ")
-                    .context("failed to read token from prompt")?,
-            };
+        ConfigProfileCommand::List => {
+            let active = config.profile_name();
+            for name in config.profile_names() {
+                let marker = if name == active { "*" } else { " " };
+                println!("{marker} {name}");
+            }
+            if !config.profile_exists() {
+                println!("* {active} (not yet saved)");
+            }
+            Ok(())
+        }
+        ConfigProfileCommand::Use { name } => {
+            config
+                .use_profile(&name)
+                .with_context(|| format!("failed to select profile `{name}`"))?;
+            println!("Profile `{name}` is now the default.");
+            Ok(())
+        }
+        ConfigProfileCommand::Copy { from, to } => {
+            config
+                .copy_profile(&from, &to)
+                .with_context(|| format!("failed to copy profile `{from}` to `{to}`"))?;
+            println!("Profile `{to}` created from `{from}`.");
+            Ok(())
+        }
+    }
+}
 
-            if value.trim().is_empty() {
-                return Err(anyhow!("token value cannot be empty"));
+fn handle_config_set(command: ConfigSetCommand, config: &mut Config) -> Result<()> {
+    match command {
+        ConfigSetCommand::Token { token, encrypt } => store_token(config, token, encrypt),
+        ConfigSetCommand::TokenCommand { command, clear } => {
+            if clear {
+                config
+                    .set_token_command(None)
+                    .context("failed to clear token command")?;
+                println!("Token command cleared.");
+                return Ok(());
             }
 
-            let secret = SecretString::new(value.into());
+            let value = command
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| anyhow!("provide --command <command> or use --clear"))?;
+
             config
-                .store_personal_access_token(&secret)
-                .context("failed to store Personal Access Token")?;
-            println!("Personal Access Token stored in configuration file.");
+                .set_token_command(Some(value.to_string()))
+                .context("failed to store token command")?;
+            println!("Token command stored in configuration file.");
             Ok(())
         }
         ConfigSetCommand::Workspace { workspace, clear } => {
@@ -403,6 +653,7 @@ fn handle_config_set(command: ConfigSetCommand, config: &mut Config) -> Result<(
 
 fn handle_config_get(config: &Config) {
     println!("Configuration file: {}", config.path().display());
+    println!("Profile: {}", config.profile_name());
     println!("API base URL: {}", config.effective_api_base_url());
     println!(
         "Default workspace: {}",
@@ -426,7 +677,9 @@ fn handle_config_get(config: &Config) {
             .unwrap_or("not set")
     );
 
-    if let Some(_token) = config.personal_access_token() {
+    if matches!(config.token_source(), TokenSource::EncryptedConfigFile) {
+        println!("Personal Access Token: stored encrypted in configuration file");
+    } else if let Some(_token) = config.personal_access_token() {
         let status = if config.environment_token_available() {
             "provided via environment variable"
         } else if config.has_persisted_token() {
@@ -438,6 +691,7 @@ fn handle_config_get(config: &Config) {
     } else {
         println!("Personal Access Token: not set");
     }
+    println!("Token source: {}", config.token_source());
 }
 
 fn handle_config_test(config: &Config) -> Result<()> {
@@ -459,35 +713,134 @@ fn handle_config_test(config: &Config) -> Result<()> {
                 println!("Personal Access Token validated for {user_name}.");
                 Ok(())
             }
-            Err(ApiError::Authentication(_)) => Err(anyhow!(
-                "authentication failed; verify your Personal Access Token"
-            )),
-            Err(ApiError::RateLimited { retry_after, .. }) => Err(anyhow!(
-                "Asana rate limited the request. Retry after {:.1} seconds",
-                retry_after.as_secs_f32()
-            )),
-            Err(ApiError::Offline { .. }) => Err(anyhow!(
-                "offline mode enabled; disable offline mode to contact Asana"
-            )),
-            Err(err) => Err(anyhow!(err)),
+            Err(err @ ApiError::Authentication(_)) => Err(anyhow::Error::new(err)
+                .context("authentication failed; verify your Personal Access Token")),
+            Err(err @ ApiError::RateLimited { retry_after, .. }) => Err(anyhow::Error::new(err)
+                .context(format!(
+                    "Asana rate limited the request. Retry after {:.1} seconds",
+                    retry_after.as_secs_f32()
+                ))),
+            Err(err @ ApiError::Offline { .. }) => Err(anyhow::Error::new(err)
+                .context("offline mode enabled; disable offline mode to contact Asana")),
+            Err(err) => Err(err.into()),
         }
     })
 }
 
 pub(super) fn build_api_client(config: &Config) -> Result<ApiClient> {
-    let token = config.personal_access_token().ok_or_else(|| {
-        anyhow!("no Personal Access Token found; run `asana-cli config set token`")
-    })?;
+    let client = api_client_builder(config)?.build()?;
+    Ok(client)
+}
 
-    let auth_token = AuthToken::new(token);
+/// Prepare a client builder from configuration, leaving room for per-command
+/// overrides such as a longer cache TTL.
+pub(super) fn api_client_builder(config: &Config) -> Result<ApiClientBuilder> {
+    let replaying = matches!(config.cassette(), Some(CassetteMode::Replay(_)));
+    let builder = match (config.token_source(), config.oauth()) {
+        (TokenSource::Command(command), _) => {
+            ApiClientBuilder::with_token_provider(Arc::new(CommandTokenProvider::new(command)))
+        }
+        (TokenSource::OAuth, Some(oauth)) => ApiClientBuilder::with_token_provider(Arc::new(
+            auth::oauth_token_provider(config, oauth)?,
+        )),
+        (TokenSource::EncryptedConfigFile, _) if !replaying => {
+            ApiClient::builder(AuthToken::new(unlock_encrypted_token(config)?))
+        }
+        _ => {
+            let token = match config.personal_access_token() {
+                Some(token) => token,
+                // Replayed runs never reach Asana, so fixtures work without credentials.
+                None if replaying => SecretString::new("replay".into()),
+                None => {
+                    return Err(ApiError::Authentication(format!(
+                        "no Personal Access Token found for profile `{}`; run \
+                         `asana-cli config set token` or `asana-cli auth login`",
+                        config.profile_name()
+                    ))
+                    .into());
+                }
+            };
+            ApiClient::builder(AuthToken::new(token))
+        }
+    };
     let cache_dir = config.cache_dir().to_path_buf();
 
-    let client = ApiClient::builder(auth_token)
+    let mut builder = builder
         .base_url(config.effective_api_base_url().to_string())
         .cache_dir(cache_dir)
-        .build()?;
+        .offline(config.offline())
+        .stale_fallback(config.cache_stale_fallback())
+        .cache_max_bytes(config.cache_max_bytes())
+        .cassette(config.cassette().cloned())
+        .trace_http(config.trace_http().map(Path::to_path_buf))
+        .page_size(config.page_size())
+        .network(config.network())
+        .on_stale(warn_when_stale());
+    if let Some(max_age) = config.cache_stale_max_age() {
+        builder = builder.stale_max_age(max_age);
+    }
+    Ok(builder)
+}
 
-    Ok(client)
+fn store_token(config: &mut Config, token: Option<String>, encrypt: bool) -> Result<()> {
+    let value = match token {
+        Some(value) => value,
+        None => rpassword::prompt_password("Enter Personal Access Token: ")
+            .context("failed to read token from prompt")?,
+    };
+
+    if value.trim().is_empty() {
+        return Err(anyhow!("token value cannot be empty"));
+    }
+
+    let secret = SecretString::new(value.into());
+    if encrypt {
+        let passphrase = prompt_new_passphrase()?;
+        let sealed = SealedToken::seal(&secret, &passphrase)?;
+        config
+            .store_encrypted_token(&sealed)
+            .context("failed to store encrypted Personal Access Token")?;
+        if let Some(cache) = config.unlock_cache() {
+            cache.clear();
+        }
+        println!("Encrypted Personal Access Token stored in configuration file.");
+        return Ok(());
+    }
+    config
+        .store_personal_access_token(&secret)
+        .context("failed to store Personal Access Token")?;
+    println!("Personal Access Token stored in configuration file.");
+    Ok(())
+}
+
+/// Decrypt the stored token, prompting for the passphrase unless a recent
+/// unlock is still cached in the runtime directory.
+fn unlock_encrypted_token(config: &Config) -> Result<SecretString> {
+    let sealed = config
+        .encrypted_token()?
+        .ok_or_else(|| anyhow!("no encrypted token stored"))?;
+    let cache = config.unlock_cache();
+    let cache = cache.as_ref().zip(config.token_unlock_duration());
+    unlock_token(&sealed, cache, || {
+        rpassword::prompt_password("Passphrase for Asana token: ")
+            .map(|passphrase| SecretString::new(passphrase.into()))
+            .context("failed to read passphrase from prompt")
+    })
+    .context("failed to unlock encrypted Personal Access Token")
+}
+
+fn prompt_new_passphrase() -> Result<SecretString> {
+    let passphrase = rpassword::prompt_password("New passphrase: ")
+        .context("failed to read passphrase from prompt")?;
+    if passphrase.is_empty() {
+        return Err(anyhow!("passphrase cannot be empty"));
+    }
+    let confirmation = rpassword::prompt_password("Repeat passphrase: ")
+        .context("failed to read passphrase from prompt")?;
+    if passphrase != confirmation {
+        return Err(anyhow!("passphrases do not match"));
+    }
+    Ok(SecretString::new(passphrase.into()))
 }
 
 fn handle_project_command(command: ProjectCommand, config: &Config) -> Result<()> {
@@ -498,6 +851,29 @@ fn handle_section_command(command: SectionCommand, config: &Config) -> Result<()
     section::execute_section_command(command, config)
 }
 
+fn run_doctor(config: &Config) -> i32 {
+    struct AsanaCliDoctor<'a>(&'a Config);
+
+    impl DoctorChecks for AsanaCliDoctor<'_> {
+        fn repo_info() -> RepoInfo {
+            RepoInfo::new("tftio", "asana-cli")
+        }
+
+        fn current_version() -> &'static str {
+            VERSION
+        }
+
+        fn tool_checks(&self) -> Vec<DoctorCheck> {
+            crate::doctor::tool_specific_checks_with(self.0)
+        }
+    }
+
+    let tool = AsanaCliDoctor(config);
+    let exit = workhelix_cli_common::doctor::run_doctor(&tool);
+    info!(exit_code = exit, "doctor command completed");
+    exit
+}
+
 fn handle_tag_command(command: TagCommand, config: &Config) -> Result<()> {
     tag::handle_tag_command(command, config)
 }
@@ -0,0 +1,51 @@
+//! Terminal progress bars for attachment transfers.
+
+use crate::api::TransferProgress;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::{
+    io::{IsTerminal, stderr},
+    sync::Arc,
+};
+
+/// Progress bar drawn on stderr while bytes are transferred.
+pub struct TransferBar {
+    bar: ProgressBar,
+}
+
+impl TransferBar {
+    /// Create a bar labelled with `message`, or `None` when stderr is not a
+    /// terminal so scripted runs stay quiet.
+    pub fn new(message: impl Into<String>) -> Option<Arc<Self>> {
+        if !stderr().is_terminal() {
+            return None;
+        }
+        let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+        bar.set_style(
+            ProgressStyle::with_template(
+                "{msg} [{wide_bar}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})",
+            )
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("=> "),
+        );
+        bar.set_message(message.into());
+        Some(Arc::new(Self { bar }))
+    }
+}
+
+impl TransferProgress for TransferBar {
+    fn start(&self, position: u64, total: Option<u64>) {
+        match total {
+            Some(total) => self.bar.set_length(total),
+            None => self.bar.unset_length(),
+        }
+        self.bar.set_position(position);
+    }
+
+    fn advance(&self, bytes: u64) {
+        self.bar.inc(bytes);
+    }
+
+    fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
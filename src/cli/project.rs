@@ -1,6 +1,6 @@
 //! Project CLI command implementations.
 
-use super::build_api_client;
+use super::{build_api_client, mark_if_stale, report_listing};
 use crate::{
     api::{self, ApiClient},
     config::Config,
@@ -12,13 +12,17 @@ use crate::{
     },
     output::{
         ProjectOutputFormat,
-        project::{render_project_detail, render_project_list, render_project_members},
+        project::{
+            ProjectListRenderer, project_detail_fields, project_list_fields, render_project_detail,
+            render_project_members,
+        },
     },
     templates,
 };
 use anyhow::{Context, anyhow, bail};
 use clap::{Args, Subcommand};
 use dialoguer::{Confirm, Input};
+use futures_util::StreamExt;
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::io::{IsTerminal, stdout};
@@ -289,12 +293,14 @@ async fn list_projects_command(
         team: args.team,
         archived: args.archived,
         limit: args.limit,
+        offset: config.start_offset().map(String::from),
+        exact_fields: true,
         ..ProjectListParams::default()
     };
     params.sort = filters::parse_sort(args.sort.as_deref())?;
-    if !args.fields.is_empty() {
-        params.fields.extend(args.fields.into_iter());
-    }
+    let format = determine_output(args.output);
+    params.fields = project_list_fields(format);
+    params.fields.extend(args.fields);
 
     let mut all_filters = filters::parse_filters(&args.filters)?;
     for name in &args.filter_saved {
@@ -311,10 +317,22 @@ async fn list_projects_command(
         );
     }
 
-    let projects = api::list_projects(client, params).await?;
-    let format = determine_output(args.output);
-    let rendered = render_project_list(&projects, format, stdout().is_terminal())?;
-    println!("{rendered}");
+    let listing = api::ListingState::default();
+    let client = client.with_listing_state(&listing);
+    let mut projects = Box::pin(api::list_projects_stream(&client, params));
+    let mut renderer = ProjectListRenderer::new(format, stdout().is_terminal());
+    while let Some(project) = projects.next().await {
+        let project = project?;
+        renderer.note_stale(client.served_stale());
+        if let Some(chunk) = renderer.push(&project)? {
+            print!("{chunk}");
+        }
+    }
+    renderer.note_stale(client.served_stale());
+    if let Some(rendered) = renderer.finish()? {
+        println!("{rendered}");
+    }
+    report_listing(config, &listing);
     Ok(())
 }
 
@@ -324,27 +342,9 @@ async fn show_project_command(
     args: ProjectShowArgs,
 ) -> Result<()> {
     let format = determine_output(args.output);
-    let fields = if args.fields.is_empty() {
-        vec![
-            "gid".to_string(),
-            "name".to_string(),
-            "notes".to_string(),
-            "color".to_string(),
-            "archived".to_string(),
-            "public".to_string(),
-            "workspace.name".to_string(),
-            "workspace.gid".to_string(),
-            "team.name".to_string(),
-            "owner.name".to_string(),
-            "owner.gid".to_string(),
-            "due_on".to_string(),
-            "start_on".to_string(),
-            "created_at".to_string(),
-            "modified_at".to_string(),
-        ]
-    } else {
-        args.fields.clone()
-    };
+    let mut fields = project_detail_fields(format);
+    fields.extend(args.fields.iter().cloned());
+    let fields: Vec<String> = fields.into_iter().collect();
 
     let mut project = if args.by_name {
         let located = find_project_by_name(client, &args.project).await?;
@@ -366,7 +366,10 @@ async fn show_project_command(
         }
     }
 
-    let rendered = render_project_detail(&project, format, stdout().is_terminal())?;
+    let mut rendered = render_project_detail(&project, format, stdout().is_terminal())?;
+    if format == ProjectOutputFormat::Json {
+        rendered = mark_if_stale(client, rendered);
+    }
     println!("{rendered}");
     Ok(())
 }
@@ -529,7 +532,10 @@ async fn project_members_list(
     let project = resolve_project_reference(client, config, &args.target).await?;
     let members = api::list_members(client, &project.gid).await?;
     let format = determine_output(args.output);
-    let rendered = render_project_members(&members.members, format, stdout().is_terminal())?;
+    let mut rendered = render_project_members(&members.members, format, stdout().is_terminal())?;
+    if format == ProjectOutputFormat::Json {
+        rendered = mark_if_stale(client, rendered);
+    }
     println!("{rendered}");
     Ok(())
 }
@@ -1,6 +1,6 @@
 //! Section CLI command implementations.
 
-use super::build_api_client;
+use super::{build_api_client, mark_if_stale};
 use crate::{
     api,
     config::Config,
@@ -109,7 +109,7 @@ async fn list_sections(args: SectionListArgs, config: &Config) -> Result<()> {
 
     match output_format {
         "json" => {
-            let json = serde_json::to_string_pretty(&sections)?;
+            let json = mark_if_stale(&client, crate::output::to_json(&sections)?);
             println!("{json}");
         }
         "csv" => {
@@ -161,7 +161,7 @@ async fn show_section(args: SectionShowArgs, config: &Config) -> Result<()> {
         .unwrap_or(if is_tty { "table" } else { "json" });
 
     if output_format == "json" {
-        let json = serde_json::to_string_pretty(&section)?;
+        let json = mark_if_stale(&client, crate::output::to_json(&section)?);
         println!("{json}");
     } else {
         println!("Section: {}", section.name);
@@ -201,7 +201,7 @@ async fn create_section(args: SectionCreateArgs, config: &Config) -> Result<()>
         .unwrap_or(if is_tty { "table" } else { "json" });
 
     if output_format == "json" {
-        let json = serde_json::to_string_pretty(&section)?;
+        let json = crate::output::to_json(&section)?;
         println!("{json}");
     } else {
         println!("Created section: {}", section.name);
@@ -230,7 +230,7 @@ async fn list_section_tasks(args: SectionTasksArgs, config: &Config) -> Result<(
 
     match output_format {
         "json" => {
-            let json = serde_json::to_string_pretty(&tasks)?;
+            let json = mark_if_stale(&client, crate::output::to_json(&tasks)?);
             println!("{json}");
         }
         "csv" => {
@@ -1,6 +1,6 @@
 //! Tag CLI command implementations.
 
-use super::build_api_client;
+use super::{build_api_client, mark_if_stale, report_listing};
 use crate::{
     api,
     config::Config,
@@ -191,11 +191,14 @@ async fn handle_tag_list(args: TagListArgs, config: &Config) -> Result<()> {
     let params = TagListParams {
         workspace,
         limit: args.limit,
+        offset: config.start_offset().map(String::from),
     };
 
-    let tags = api::list_tags(&client, params)
+    let listing = api::ListingState::default();
+    let tags = api::list_tags(&client.with_listing_state(&listing), params)
         .await
         .context("failed to list tags")?;
+    report_listing(config, &listing);
 
     match args.format {
         TagOutputFormat::Table => render_tag_table(&tags),
@@ -206,8 +209,10 @@ async fn handle_tag_list(args: TagListArgs, config: &Config) -> Result<()> {
             }
         }
         TagOutputFormat::Json => {
-            let json =
-                serde_json::to_string_pretty(&tags).context("failed to serialize tags to JSON")?;
+            let json = mark_if_stale(
+                &client,
+                crate::output::to_json(&tags).context("failed to serialize tags to JSON")?,
+            );
             println!("{json}");
         }
     }
@@ -225,8 +230,10 @@ async fn handle_tag_show(args: TagShowArgs, config: &Config) -> Result<()> {
     match args.format {
         TagOutputFormat::Detail | TagOutputFormat::Table => render_tag_detail(&tag),
         TagOutputFormat::Json => {
-            let json =
-                serde_json::to_string_pretty(&tag).context("failed to serialize tag to JSON")?;
+            let json = mark_if_stale(
+                &client,
+                crate::output::to_json(&tag).context("failed to serialize tag to JSON")?,
+            );
             println!("{json}");
         }
     }
@@ -266,8 +273,7 @@ async fn handle_tag_create(args: TagCreateArgs, config: &Config) -> Result<()> {
             render_tag_detail(&tag);
         }
         TagOutputFormat::Json => {
-            let json =
-                serde_json::to_string_pretty(&tag).context("failed to serialize tag to JSON")?;
+            let json = crate::output::to_json(&tag).context("failed to serialize tag to JSON")?;
             println!("{json}");
         }
     }
@@ -308,8 +314,7 @@ async fn handle_tag_update(args: TagUpdateArgs, config: &Config) -> Result<()> {
             render_tag_detail(&tag);
         }
         TagOutputFormat::Json => {
-            let json =
-                serde_json::to_string_pretty(&tag).context("failed to serialize tag to JSON")?;
+            let json = crate::output::to_json(&tag).context("failed to serialize tag to JSON")?;
             println!("{json}");
         }
     }
@@ -1,8 +1,11 @@
 //! Task CLI command implementations including subtasks and relationship management.
 
-use super::build_api_client;
+use super::{build_api_client, mark_if_stale, progress::TransferBar, report_listing};
 use crate::{
-    api::{self, ApiClient},
+    api::{
+        self, ApiClient, ApiError, DEFAULT_CONCURRENCY, MAX_BATCH_ACTIONS, TaskUpsert,
+        TransferProgress,
+    },
     config::Config,
     error::Result,
     models::{
@@ -13,7 +16,10 @@ use crate::{
     },
     output::{
         TaskOutputFormat,
-        task::{render_task_detail, render_task_list},
+        task::{
+            TaskListRenderer, render_task_detail, render_task_list, task_detail_fields,
+            task_list_fields,
+        },
     },
 };
 use anyhow::{Context, anyhow, bail};
@@ -21,13 +27,14 @@ use chrono::Utc;
 use clap::{Args, Subcommand, ValueEnum};
 use colored::Colorize;
 use dialoguer::{Confirm, Input, theme::ColorfulTheme};
+use futures_util::{StreamExt, pin_mut};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value, json};
 use std::{
-    collections::{BTreeSet, VecDeque},
     fmt::Write as FmtWrite,
     fs,
+    future::Future,
     io::{IsTerminal, stdout},
     path::{Path, PathBuf},
 };
@@ -143,7 +150,8 @@ pub struct TaskListArgs {
 /// Arguments for `task show`.
 #[derive(Args, Debug)]
 pub struct TaskShowArgs {
-    /// Task identifier (gid).
+    /// Task identifier (gid), or `external:<id>` for a task created with
+    /// `--external-id`.
     #[arg(value_name = "TASK")]
     pub task: String,
     /// Additional fields to request from the API.
@@ -202,6 +210,9 @@ pub struct TaskCreateArgs {
     /// Custom field assignments in KEY=VALUE form.
     #[arg(long = "custom-field", value_name = "KEY=VALUE")]
     pub custom_fields: Vec<String>,
+    /// Idempotency key; updates the task already created with this id.
+    #[arg(long = "external-id", value_name = "ID")]
+    pub external_id: Option<String>,
     /// Prompt for missing values interactively.
     #[arg(long)]
     pub interactive: bool,
@@ -333,6 +344,9 @@ pub struct TaskBatchCreateArgs {
     /// Continue processing after an error.
     #[arg(long)]
     pub continue_on_error: bool,
+    /// Maximum number of batch requests in flight at once.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
     /// Output format for created tasks.
     #[arg(long, value_enum)]
     pub output: Option<TaskOutputFormat>,
@@ -350,6 +364,9 @@ pub struct TaskBatchUpdateArgs {
     /// Continue processing after an error.
     #[arg(long)]
     pub continue_on_error: bool,
+    /// Maximum number of batch requests in flight at once.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
     /// Output format for updated tasks.
     #[arg(long, value_enum)]
     pub output: Option<TaskOutputFormat>,
@@ -367,6 +384,9 @@ pub struct TaskBatchCompleteArgs {
     /// Continue processing after an error.
     #[arg(long)]
     pub continue_on_error: bool,
+    /// Maximum number of batch requests in flight at once.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
     /// Output format for resulting tasks.
     #[arg(long, value_enum)]
     pub output: Option<TaskOutputFormat>,
@@ -480,6 +500,7 @@ struct BatchCreateRecord {
     followers: Vec<String>,
     #[serde(default, deserialize_with = "deserialize_map_field")]
     custom_fields: Map<String, Value>,
+    external_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -554,6 +575,9 @@ pub struct TaskSubtasksListArgs {
     /// Traverse subtasks recursively.
     #[arg(long)]
     pub recursive: bool,
+    /// Maximum number of subtask requests in flight at once.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONCURRENCY)]
+    pub concurrency: usize,
     /// Additional fields to request.
     #[arg(long, value_name = "FIELD")]
     pub fields: Vec<String>,
@@ -987,9 +1011,11 @@ pub fn handle_task_command(command: TaskCommand, config: &Config) -> Result<()>
             TaskCommand::Projects { command } => handle_projects_command(&client, command).await,
             TaskCommand::Followers { command } => handle_followers_command(&client, command).await,
             TaskCommand::Tags { command } => handle_tags_command(&client, command).await,
-            TaskCommand::Comments { command } => handle_comments_command(&client, command).await,
+            TaskCommand::Comments { command } => {
+                handle_comments_command(&client, config, command).await
+            }
             TaskCommand::Attachments { command } => {
-                handle_attachments_command(&client, command).await
+                handle_attachments_command(&client, config, command).await
             }
             TaskCommand::MoveToSection(args) => move_to_section_command(&client, args).await,
         }
@@ -1009,6 +1035,8 @@ async fn list_tasks_command(client: &ApiClient, config: &Config, args: TaskListA
         completed: args.completed,
         include_subtasks: args.include_subtasks,
         limit: args.limit,
+        offset: config.start_offset().map(String::from),
+        exact_fields: true,
         sort: parse_sort(args.sort.as_deref())?,
         ..Default::default()
     };
@@ -1019,27 +1047,42 @@ async fn list_tasks_command(client: &ApiClient, config: &Config, args: TaskListA
     if let Some(due_after) = args.due_after.as_ref() {
         params.due_after = Some(parse_date_input(due_after)?);
     }
+    let format = determine_output(args.output);
+    params.fields = task_list_fields(format);
     params.fields.extend(args.fields.iter().cloned());
 
     debug!(?params, "listing tasks with params");
 
-    let tasks = api::list_tasks(client, params).await?;
-    let format = determine_output(args.output);
-    let rendered = render_task_list(&tasks, format, stdout().is_terminal())?;
-    println!("{rendered}");
+    let listing = api::ListingState::default();
+    let client = client.with_listing_state(&listing);
+    let mut tasks = Box::pin(api::list_tasks_stream(&client, params));
+    let mut renderer = TaskListRenderer::new(format, stdout().is_terminal());
+    while let Some(task) = tasks.next().await {
+        let task = task?;
+        renderer.note_stale(client.served_stale());
+        if let Some(chunk) = renderer.push(&task)? {
+            print!("{chunk}");
+        }
+    }
+    renderer.note_stale(client.served_stale());
+    if let Some(rendered) = renderer.finish()? {
+        println!("{rendered}");
+    }
+    report_listing(config, &listing);
     Ok(())
 }
 
 async fn show_task_command(client: &ApiClient, config: &Config, args: TaskShowArgs) -> Result<()> {
     let format = determine_output(args.output);
-    let fields = if args.fields.is_empty() {
-        Vec::new()
-    } else {
-        args.fields.clone()
-    };
+    let mut fields = task_detail_fields(format);
+    fields.extend(args.fields.iter().cloned());
+    let fields = fields.into_iter().collect();
 
-    let task = api::get_task(client, &args.task, fields).await?;
-    let rendered = render_task_detail(&task, format, stdout().is_terminal())?;
+    let task = api::get_task_with_exact_fields(client, &args.task, fields).await?;
+    let mut rendered = render_task_detail(&task, format, stdout().is_terminal())?;
+    if format == TaskOutputFormat::Json {
+        rendered = mark_if_stale(client, rendered);
+    }
     println!("{rendered}");
 
     if matches!(format, TaskOutputFormat::Table | TaskOutputFormat::Markdown) {
@@ -1176,11 +1219,20 @@ async fn create_task_command(
     for (field, value) in parse_custom_field_assignments(&args.custom_fields)? {
         builder = builder.custom_field(field, value);
     }
+    if let Some(external_id) = args.external_id {
+        builder = builder.external_id(external_id);
+    }
 
     let request = builder
         .build()
         .map_err(|err| map_validation_error(&err, "create"))?;
-    let task = api::create_task(client, request).await?;
+    let (task, outcome) = api::create_or_update_task(client, request).await?;
+    if outcome == TaskUpsert::Updated {
+        eprintln!(
+            "Task with this external id already exists; updated {}.",
+            task.gid
+        );
+    }
     let format = determine_output(args.output);
     let rendered = render_task_detail(&task, format, stdout().is_terminal())?;
     println!("{rendered}");
@@ -1309,41 +1361,23 @@ async fn create_batch_command(
         return Ok(());
     }
 
-    let total = records.len();
-    let mut created = Vec::new();
-    for (index, record) in records.into_iter().enumerate() {
-        if stdout().is_terminal() {
-            println!("[{}/{}] creating {}", index + 1, total, record.name);
-        }
-
-        let request = match build_create_request(&record, config) {
-            Ok(request) => request,
-            Err(err) => {
-                if args.continue_on_error {
-                    warn!(index, "failed to build create payload: {err:?}");
-                    continue;
-                }
-                return Err(err);
-            }
-        };
-
-        match api::create_task(client, request).await {
-            Ok(task) => {
-                if let Err(err) = record_recent_task(config, &task) {
-                    warn!(task = %task.gid, "failed to record recent task: {err:?}");
-                }
-                created.push(task);
-            }
-            Err(err) => {
-                let err = anyhow::Error::new(err);
-                if args.continue_on_error {
-                    warn!(index, "batch create failed: {err:?}");
-                    continue;
-                }
-                return Err(err);
-            }
-        }
-    }
+    let chunks = prepare_batch_chunks(
+        &records,
+        args.continue_on_error,
+        |record| record.name.clone(),
+        |record| build_create_request(record, config).context("failed to build create payload"),
+    )?;
+    let run = BatchRun {
+        config,
+        total: records.len(),
+        concurrency: args.concurrency,
+        continue_on_error: args.continue_on_error,
+        verb: "created",
+        failure: "batch create failed",
+    };
+    let created = run
+        .execute(chunks, |requests| api::create_tasks_batch(client, requests))
+        .await?;
 
     if created.is_empty() {
         println!("No tasks created.");
@@ -1368,41 +1402,27 @@ async fn update_batch_command(
         return Ok(());
     }
 
-    let total = records.len();
-    let mut updated = Vec::new();
-    for (index, record) in records.into_iter().enumerate() {
-        if stdout().is_terminal() {
-            println!("[{}/{}] updating {}", index + 1, total, record.task);
-        }
-
-        let request = match build_update_request(&record, config) {
-            Ok(request) => request,
-            Err(err) => {
-                if args.continue_on_error {
-                    warn!(index, "failed to build update payload: {err:?}");
-                    continue;
-                }
-                return Err(err);
-            }
-        };
-
-        match api::update_task(client, &record.task, request).await {
-            Ok(task) => {
-                if let Err(err) = record_recent_task(config, &task) {
-                    warn!(task = %task.gid, "failed to record recent task: {err:?}");
-                }
-                updated.push(task);
-            }
-            Err(err) => {
-                let err = anyhow::Error::new(err);
-                if args.continue_on_error {
-                    warn!(index, "batch update failed: {err:?}");
-                    continue;
-                }
-                return Err(err);
-            }
-        }
-    }
+    let chunks = prepare_batch_chunks(
+        &records,
+        args.continue_on_error,
+        |record| record.task.clone(),
+        |record| {
+            let request =
+                build_update_request(record, config).context("failed to build update payload")?;
+            Ok((record.task.clone(), request))
+        },
+    )?;
+    let run = BatchRun {
+        config,
+        total: records.len(),
+        concurrency: args.concurrency,
+        continue_on_error: args.continue_on_error,
+        verb: "updated",
+        failure: "batch update failed",
+    };
+    let updated = run
+        .execute(chunks, |updates| api::update_tasks_batch(client, updates))
+        .await?;
 
     if updated.is_empty() {
         println!("No tasks updated.");
@@ -1427,45 +1447,183 @@ async fn complete_batch_command(
         return Ok(());
     }
 
-    let total = records.len();
-    let mut completed = Vec::new();
-    for (index, record) in records.into_iter().enumerate() {
-        if stdout().is_terminal() {
-            println!("[{}/{}] completing {}", index + 1, total, record.task);
-        }
+    let chunks = prepare_batch_chunks(
+        &records,
+        false,
+        |record| record.task.clone(),
+        |record| {
+            let request = TaskUpdateBuilder::new()
+                .completed(record.completed)
+                .build()
+                .map_err(|err| map_validation_error(&err, "complete task"))?;
+            Ok((record.task.clone(), request))
+        },
+    )?;
+    let run = BatchRun {
+        config,
+        total: records.len(),
+        concurrency: args.concurrency,
+        continue_on_error: args.continue_on_error,
+        verb: "completed",
+        failure: "batch completion failed",
+    };
+    let completed = run
+        .execute(chunks, |updates| api::update_tasks_batch(client, updates))
+        .await?;
 
-        let request = TaskUpdateBuilder::new()
-            .completed(record.completed)
-            .build()
-            .map_err(|err| map_validation_error(&err, "complete task"))?;
+    if completed.is_empty() {
+        println!("No tasks updated.");
+        return Ok(());
+    }
+
+    let format = determine_output(args.output);
+    let rendered = render_task_list(&completed, format, stdout().is_terminal())?;
+    println!("{rendered}");
+    Ok(())
+}
 
-        match api::update_task(client, &record.task, request).await {
-            Ok(task) => {
-                if let Err(err) = record_recent_task(config, &task) {
-                    warn!(task = %task.gid, "failed to record recent task: {err:?}");
+/// Per-action results of a task `/batch` round trip, or the failure of the request itself.
+type TaskBatchOutcome = std::result::Result<Vec<std::result::Result<Task, ApiError>>, ApiError>;
+
+/// Payloads prepared for one `/batch` round trip.
+struct BatchChunk<R> {
+    indices: Vec<usize>,
+    labels: Vec<String>,
+    requests: Vec<R>,
+}
+
+/// Build request payloads for every record and pack them into `/batch`-sized
+/// chunks. Records whose payload cannot be built are skipped when
+/// `continue_on_error` is set.
+fn prepare_batch_chunks<T, R>(
+    records: &[T],
+    continue_on_error: bool,
+    label: impl Fn(&T) -> String,
+    mut build: impl FnMut(&T) -> Result<R>,
+) -> Result<Vec<BatchChunk<R>>> {
+    let mut chunks: Vec<BatchChunk<R>> = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        let request = match build(record) {
+            Ok(request) => request,
+            Err(err) => {
+                if continue_on_error {
+                    warn!(index, "{err:?}");
+                    continue;
                 }
-                completed.push(task);
+                return Err(err);
             }
+        };
+
+        if chunks
+            .last()
+            .is_none_or(|chunk| chunk.requests.len() >= MAX_BATCH_ACTIONS)
+        {
+            chunks.push(BatchChunk {
+                indices: Vec::new(),
+                labels: Vec::new(),
+                requests: Vec::new(),
+            });
+        }
+        if let Some(chunk) = chunks.last_mut() {
+            chunk.indices.push(index);
+            chunk.labels.push(label(record));
+            chunk.requests.push(request);
+        }
+    }
+    Ok(chunks)
+}
+
+/// Shared driver for the task batch commands.
+struct BatchRun<'a> {
+    config: &'a Config,
+    total: usize,
+    concurrency: usize,
+    continue_on_error: bool,
+    verb: &'static str,
+    failure: &'static str,
+}
+
+impl BatchRun<'_> {
+    /// Submit every chunk with at most `concurrency` batch requests in flight.
+    ///
+    /// Chunks are processed in input order. Without `--continue-on-error` the
+    /// first failure stops the run and abandons chunks not yet submitted.
+    async fn execute<R, F, Fut>(&self, chunks: Vec<BatchChunk<R>>, submit: F) -> Result<Vec<Task>>
+    where
+        R: Send,
+        F: Fn(Vec<R>) -> Fut + Sync,
+        Fut: Future<Output = TaskBatchOutcome> + Send,
+    {
+        let stream = api::bounded(chunks, self.concurrency, |chunk| {
+            let pending = submit(chunk.requests);
+            async move { (chunk.indices, chunk.labels, pending.await) }
+        });
+        pin_mut!(stream);
+
+        let mut output = Vec::new();
+        while let Some((indices, labels, results)) = stream.next().await {
+            self.absorb(&indices, &labels, results, &mut output)?;
+        }
+        Ok(output)
+    }
+
+    /// Fold the outcome of one `/batch` round trip into `output`.
+    ///
+    /// Every successful action is recorded before any failure is surfaced,
+    /// since the whole chunk has already been applied server-side.
+    fn absorb(
+        &self,
+        indices: &[usize],
+        labels: &[String],
+        results: TaskBatchOutcome,
+        output: &mut Vec<Task>,
+    ) -> Result<()> {
+        let results = match results {
+            Ok(results) => results,
             Err(err) => {
                 let err = anyhow::Error::new(err);
-                if args.continue_on_error {
-                    warn!(index, "batch completion failed: {err:?}");
-                    continue;
+                if self.continue_on_error {
+                    warn!(?indices, "{}: {err:?}", self.failure);
+                    return Ok(());
                 }
                 return Err(err);
             }
+        };
+
+        let mut first_error = None;
+        for ((&index, label), result) in indices.iter().zip(labels).zip(results) {
+            match result {
+                Ok(task) => {
+                    self.report(index, self.verb, label);
+                    if let Err(err) = record_recent_task(self.config, &task) {
+                        warn!(task = %task.gid, "failed to record recent task: {err:?}");
+                    }
+                    output.push(task);
+                }
+                Err(err) => {
+                    self.report(index, "failed", label);
+                    let err = anyhow::Error::new(err).context(format!(
+                        "{} for record {}",
+                        self.failure,
+                        index + 1
+                    ));
+                    if self.continue_on_error {
+                        warn!(index, "{err:?}");
+                    } else if first_error.is_none() {
+                        first_error = Some(err);
+                    }
+                }
+            }
         }
-    }
 
-    if completed.is_empty() {
-        println!("No tasks updated.");
-        return Ok(());
+        first_error.map_or(Ok(()), Err)
     }
 
-    let format = determine_output(args.output);
-    let rendered = render_task_list(&completed, format, stdout().is_terminal())?;
-    println!("{rendered}");
-    Ok(())
+    fn report(&self, index: usize, outcome: &str, label: &str) {
+        if stdout().is_terminal() {
+            println!("[{}/{}] {outcome} {label}", index + 1, self.total);
+        }
+    }
 }
 
 async fn search_task_command(
@@ -1495,6 +1653,7 @@ async fn search_task_command(
         .context("workspace is required; provide --workspace or set default_workspace in config")?;
 
     // Build search params
+    let format = determine_output(args.output);
     let params = TaskSearchParams {
         workspace,
         text: args.query.clone(),
@@ -1520,11 +1679,11 @@ async fn search_task_command(
         sort_by: args.sort_by.clone(),
         sort_ascending: args.sort_ascending,
         limit: args.limit,
-        fields: BTreeSet::new(),
+        fields: task_list_fields(format),
     };
 
     // Execute search
-    let tasks = api::search_tasks(client, params).await?;
+    let tasks = api::search_tasks_with_exact_fields(client, params).await?;
 
     if tasks.is_empty() {
         if let Some(ref query) = args.query {
@@ -1543,8 +1702,10 @@ async fn search_task_command(
     }
 
     // Display results
-    let format = determine_output(args.output);
-    let rendered = render_task_list(&tasks, format, stdout().is_terminal())?;
+    let mut rendered = render_task_list(&tasks, format, stdout().is_terminal())?;
+    if format == TaskOutputFormat::Json {
+        rendered = mark_if_stale(client, rendered);
+    }
     println!("{rendered}");
     Ok(())
 }
@@ -1568,7 +1729,7 @@ async fn handle_dependencies_command(
     match command {
         TaskDependencyCommand::List(args) => {
             let refs = api::list_dependencies(client, &args.task).await?;
-            output_task_refs(refs, determine_output(args.output));
+            output_task_refs(client, refs, determine_output(args.output));
             Ok(())
         }
         TaskDependencyCommand::Add(args) => {
@@ -1609,7 +1770,7 @@ async fn handle_dependents_command(
     match command {
         TaskDependentCommand::List(args) => {
             let refs = api::list_dependents(client, &args.task).await?;
-            output_task_refs(refs, determine_output(args.output));
+            output_task_refs(client, refs, determine_output(args.output));
             Ok(())
         }
         TaskDependentCommand::Add(args) => {
@@ -1726,8 +1887,11 @@ async fn handle_tags_command(client: &ApiClient, command: TaskTagCommand) -> Res
                     }
                 }
                 TaskOutputFormat::Json => {
-                    let json = serde_json::to_string_pretty(&task.tags)
-                        .context("failed to serialize tags to JSON")?;
+                    let json = mark_if_stale(
+                        client,
+                        crate::output::to_json(&task.tags)
+                            .context("failed to serialize tags to JSON")?,
+                    );
                     println!("{json}");
                 }
                 _ => {
@@ -1742,14 +1906,21 @@ async fn handle_tags_command(client: &ApiClient, command: TaskTagCommand) -> Res
 }
 
 #[allow(clippy::too_many_lines)]
-async fn handle_comments_command(client: &ApiClient, command: TaskCommentsCommand) -> Result<()> {
+async fn handle_comments_command(
+    client: &ApiClient,
+    config: &Config,
+    command: TaskCommentsCommand,
+) -> Result<()> {
     match command {
         TaskCommentsCommand::List(args) => {
             let params = StoryListParams {
                 task_gid: args.task.clone(),
                 limit: args.limit,
+                offset: config.start_offset().map(String::from),
             };
-            let stories = api::list_stories(client, params).await?;
+            let listing = api::ListingState::default();
+            let stories = api::list_stories(&client.with_listing_state(&listing), params).await?;
+            report_listing(config, &listing);
 
             if stories.is_empty() {
                 println!("No comments on task {}.", args.task);
@@ -1803,8 +1974,11 @@ async fn handle_comments_command(client: &ApiClient, command: TaskCommentsComman
                     }
                 }
                 TaskOutputFormat::Json => {
-                    let json = serde_json::to_string_pretty(&stories)
-                        .context("failed to serialize comments to JSON")?;
+                    let json = mark_if_stale(
+                        client,
+                        crate::output::to_json(&stories)
+                            .context("failed to serialize comments to JSON")?,
+                    );
                     println!("{json}");
                 }
                 _ => {
@@ -1830,7 +2004,7 @@ async fn handle_comments_command(client: &ApiClient, command: TaskCommentsComman
             let story = api::create_story(client, &args.task, request).await?;
 
             if args.format == TaskOutputFormat::Json {
-                let json = serde_json::to_string_pretty(&story)
+                let json = crate::output::to_json(&story)
                     .context("failed to serialize comment to JSON")?;
                 println!("{json}");
             } else {
@@ -1845,8 +2019,11 @@ async fn handle_comments_command(client: &ApiClient, command: TaskCommentsComman
             let story = api::get_story(client, &args.comment).await?;
 
             if args.format == TaskOutputFormat::Json {
-                let json = serde_json::to_string_pretty(&story)
-                    .context("failed to serialize comment to JSON")?;
+                let json = mark_if_stale(
+                    client,
+                    crate::output::to_json(&story)
+                        .context("failed to serialize comment to JSON")?,
+                );
                 println!("{json}");
             } else {
                 let gid = &story.gid;
@@ -1896,7 +2073,7 @@ async fn handle_comments_command(client: &ApiClient, command: TaskCommentsComman
             let story = api::update_story(client, &args.comment, request).await?;
 
             if args.format == TaskOutputFormat::Json {
-                let json = serde_json::to_string_pretty(&story)
+                let json = crate::output::to_json(&story)
                     .context("failed to serialize comment to JSON")?;
                 println!("{json}");
             } else {
@@ -1928,6 +2105,7 @@ async fn handle_comments_command(client: &ApiClient, command: TaskCommentsComman
 #[allow(clippy::too_many_lines)]
 async fn handle_attachments_command(
     client: &ApiClient,
+    config: &Config,
     command: TaskAttachmentsCommand,
 ) -> Result<()> {
     match command {
@@ -1935,8 +2113,12 @@ async fn handle_attachments_command(
             let params = AttachmentListParams {
                 task_gid: args.task.clone(),
                 limit: args.limit,
+                offset: config.start_offset().map(String::from),
             };
-            let attachments = api::list_attachments(client, params).await?;
+            let listing = api::ListingState::default();
+            let attachments =
+                api::list_attachments(&client.with_listing_state(&listing), params).await?;
+            report_listing(config, &listing);
 
             if attachments.is_empty() {
                 println!("No attachments on task {}.", args.task);
@@ -1978,8 +2160,11 @@ async fn handle_attachments_command(
                     }
                 }
                 TaskOutputFormat::Json => {
-                    let json = serde_json::to_string_pretty(&attachments)
-                        .context("failed to serialize attachments to JSON")?;
+                    let json = mark_if_stale(
+                        client,
+                        crate::output::to_json(&attachments)
+                            .context("failed to serialize attachments to JSON")?,
+                    );
                     println!("{json}");
                 }
                 _ => {
@@ -1998,10 +2183,12 @@ async fn handle_attachments_command(
             };
 
             println!("Uploading {}...", args.file.display());
-            let attachment = api::upload_attachment(client, params).await?;
+            let progress = TransferBar::new("Uploading")
+                .map(|bar| bar as std::sync::Arc<dyn TransferProgress>);
+            let attachment = api::upload_attachment_with_progress(client, params, progress).await?;
 
             if args.format == TaskOutputFormat::Json {
-                let json = serde_json::to_string_pretty(&attachment)
+                let json = crate::output::to_json(&attachment)
                     .context("failed to serialize attachment to JSON")?;
                 println!("{json}");
             } else {
@@ -2018,7 +2205,14 @@ async fn handle_attachments_command(
         }
         TaskAttachmentsCommand::Download(args) => {
             println!("Downloading attachment {}...", args.attachment);
-            api::download_attachment(client, &args.attachment, &args.output).await?;
+            let progress = TransferBar::new("Downloading");
+            api::download_attachment_with_progress(
+                client,
+                &args.attachment,
+                &args.output,
+                progress.as_deref().map(|bar| bar as &dyn TransferProgress),
+            )
+            .await?;
             println!("Downloaded to {}", args.output.display());
             Ok(())
         }
@@ -2026,8 +2220,11 @@ async fn handle_attachments_command(
             let attachment = api::get_attachment(client, &args.attachment).await?;
 
             if args.format == TaskOutputFormat::Json {
-                let json = serde_json::to_string_pretty(&attachment)
-                    .context("failed to serialize attachment to JSON")?;
+                let json = mark_if_stale(
+                    client,
+                    crate::output::to_json(&attachment)
+                        .context("failed to serialize attachment to JSON")?,
+                );
                 println!("{json}");
             } else {
                 let gid = &attachment.gid;
@@ -2073,7 +2270,7 @@ async fn handle_attachments_command(
 }
 
 #[allow(clippy::cast_precision_loss)]
-fn format_bytes(bytes: u64) -> String {
+pub(super) fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -2103,14 +2300,24 @@ async fn move_to_section_command(client: &ApiClient, args: TaskMoveToSectionArgs
 }
 
 async fn subtasks_list_command(client: &ApiClient, args: TaskSubtasksListArgs) -> Result<()> {
-    let fields = args.fields.clone();
-    let entries = collect_subtasks(client, &args.task, args.recursive, 0, &fields).await?;
+    let format = determine_output(args.output);
+    let mut fields = task_list_fields(format);
+    fields.extend(args.fields.iter().cloned());
+    let fields: Vec<String> = fields.into_iter().collect();
+    let entries = collect_subtasks(
+        client,
+        &args.task,
+        args.recursive,
+        0,
+        &fields,
+        args.concurrency,
+    )
+    .await?;
     if entries.is_empty() {
         println!("No subtasks found.");
         return Ok(());
     }
 
-    let format = determine_output(args.output);
     match format {
         TaskOutputFormat::Json => {
             let payload: Vec<_> = entries
@@ -2122,7 +2329,10 @@ async fn subtasks_list_command(client: &ApiClient, args: TaskSubtasksListArgs) -
                     })
                 })
                 .collect();
-            println!("{}", serde_json::to_string_pretty(&payload)?);
+            println!(
+                "{}",
+                mark_if_stale(client, crate::output::to_json(&payload)?)
+            );
         }
         TaskOutputFormat::Csv => {
             let tasks = tasks_with_indent(&entries);
@@ -2247,22 +2457,31 @@ async fn collect_subtasks(
     recursive: bool,
     depth: usize,
     fields: &[String],
+    concurrency: usize,
 ) -> Result<Vec<(usize, Task)>> {
     let mut results = Vec::new();
-    let mut queue: VecDeque<(String, usize)> = VecDeque::new();
-    queue.push_back((task_gid.to_string(), depth));
-
-    while let Some((parent_gid, level)) = queue.pop_front() {
-        let subtasks = api::list_subtasks(client, &parent_gid, fields.to_vec())
-            .await
-            .map_err(|err| anyhow!(err))?;
-        for task in subtasks {
-            let gid = task.gid.clone();
-            results.push((level, task));
-            if recursive {
-                queue.push_back((gid, level + 1));
+    let mut frontier = vec![task_gid.to_string()];
+    let mut level = depth;
+
+    // Walk the tree one level at a time so siblings are fetched concurrently
+    // while the output keeps breadth-first order.
+    while !frontier.is_empty() {
+        let pages = api::map_bounded(frontier, concurrency, |parent_gid| async move {
+            api::list_subtasks(client, &parent_gid, fields.to_vec()).await
+        })
+        .await;
+
+        let mut next = Vec::new();
+        for page in pages {
+            for task in page.map_err(|err| anyhow!(err))? {
+                if recursive {
+                    next.push(task.gid.clone());
+                }
+                results.push((level, task));
             }
         }
+        frontier = next;
+        level += 1;
     }
 
     Ok(results)
@@ -2294,14 +2513,17 @@ fn render_subtask_tree(entries: &[(usize, Task)]) -> String {
     }
 }
 
-fn output_task_refs(refs: Vec<TaskReference>, format: TaskOutputFormat) {
+fn output_task_refs(client: &ApiClient, refs: Vec<TaskReference>, format: TaskOutputFormat) {
     if refs.is_empty() {
         println!("None.");
         return;
     }
     match format {
         TaskOutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&refs).unwrap());
+            println!(
+                "{}",
+                mark_if_stale(client, crate::output::to_json(&refs).unwrap())
+            );
         }
         TaskOutputFormat::Csv => {
             let mut wtr = csv::Writer::from_writer(vec![]);
@@ -2428,7 +2650,11 @@ fn map_validation_error(err: &TaskValidationError, context: &str) -> anyhow::Err
     }
 }
 
-fn resolve_assignee(input: Option<String>, config: &Config, fallback_me: bool) -> Option<String> {
+pub(super) fn resolve_assignee(
+    input: Option<String>,
+    config: &Config,
+    fallback_me: bool,
+) -> Option<String> {
     input.map_or_else(
         || {
             config
@@ -2545,6 +2771,7 @@ fn blank_task(gid: String, name: String) -> Task {
         attachments: Vec::new(),
         permalink_url: None,
         num_subtasks: None,
+        external: None,
     }
 }
 
@@ -2759,6 +2986,9 @@ fn build_create_request(record: &BatchCreateRecord, config: &Config) -> Result<T
     for (field, value) in &record.custom_fields {
         builder = builder.custom_field(field.clone(), to_custom_field_value(value.clone()));
     }
+    if let Some(external_id) = record.external_id.as_ref().filter(|id| !id.is_empty()) {
+        builder = builder.external_id(external_id.clone());
+    }
 
     builder
         .build()
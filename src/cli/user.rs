@@ -1,10 +1,11 @@
 //! User CLI command implementations.
 
-use super::build_api_client;
-use crate::{api, config::Config, error::Result, models::User};
+use super::{build_api_client, mark_if_stale, report_listing};
+use crate::{api, config::Config, error::Result, models::User, output::stream::JsonArrayWriter};
 use anyhow::Context;
 use clap::{Args, Subcommand, ValueEnum};
 use colored::Colorize;
+use futures_util::{StreamExt, pin_mut};
 use std::io::{IsTerminal, stdout};
 use tokio::runtime::Builder as RuntimeBuilder;
 
@@ -97,54 +98,65 @@ async fn list_users_command(
     let params = crate::models::UserListParams {
         workspace_gid: workspace_gid.to_string(),
         limit: args.limit,
+        offset: config.start_offset().map(String::from),
     };
 
-    let users = api::list_users(client, params).await?;
-
-    if users.is_empty() {
-        println!("No users found in workspace {workspace_gid}.");
-        return Ok(());
-    }
-
-    match args.format {
-        UserOutputFormat::Table => {
-            if stdout().is_terminal() {
-                println!(
-                    "{:<20} {:<30} {}",
-                    "GID".bold(),
-                    "Name".bold(),
-                    "Email".bold()
-                );
-                println!("{}", "─".repeat(80));
+    let listing = api::ListingState::default();
+    let client = client.with_listing_state(&listing);
+    let users = api::list_users_stream(&client, params);
+    pin_mut!(users);
+
+    let is_tty = stdout().is_terminal();
+    let mut json = JsonArrayWriter::default();
+    let mut count = 0usize;
+    while let Some(user) = users.next().await {
+        let user = user?;
+        match args.format {
+            UserOutputFormat::Json => {
+                json.note_stale(client.served_stale());
+                let element = json
+                    .push(&user)
+                    .context("failed to serialize users to JSON")?;
+                print!("{element}");
             }
-            for user in &users {
+            UserOutputFormat::Table => {
+                if is_tty && count == 0 {
+                    println!(
+                        "{:<20} {:<30} {}",
+                        "GID".bold(),
+                        "Name".bold(),
+                        "Email".bold()
+                    );
+                    println!("{}", "─".repeat(80));
+                }
                 let email = user.email.as_deref().unwrap_or("N/A");
-
-                if stdout().is_terminal() {
+                if is_tty {
                     println!("{:<20} {:<30} {}", user.gid, user.name, email);
                 } else {
                     println!("{}\t{}\t{}", user.gid, user.name, email);
                 }
             }
-            if stdout().is_terminal() {
-                println!("\n{} users listed.", users.len());
-            }
-        }
-        UserOutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&users)
-                .context("failed to serialize users to JSON")?;
-            println!("{json}");
-        }
-        UserOutputFormat::Detail => {
-            for (i, user) in users.iter().enumerate() {
-                if i > 0 {
+            UserOutputFormat::Detail => {
+                if count > 0 {
                     println!();
                 }
-                print_user_detail(user);
+                print_user_detail(&user);
             }
         }
+        count += 1;
+    }
+
+    if count == 0 {
+        println!("No users found in workspace {workspace_gid}.");
+    } else if args.format == UserOutputFormat::Json {
+        json.note_stale(client.served_stale());
+        let end = json.finish().context("failed to serialize users to JSON")?;
+        println!("{end}");
+    } else if is_tty && args.format == UserOutputFormat::Table {
+        println!("\n{count} users listed.");
     }
 
+    report_listing(config, &listing);
     Ok(())
 }
 
@@ -152,8 +164,10 @@ async fn show_user_command(client: &api::ApiClient, args: UserShowArgs) -> Resul
     let user = api::get_user(client, &args.gid).await?;
 
     if args.format == UserOutputFormat::Json {
-        let json =
-            serde_json::to_string_pretty(&user).context("failed to serialize user to JSON")?;
+        let json = mark_if_stale(
+            client,
+            crate::output::to_json(&user).context("failed to serialize user to JSON")?,
+        );
         println!("{json}");
     } else {
         print_user_detail(&user);
@@ -166,8 +180,10 @@ async fn show_current_user_command(client: &api::ApiClient, args: UserMeArgs) ->
     let user = api::get_current_user(client).await?;
 
     if args.format == UserOutputFormat::Json {
-        let json =
-            serde_json::to_string_pretty(&user).context("failed to serialize user to JSON")?;
+        let json = mark_if_stale(
+            client,
+            crate::output::to_json(&user).context("failed to serialize user to JSON")?,
+        );
         println!("{json}");
     } else {
         print_user_detail(&user);
@@ -0,0 +1,158 @@
+//! `watch` command: tail changes through the Events API.
+
+use super::build_api_client;
+use crate::{
+    api::{self, ApiClient, SyncTokenStore},
+    config::Config,
+    error::Result,
+    models::{Event, EventResource},
+};
+use anyhow::Context;
+use clap::{ArgGroup, Args, ValueEnum};
+use std::{fmt::Write as _, time::Duration};
+use tokio::runtime::Builder as RuntimeBuilder;
+
+/// Arguments for `watch`.
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("resource").required(true).args(["project", "task", "tag"])))]
+pub struct WatchArgs {
+    /// Watch a project and the tasks in it.
+    #[arg(long, value_name = "GID", value_parser = parse_gid)]
+    pub project: Option<String>,
+    /// Watch a single task.
+    #[arg(long, value_name = "GID", value_parser = parse_gid)]
+    pub task: Option<String>,
+    /// Watch a tag.
+    #[arg(long, value_name = "GID", value_parser = parse_gid)]
+    pub tag: Option<String>,
+    /// Seconds to wait between polls once caught up.
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    pub interval: u64,
+    /// Print the events available now and exit instead of polling.
+    #[arg(long)]
+    pub once: bool,
+    /// Discard the persisted sync token and start from the current state.
+    #[arg(long)]
+    pub reset: bool,
+    /// Output format.
+    #[arg(long, value_enum, default_value = "human")]
+    pub format: WatchOutputFormat,
+}
+
+/// Resource gids name the sync token files, so only digits are accepted.
+fn parse_gid(value: &str) -> std::result::Result<String, String> {
+    if api::events::is_gid(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("`{value}` is not a resource gid"))
+    }
+}
+
+/// Output format choices for `watch`.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum WatchOutputFormat {
+    /// One readable line per change.
+    Human,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+/// Execute the `watch` command until interrupted (or once with `--once`).
+///
+/// # Errors
+/// Returns an error when the events cannot be fetched or the sync token
+/// cannot be persisted.
+pub fn handle_watch_command(args: WatchArgs, config: &Config) -> Result<()> {
+    let client = build_api_client(config)?;
+    let store = SyncTokenStore::new(&config.sync_dir());
+
+    let runtime = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to initialise async runtime")?;
+
+    runtime.block_on(watch(&client, &store, args))
+}
+
+async fn watch(client: &ApiClient, store: &SyncTokenStore, args: WatchArgs) -> Result<()> {
+    let (kind, resource) = match (&args.project, &args.task, &args.tag) {
+        (Some(gid), _, _) => ("project", gid.as_str()),
+        (_, Some(gid), _) => ("task", gid.as_str()),
+        (_, _, Some(gid)) => ("tag", gid.as_str()),
+        (None, None, None) => unreachable!("clap requires one resource"),
+    };
+    if args.reset {
+        store.clear(resource).await?;
+    }
+    let mut sync = store.load(resource).await?;
+    if sync.is_none() {
+        eprintln!(
+            "Starting a new event stream for {kind} {resource}; earlier changes are not shown."
+        );
+    }
+
+    loop {
+        let batch = api::get_events(client, resource, sync.as_deref())
+            .await
+            .with_context(|| format!("failed to fetch events for {kind} {resource}"))?;
+        if batch.resynced {
+            eprintln!(
+                "warning: the sync token for {kind} {resource} expired; changes since the last \
+                 run were missed"
+            );
+        }
+        for event in &batch.events {
+            match args.format {
+                WatchOutputFormat::Human => println!("{}", format_event(event)),
+                WatchOutputFormat::Ndjson => println!(
+                    "{}",
+                    serde_json::to_string(event).context("failed to serialize event")?
+                ),
+            }
+        }
+        store.save(resource, &batch.sync).await?;
+        sync = Some(batch.sync);
+
+        if batch.has_more {
+            continue;
+        }
+        if args.once {
+            return Ok(());
+        }
+        tokio::select! {
+            () = tokio::time::sleep(Duration::from_secs(args.interval)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+fn format_event(event: &Event) -> String {
+    let mut line = format!(
+        "{}  {:<9} {}",
+        event.created_at.as_deref().unwrap_or("-"),
+        event.action,
+        describe(&event.resource)
+    );
+    if let Some(change) = &event.change {
+        let _ = write!(line, "  {} {}", change.action, change.field);
+    }
+    if let Some(parent) = &event.parent {
+        let _ = write!(line, "  in {}", describe(parent));
+    }
+    if let Some(user) = &event.user {
+        let _ = write!(line, "  by {}", user.name.as_deref().unwrap_or(&user.gid));
+    }
+    line
+}
+
+fn describe(resource: &EventResource) -> String {
+    let kind = resource
+        .resource_subtype
+        .as_deref()
+        .or(resource.resource_type.as_deref())
+        .unwrap_or("resource");
+    resource.name.as_ref().map_or_else(
+        || format!("{kind} {}", resource.gid),
+        |name| format!("{kind} {} \"{name}\"", resource.gid),
+    )
+}
@@ -0,0 +1,369 @@
+//! Webhook CLI command implementations.
+
+use super::{build_api_client, mark_if_stale, report_listing};
+use crate::{
+    api::{self, ApiClient, WebhookRequest, WebhookSecretStore, auth::shell_command},
+    config::Config,
+    error::Result,
+    models::{Event, Webhook, WebhookCreateData, WebhookCreateRequest, WebhookListParams},
+};
+use anyhow::{Context, anyhow};
+use clap::{Args, Subcommand, ValueEnum};
+use colored::Colorize;
+use std::{
+    io::{IsTerminal, stdout},
+    process::Stdio,
+    time::Duration,
+};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+    runtime::Builder as RuntimeBuilder,
+    time::timeout,
+};
+
+/// How long a client may take to send a complete request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Primary `webhook` subcommands.
+#[derive(Subcommand, Debug)]
+pub enum WebhookCommand {
+    /// Subscribe a target URL to changes on a resource.
+    Create(WebhookCreateArgs),
+    /// List webhooks in a workspace.
+    List(WebhookListArgs),
+    /// Delete a webhook and forget its stored secret.
+    Delete(WebhookDeleteArgs),
+    /// Receive webhook deliveries, verifying their signatures.
+    Serve(WebhookServeArgs),
+}
+
+/// Arguments for `webhook create`.
+#[derive(Args, Debug)]
+pub struct WebhookCreateArgs {
+    /// Resource (project, task, tag, ...) to subscribe to.
+    #[arg(long, value_name = "GID")]
+    pub resource: String,
+    /// Public URL forwarding to `webhook serve`; use a distinct path per
+    /// webhook so each keeps its own secret.
+    #[arg(long, value_name = "URL")]
+    pub target: String,
+    /// Output format.
+    #[arg(long, value_enum, default_value = "detail")]
+    pub format: WebhookOutputFormat,
+}
+
+/// Arguments for `webhook list`.
+#[derive(Args, Debug)]
+pub struct WebhookListArgs {
+    /// Workspace identifier (defaults to the configured workspace).
+    #[arg(long)]
+    pub workspace: Option<String>,
+    /// Only list webhooks for this resource.
+    #[arg(long, value_name = "GID")]
+    pub resource: Option<String>,
+    /// Maximum number of webhooks to retrieve.
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Output format.
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: WebhookOutputFormat,
+}
+
+/// Arguments for `webhook delete`.
+#[derive(Args, Debug)]
+pub struct WebhookDeleteArgs {
+    /// Webhook identifier.
+    pub gid: String,
+    /// Skip confirmation prompt.
+    #[arg(long)]
+    pub yes: bool,
+}
+
+/// Arguments for `webhook serve`.
+#[derive(Args, Debug)]
+pub struct WebhookServeArgs {
+    /// Port to listen on.
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+    /// Address to bind; expose it through a tunnel or reverse proxy.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+    /// Shell command receiving each verified event as JSON on stdin, instead
+    /// of printing NDJSON to stdout.
+    #[arg(long)]
+    pub command: Option<String>,
+}
+
+/// Output format choices for webhook commands.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum WebhookOutputFormat {
+    /// Tabular listing.
+    Table,
+    /// Detailed view.
+    Detail,
+    /// JSON output.
+    Json,
+}
+
+/// Execute webhook subcommands.
+///
+/// # Errors
+/// Returns an error when an API request fails, the listener cannot be bound,
+/// or stored secrets cannot be updated.
+pub fn handle_webhook_command(command: WebhookCommand, config: &Config) -> Result<()> {
+    let runtime = RuntimeBuilder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to initialise async runtime")?;
+    let store = WebhookSecretStore::new(&config.webhook_secrets_path());
+
+    runtime.block_on(async move {
+        match command {
+            WebhookCommand::Create(args) => {
+                handle_webhook_create(&build_api_client(config)?, &store, args).await
+            }
+            WebhookCommand::List(args) => {
+                handle_webhook_list(&build_api_client(config)?, config, args).await
+            }
+            WebhookCommand::Delete(args) => {
+                handle_webhook_delete(&build_api_client(config)?, &store, args).await
+            }
+            WebhookCommand::Serve(args) => serve(&store, args).await,
+        }
+    })
+}
+
+async fn handle_webhook_create(
+    client: &ApiClient,
+    store: &WebhookSecretStore,
+    args: WebhookCreateArgs,
+) -> Result<()> {
+    let path = api::webhooks::target_path(&args.target);
+    store.expect_handshake(&path).await?;
+    let request = WebhookCreateRequest {
+        data: WebhookCreateData {
+            resource: args.resource,
+            target: args.target,
+        },
+    };
+    let created = api::create_webhook(client, request).await;
+    store.cancel_handshake(&path).await?;
+    let webhook =
+        created.context("failed to create webhook; is `webhook serve` reachable at the target?")?;
+    render_webhooks(client, &[webhook], args.format)
+}
+
+async fn handle_webhook_list(
+    client: &ApiClient,
+    config: &Config,
+    args: WebhookListArgs,
+) -> Result<()> {
+    let workspace = args
+        .workspace
+        .or_else(|| config.default_workspace().map(String::from))
+        .ok_or_else(|| anyhow!("workspace is required; provide --workspace or set a default"))?;
+    let params = WebhookListParams {
+        workspace,
+        resource: args.resource,
+        limit: args.limit,
+        offset: config.start_offset().map(String::from),
+    };
+    let listing = api::ListingState::default();
+    let webhooks = api::list_webhooks(&client.with_listing_state(&listing), params)
+        .await
+        .context("failed to list webhooks")?;
+    report_listing(config, &listing);
+    render_webhooks(client, &webhooks, args.format)
+}
+
+async fn handle_webhook_delete(
+    client: &ApiClient,
+    store: &WebhookSecretStore,
+    args: WebhookDeleteArgs,
+) -> Result<()> {
+    let webhook = api::get_webhook(client, &args.gid)
+        .await
+        .context("failed to retrieve webhook")?;
+    if !args.yes {
+        println!("{}", "Webhook to be deleted:".yellow().bold());
+        render_webhook_detail(&webhook);
+        if !confirm_deletion()? {
+            println!("Deletion cancelled.");
+            return Ok(());
+        }
+    }
+
+    api::delete_webhook(client, &args.gid)
+        .await
+        .context("failed to delete webhook")?;
+    if let Some(target) = &webhook.target {
+        store.remove(&api::webhooks::target_path(target)).await?;
+    }
+    println!("{}", "Webhook deleted successfully.".green().bold());
+    Ok(())
+}
+
+async fn serve(store: &WebhookSecretStore, args: WebhookServeArgs) -> Result<()> {
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .await
+        .with_context(|| format!("failed to listen on {}:{}", args.host, args.port))?;
+    eprintln!(
+        "Listening for Asana webhooks on http://{}",
+        listener.local_addr()?
+    );
+
+    loop {
+        let (socket, _) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        let store = store.clone();
+        let command = args.command.clone();
+        tokio::spawn(async move { handle_connection(socket, &store, command.as_deref()).await });
+    }
+}
+
+/// Answer one connection and hand its events on. Failures are logged so one
+/// bad request or event never stops the receiver.
+async fn handle_connection(socket: TcpStream, store: &WebhookSecretStore, command: Option<&str>) {
+    let handled = timeout(REQUEST_TIMEOUT, api::handle_webhook_request(socket, store)).await;
+    match handled {
+        Ok(Ok(WebhookRequest::Handshake { path })) => {
+            eprintln!("Handshake completed for {path}; secret stored.");
+        }
+        Ok(Ok(WebhookRequest::Rejected { path, reason })) => {
+            eprintln!("warning: rejected request to {path}: {reason}");
+        }
+        Ok(Ok(WebhookRequest::Delivery { path, events })) => {
+            for event in &events {
+                if let Err(err) = deliver(event, &path, command).await {
+                    eprintln!("warning: failed to deliver event from {path}: {err:#}");
+                }
+            }
+        }
+        Ok(Err(err)) => eprintln!("warning: failed to handle webhook request: {err}"),
+        Err(_) => eprintln!(
+            "warning: dropped a webhook request not received within {}s",
+            REQUEST_TIMEOUT.as_secs()
+        ),
+    }
+}
+
+async fn deliver(event: &Event, path: &str, command: Option<&str>) -> Result<()> {
+    let json = serde_json::to_string(event).context("failed to serialize event")?;
+    let Some(command) = command else {
+        println!("{json}");
+        return Ok(());
+    };
+
+    let mut child = shell_command(command)
+        .env("ASANA_WEBHOOK_PATH", path)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run webhook command `{command}`"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(json.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        eprintln!("warning: webhook command `{command}` exited with {status}");
+    }
+    Ok(())
+}
+
+fn render_webhooks(
+    client: &ApiClient,
+    webhooks: &[Webhook],
+    format: WebhookOutputFormat,
+) -> Result<()> {
+    match format {
+        WebhookOutputFormat::Json => {
+            let json = mark_if_stale(
+                client,
+                crate::output::to_json(&webhooks)
+                    .context("failed to serialize webhooks to JSON")?,
+            );
+            println!("{json}");
+        }
+        WebhookOutputFormat::Detail => {
+            for webhook in webhooks {
+                render_webhook_detail(webhook);
+            }
+        }
+        WebhookOutputFormat::Table => render_webhook_table(webhooks),
+    }
+    Ok(())
+}
+
+fn render_webhook_table(webhooks: &[Webhook]) {
+    if webhooks.is_empty() {
+        println!("No webhooks found.");
+        return;
+    }
+
+    let is_tty = stdout().is_terminal();
+    if is_tty {
+        println!(
+            "{:<20} {:<8} {:<20} {}",
+            "GID".bold(),
+            "Active".bold(),
+            "Resource".bold(),
+            "Target".bold()
+        );
+        println!("{}", "─".repeat(80));
+    }
+    for webhook in webhooks {
+        let resource = webhook
+            .resource
+            .as_ref()
+            .map_or("unknown", |resource| resource.gid.as_str());
+        let target = webhook.target.as_deref().unwrap_or("unknown");
+        if is_tty {
+            println!(
+                "{:<20} {:<8} {:<20} {}",
+                webhook.gid, webhook.active, resource, target
+            );
+        } else {
+            println!(
+                "{}\t{}\t{}\t{}",
+                webhook.gid, webhook.active, resource, target
+            );
+        }
+    }
+}
+
+fn render_webhook_detail(webhook: &Webhook) {
+    println!("  GID: {}", webhook.gid);
+    println!("  Active: {}", webhook.active);
+    if let Some(resource) = &webhook.resource {
+        let name = resource.name.as_deref().unwrap_or("");
+        println!("  Resource: {} {name}", resource.gid);
+    }
+    if let Some(target) = &webhook.target {
+        println!("  Target: {target}");
+    }
+    if let Some(at) = &webhook.last_success_at {
+        println!("  Last success: {at}");
+    }
+    if let Some(at) = &webhook.last_failure_at {
+        let content = webhook.last_failure_content.as_deref().unwrap_or("");
+        println!("  Last failure: {at} {content}");
+    }
+}
+
+fn confirm_deletion() -> Result<bool> {
+    use std::io::{self, Write};
+
+    print!("Are you sure you want to delete this webhook? [y/N] ");
+    io::stdout().flush().context("failed to flush stdout")?;
+
+    let mut response = String::new();
+    io::stdin()
+        .read_line(&mut response)
+        .context("failed to read user input")?;
+
+    let response = response.trim().to_lowercase();
+    Ok(response == "y" || response == "yes")
+}
@@ -1,6 +1,6 @@
 //! Workspace CLI command implementations.
 
-use super::build_api_client;
+use super::{build_api_client, mark_if_stale, report_listing};
 use crate::{api, config::Config, error::Result, models::Workspace};
 use anyhow::Context;
 use clap::{Args, Subcommand, ValueEnum};
@@ -63,16 +63,25 @@ pub fn handle_workspace_command(command: WorkspaceCommand, config: &Config) -> R
 
     runtime.block_on(async move {
         match command {
-            WorkspaceCommand::List(args) => list_workspaces_command(&client, args).await,
+            WorkspaceCommand::List(args) => list_workspaces_command(&client, config, args).await,
             WorkspaceCommand::Show(args) => show_workspace_command(&client, args).await,
         }
     })
 }
 
-async fn list_workspaces_command(client: &api::ApiClient, args: WorkspaceListArgs) -> Result<()> {
-    let params = crate::models::WorkspaceListParams { limit: args.limit };
+async fn list_workspaces_command(
+    client: &api::ApiClient,
+    config: &Config,
+    args: WorkspaceListArgs,
+) -> Result<()> {
+    let params = crate::models::WorkspaceListParams {
+        limit: args.limit,
+        offset: config.start_offset().map(String::from),
+    };
 
-    let workspaces = api::list_workspaces(client, params).await?;
+    let listing = api::ListingState::default();
+    let workspaces = api::list_workspaces(&client.with_listing_state(&listing), params).await?;
+    report_listing(config, &listing);
 
     if workspaces.is_empty() {
         println!("No workspaces found.");
@@ -111,8 +120,11 @@ async fn list_workspaces_command(client: &api::ApiClient, args: WorkspaceListArg
             }
         }
         WorkspaceOutputFormat::Json => {
-            let json = serde_json::to_string_pretty(&workspaces)
-                .context("failed to serialize workspaces to JSON")?;
+            let json = mark_if_stale(
+                client,
+                crate::output::to_json(&workspaces)
+                    .context("failed to serialize workspaces to JSON")?,
+            );
             println!("{json}");
         }
         WorkspaceOutputFormat::Detail => {
@@ -132,8 +144,10 @@ async fn show_workspace_command(client: &api::ApiClient, args: WorkspaceShowArgs
     let workspace = api::get_workspace(client, &args.gid).await?;
 
     if args.format == WorkspaceOutputFormat::Json {
-        let json = serde_json::to_string_pretty(&workspace)
-            .context("failed to serialize workspace to JSON")?;
+        let json = mark_if_stale(
+            client,
+            crate::output::to_json(&workspace).context("failed to serialize workspace to JSON")?,
+        );
         println!("{json}");
     } else {
         print_workspace_detail(&workspace);
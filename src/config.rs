@@ -3,15 +3,20 @@
 //! Phase 1 establishes the persistent configuration surface and token storage.
 //! Subsequent phases will expand the persisted settings and runtime validation.
 
+use crate::api::{CassetteMode, NetworkOptions};
 use crate::error::Result;
+use crate::secrets::{SealedToken, UnlockCache};
 use anyhow::{Context, anyhow};
 use directories::ProjectDirs;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::Duration;
 use tracing::debug;
 
 #[cfg(unix)]
@@ -22,8 +27,12 @@ const ENV_BASE_URL: &str = "ASANA_BASE_URL";
 const ENV_WORKSPACE: &str = "ASANA_WORKSPACE";
 const ENV_ASSIGNEE: &str = "ASANA_ASSIGNEE";
 const ENV_PROJECT: &str = "ASANA_PROJECT";
+const ENV_OFFLINE: &str = "ASANA_OFFLINE";
 const ENV_CONFIG_HOME: &str = "ASANA_CLI_CONFIG_HOME";
 const ENV_DATA_HOME: &str = "ASANA_CLI_DATA_HOME";
+const ENV_PROFILE: &str = "ASANA_PROFILE";
+/// Name of the profile stored at the top level of the configuration file.
+pub const DEFAULT_PROFILE: &str = "default";
 /// Default Asana API base URL when no override is provided.
 pub const DEFAULT_API_BASE_URL: &str = "https://app.asana.com/api/1.0";
 
@@ -41,6 +50,73 @@ pub struct FileConfig {
     pub default_project: Option<String>,
     /// Stored Personal Access Token (if persisted on disk).
     pub personal_access_token: Option<String>,
+    /// Personal Access Token encrypted with a passphrase (see `config set
+    /// token --encrypt`).
+    pub encrypted_token: Option<String>,
+    /// Minutes an unlocked encrypted token stays usable without the
+    /// passphrase; unset or `0` asks every time.
+    pub token_unlock_minutes: Option<u64>,
+    /// Shell command whose stdout is the token, like git's credential helpers.
+    pub token_command: Option<String>,
+    /// Hours that expired cache entries are kept for offline or fallback reads.
+    pub cache_stale_max_age_hours: Option<u64>,
+    /// Serve stale cache entries when Asana cannot be reached.
+    pub cache_stale_fallback: Option<bool>,
+    /// Disk cache size cap in MiB; `0` disables eviction.
+    pub cache_max_size_mb: Option<u64>,
+    /// PEM file with extra CA certificates to trust, for TLS-intercepting
+    /// proxies.
+    pub ca_bundle: Option<PathBuf>,
+    /// PEM file with a client certificate and private key for mutual TLS.
+    pub client_cert: Option<PathBuf>,
+    /// Proxy URL for all API traffic; overrides `HTTPS_PROXY`.
+    pub proxy: Option<String>,
+    /// Comma separated hosts that bypass `proxy`.
+    pub no_proxy: Option<String>,
+    /// OAuth application and tokens stored by `auth login`.
+    pub oauth: Option<OAuthConfig>,
+    /// Profile selected by `config profile use` when neither `--profile` nor
+    /// `ASANA_PROFILE` is given.
+    pub profile: Option<String>,
+    /// Named profiles (`[profiles.<name>]`). Each accepts the same keys as the
+    /// top level; credentials and defaults are never inherited, cache and
+    /// network settings fall back to the top-level values.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, FileConfig>,
+}
+
+/// Settings of a named profile that has not been written yet.
+static EMPTY_PROFILE: LazyLock<FileConfig> = LazyLock::new(FileConfig::default);
+
+/// OAuth credentials persisted by `auth login`.
+#[derive(Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct OAuthConfig {
+    /// OAuth application client identifier.
+    pub client_id: String,
+    /// OAuth application client secret, if one was issued.
+    pub client_secret: Option<String>,
+    /// Most recent access token.
+    pub access_token: String,
+    /// Refresh token used to renew the access token.
+    pub refresh_token: String,
+    /// Access token expiry in seconds since the Unix epoch.
+    pub expires_at: u64,
+}
+
+impl fmt::Debug for OAuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuthConfig")
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| "REDACTED"),
+            )
+            .field("access_token", &"REDACTED")
+            .field("refresh_token", &"REDACTED")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
 }
 
 impl fmt::Debug for FileConfig {
@@ -54,15 +130,65 @@ impl fmt::Debug for FileConfig {
                 "personal_access_token",
                 &self.personal_access_token.as_ref().map(|_| "REDACTED"),
             )
+            .field(
+                "encrypted_token",
+                &self.encrypted_token.as_ref().map(|_| "ENCRYPTED"),
+            )
+            .field("token_unlock_minutes", &self.token_unlock_minutes)
+            .field("token_command", &self.token_command)
+            .field("cache_stale_max_age_hours", &self.cache_stale_max_age_hours)
+            .field("cache_stale_fallback", &self.cache_stale_fallback)
+            .field("cache_max_size_mb", &self.cache_max_size_mb)
+            .field("ca_bundle", &self.ca_bundle)
+            .field("client_cert", &self.client_cert)
+            .field("proxy", &self.proxy)
+            .field("no_proxy", &self.no_proxy)
+            .field("oauth", &self.oauth)
+            .field("profile", &self.profile)
+            .field("profiles", &self.profiles)
             .finish()
     }
 }
 
+/// Origin of the token used for API requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// `ASANA_PAT` environment variable.
+    Environment,
+    /// Output of the configured `token_command`.
+    Command(String),
+    /// OAuth credentials stored by `auth login`.
+    OAuth,
+    /// Passphrase-encrypted Personal Access Token in the configuration file.
+    EncryptedConfigFile,
+    /// Personal Access Token persisted in the configuration file.
+    ConfigFile,
+    /// No credentials are configured.
+    None,
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Environment => f.write_str("ASANA_PAT environment variable"),
+            Self::Command(command) => write!(f, "token_command `{command}`"),
+            Self::OAuth => f.write_str("OAuth login"),
+            Self::EncryptedConfigFile => {
+                f.write_str("encrypted Personal Access Token in configuration file")
+            }
+            Self::ConfigFile => f.write_str("Personal Access Token in configuration file"),
+            Self::None => f.write_str("not configured"),
+        }
+    }
+}
+
 /// Runtime configuration including environment overrides and persisted settings.
 pub struct Config {
     file: FileConfig,
     overrides: Overrides,
     paths: ConfigPaths,
+    /// Active named profile; `None` selects the top-level settings.
+    profile: Option<String>,
 }
 
 impl Config {
@@ -77,23 +203,125 @@ impl Config {
                 format!("failed to create config directory at {}", parent.display())
             })?;
         }
-        fs::create_dir_all(&paths.cache_dir).with_context(|| {
-            format!(
-                "failed to create cache directory at {}",
-                paths.cache_dir.display()
-            )
-        })?;
 
         let file = read_config_file(&paths.config_file)?;
         let overrides = Overrides::collect();
+        let profile = overrides
+            .profile
+            .clone()
+            .or_else(|| file.profile.clone())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
 
-        Ok(Self {
+        let mut config = Self {
             file,
             overrides,
             paths,
+            profile: None,
+        };
+        config.set_profile(&profile)?;
+        Ok(config)
+    }
+
+    /// Switch the active profile for the current invocation (not persisted).
+    /// Each profile caches responses in its own directory.
+    ///
+    /// # Errors
+    /// Returns an error if the name is invalid or the profile cache directory
+    /// cannot be created.
+    pub fn set_profile(&mut self, name: &str) -> Result<()> {
+        validate_profile_name(name)?;
+        self.profile = (name != DEFAULT_PROFILE).then(|| name.to_string());
+        self.paths.cache_dir = self.profile_data_dir().join("cache");
+        fs::create_dir_all(&self.paths.cache_dir).with_context(|| {
+            format!(
+                "failed to create cache directory at {}",
+                self.paths.cache_dir.display()
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Name of the active profile.
+    #[must_use]
+    pub fn profile_name(&self) -> &str {
+        self.profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Whether the active profile has settings in the configuration file.
+    #[must_use]
+    pub fn profile_exists(&self) -> bool {
+        self.profile
+            .as_ref()
+            .is_none_or(|name| self.file.profiles.contains_key(name))
+    }
+
+    /// Names of every profile, starting with the default profile.
+    #[must_use]
+    pub fn profile_names(&self) -> Vec<&str> {
+        std::iter::once(DEFAULT_PROFILE)
+            .chain(self.file.profiles.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// Profile selected by `config profile use`.
+    #[must_use]
+    pub fn persisted_profile(&self) -> &str {
+        self.file.profile.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Make `name` the profile used when no other selection is given.
+    ///
+    /// # Errors
+    /// Returns an error if the profile does not exist or the configuration
+    /// file cannot be updated.
+    pub fn use_profile(&mut self, name: &str) -> Result<()> {
+        if name != DEFAULT_PROFILE && !self.file.profiles.contains_key(name) {
+            return Err(anyhow!("profile `{name}` does not exist"));
+        }
+        self.file.profile = (name != DEFAULT_PROFILE).then(|| name.to_string());
+        self.save()
+    }
+
+    /// Create profile `to` from the settings of profile `from`.
+    ///
+    /// # Errors
+    /// Returns an error if `from` does not exist, `to` already exists or is
+    /// invalid, or the configuration file cannot be updated.
+    pub fn copy_profile(&mut self, from: &str, to: &str) -> Result<()> {
+        validate_profile_name(to)?;
+        if to == DEFAULT_PROFILE || self.file.profiles.contains_key(to) {
+            return Err(anyhow!("profile `{to}` already exists"));
+        }
+        let source = if from == DEFAULT_PROFILE {
+            FileConfig {
+                profile: None,
+                profiles: BTreeMap::new(),
+                ..self.file.clone()
+            }
+        } else {
+            self.file
+                .profiles
+                .get(from)
+                .cloned()
+                .ok_or_else(|| anyhow!("profile `{from}` does not exist"))?
+        };
+        self.file.profiles.insert(to.to_string(), source);
+        self.save()
+    }
+
+    fn profile(&self) -> &FileConfig {
+        self.profile.as_ref().map_or(&self.file, |name| {
+            self.file.profiles.get(name).unwrap_or(&EMPTY_PROFILE)
         })
     }
 
+    fn profile_mut(&mut self) -> &mut FileConfig {
+        match &self.profile {
+            Some(name) => self.file.profiles.entry(name.clone()).or_default(),
+            None => &mut self.file,
+        }
+    }
+
     /// Return the path to the configuration file.
     #[must_use]
     pub fn path(&self) -> &Path {
@@ -112,6 +340,27 @@ impl Config {
         &self.paths.data_dir
     }
 
+    /// Directory holding Events API sync tokens for the active profile.
+    #[must_use]
+    pub fn sync_dir(&self) -> PathBuf {
+        self.profile_data_dir().join("sync")
+    }
+
+    /// File holding webhook handshake secrets for the active profile.
+    #[must_use]
+    pub fn webhook_secrets_path(&self) -> PathBuf {
+        self.profile_data_dir().join("webhook-secrets.json")
+    }
+
+    /// Data directory scoped to the active profile; the default profile uses
+    /// the data directory itself.
+    fn profile_data_dir(&self) -> PathBuf {
+        self.profile.as_ref().map_or_else(
+            || self.paths.data_dir.clone(),
+            |name| self.paths.data_dir.join("profiles").join(name),
+        )
+    }
+
     /// Directory where user templates are stored.
     #[must_use]
     pub fn templates_dir(&self) -> PathBuf {
@@ -130,7 +379,7 @@ impl Config {
         self.overrides
             .api_base_url
             .as_deref()
-            .or(self.file.api_base_url.as_deref())
+            .or_else(|| self.profile().api_base_url.as_deref())
     }
 
     /// Return the effective API base URL, falling back to the default value.
@@ -145,7 +394,7 @@ impl Config {
         self.overrides
             .default_workspace
             .as_deref()
-            .or(self.file.default_workspace.as_deref())
+            .or_else(|| self.profile().default_workspace.as_deref())
     }
 
     /// Update the stored default workspace identifier.
@@ -154,7 +403,7 @@ impl Config {
     ///
     /// Returns an error if the configuration file cannot be saved to disk.
     pub fn set_default_workspace(&mut self, workspace: Option<String>) -> Result<()> {
-        self.file.default_workspace = workspace;
+        self.profile_mut().default_workspace = workspace;
         self.save()
     }
 
@@ -164,7 +413,7 @@ impl Config {
         self.overrides
             .default_assignee
             .as_deref()
-            .or(self.file.default_assignee.as_deref())
+            .or_else(|| self.profile().default_assignee.as_deref())
     }
 
     /// Update the stored default assignee identifier.
@@ -173,7 +422,7 @@ impl Config {
     ///
     /// Returns an error if the configuration file cannot be saved to disk.
     pub fn set_default_assignee(&mut self, assignee: Option<String>) -> Result<()> {
-        self.file.default_assignee = assignee;
+        self.profile_mut().default_assignee = assignee;
         self.save()
     }
 
@@ -183,7 +432,7 @@ impl Config {
         self.overrides
             .default_project
             .as_deref()
-            .or(self.file.default_project.as_deref())
+            .or_else(|| self.profile().default_project.as_deref())
     }
 
     /// Update the stored default project identifier.
@@ -192,7 +441,7 @@ impl Config {
     ///
     /// Returns an error if the configuration file cannot be saved to disk.
     pub fn set_default_project(&mut self, project: Option<String>) -> Result<()> {
-        self.file.default_project = project;
+        self.profile_mut().default_project = project;
         self.save()
     }
 
@@ -228,25 +477,76 @@ impl Config {
     /// # Errors
     /// Returns an error if the configuration file cannot be updated.
     pub fn store_personal_access_token(&mut self, token: &SecretString) -> Result<()> {
-        self.file
+        let profile = self.profile_mut();
+        profile
             .personal_access_token
             .replace(token.expose_secret().to_owned());
+        profile.encrypted_token = None;
+        self.save()
+    }
+
+    /// Store a passphrase-encrypted Personal Access Token, replacing any
+    /// plaintext copy.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration file cannot be updated.
+    pub fn store_encrypted_token(&mut self, sealed: &SealedToken) -> Result<()> {
+        let profile = self.profile_mut();
+        profile.encrypted_token = Some(sealed.encode());
+        profile.personal_access_token = None;
         self.save()
     }
 
+    /// Passphrase-encrypted token stored in the configuration file, if any.
+    ///
+    /// # Errors
+    /// Returns an error if the stored value cannot be parsed.
+    pub fn encrypted_token(&self) -> Result<Option<SealedToken>> {
+        self.profile()
+            .encrypted_token
+            .as_deref()
+            .filter(|value| !value.trim().is_empty())
+            .map(SealedToken::parse)
+            .transpose()
+            .with_context(|| format!("invalid encrypted_token in {}", self.path().display()))
+    }
+
+    /// How long an unlocked encrypted token may be reused across invocations.
+    #[must_use]
+    pub fn token_unlock_duration(&self) -> Option<Duration> {
+        self.profile()
+            .token_unlock_minutes
+            .or(self.file.token_unlock_minutes)
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| Duration::from_secs(minutes.saturating_mul(60)))
+    }
+
+    /// Cache of the unlock key for the active profile, when a runtime
+    /// directory is available.
+    #[must_use]
+    pub fn unlock_cache(&self) -> Option<UnlockCache> {
+        self.paths
+            .runtime_dir
+            .as_deref()
+            .map(|dir| UnlockCache::new(dir, self.profile_name()))
+    }
+
     /// Retrieve the Personal Access Token, taking environment overrides into account.
     #[must_use]
     pub fn personal_access_token(&self) -> Option<SecretString> {
         if let Some(token) = self.overrides.personal_access_token.clone() {
             return Some(token);
         }
-        self.file.personal_access_token.as_ref().and_then(|value| {
-            if value.trim().is_empty() {
-                None
-            } else {
-                Some(SecretString::new(value.clone().into()))
-            }
-        })
+        self.profile()
+            .personal_access_token
+            .as_ref()
+            .and_then(|value| {
+                if value.trim().is_empty() {
+                    None
+                } else {
+                    Some(SecretString::new(value.clone().into()))
+                }
+            })
     }
 
     /// Remove any stored Personal Access Token.
@@ -254,14 +554,41 @@ impl Config {
     /// # Errors
     /// Returns an error when stored secrets cannot be removed.
     pub fn delete_personal_access_token(&mut self) -> Result<()> {
-        self.file.personal_access_token = None;
+        let profile = self.profile_mut();
+        profile.personal_access_token = None;
+        profile.encrypted_token = None;
         self.save()
     }
 
+    /// OAuth credentials stored by `auth login`, if any.
+    #[must_use]
+    pub fn oauth(&self) -> Option<&OAuthConfig> {
+        self.profile().oauth.as_ref()
+    }
+
+    /// Persist OAuth credentials.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration file cannot be updated.
+    pub fn store_oauth(&mut self, oauth: OAuthConfig) -> Result<()> {
+        self.profile_mut().oauth = Some(oauth);
+        self.save()
+    }
+
+    /// Remove stored OAuth credentials, returning whether any were present.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration file cannot be updated.
+    pub fn delete_oauth(&mut self) -> Result<bool> {
+        let removed = self.profile_mut().oauth.take().is_some();
+        self.save()?;
+        Ok(removed)
+    }
+
     /// Determine whether a token is persisted in the configuration file.
     #[must_use]
     pub fn has_persisted_token(&self) -> bool {
-        self.file
+        self.profile()
             .personal_access_token
             .as_ref()
             .is_some_and(|value| !value.trim().is_empty())
@@ -276,10 +603,194 @@ impl Config {
             .is_some_and(|value| !value.expose_secret().trim().is_empty())
     }
 
+    /// External command that prints the token, if configured.
+    #[must_use]
+    pub fn token_command(&self) -> Option<&str> {
+        self.profile()
+            .token_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+    }
+
+    /// Store or clear the token helper command.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration file cannot be updated.
+    pub fn set_token_command(&mut self, command: Option<String>) -> Result<()> {
+        self.profile_mut().token_command = command;
+        self.save()
+    }
+
+    /// Where the token for API requests comes from, in precedence order:
+    /// `ASANA_PAT`, `token_command`, OAuth credentials, then a stored
+    /// (encrypted or plaintext) PAT.
+    #[must_use]
+    pub fn token_source(&self) -> TokenSource {
+        if self.environment_token_available() {
+            TokenSource::Environment
+        } else if let Some(command) = self.token_command() {
+            TokenSource::Command(command.to_owned())
+        } else if self.oauth().is_some() {
+            TokenSource::OAuth
+        } else if self
+            .profile()
+            .encrypted_token
+            .as_ref()
+            .is_some_and(|value| !value.trim().is_empty())
+        {
+            TokenSource::EncryptedConfigFile
+        } else if self.has_persisted_token() {
+            TokenSource::ConfigFile
+        } else {
+            TokenSource::None
+        }
+    }
+
     /// Expose the underlying file configuration for mutation.
     pub fn file_config_mut(&mut self) -> &mut FileConfig {
         &mut self.file
     }
+
+    /// How long expired cache entries are retained, if configured.
+    #[must_use]
+    pub fn cache_stale_max_age(&self) -> Option<Duration> {
+        self.profile()
+            .cache_stale_max_age_hours
+            .or(self.file.cache_stale_max_age_hours)
+            .map(|hours| Duration::from_secs(hours.saturating_mul(3600)))
+    }
+
+    /// Whether stale cache entries may stand in for failed requests.
+    #[must_use]
+    pub fn cache_stale_fallback(&self) -> bool {
+        self.profile()
+            .cache_stale_fallback
+            .or(self.file.cache_stale_fallback)
+            .unwrap_or(false)
+    }
+
+    /// Disk cache size cap in bytes, or `None` when eviction is disabled.
+    #[must_use]
+    pub fn cache_max_bytes(&self) -> Option<u64> {
+        match self
+            .profile()
+            .cache_max_size_mb
+            .or(self.file.cache_max_size_mb)
+        {
+            None => Some(crate::api::cache::DEFAULT_CACHE_MAX_BYTES),
+            Some(0) => None,
+            Some(mb) => Some(mb.saturating_mul(1024 * 1024)),
+        }
+    }
+
+    /// TLS trust, client certificate, and proxy settings, with flags taking
+    /// precedence over the profile and the top level.
+    #[must_use]
+    pub fn network(&self) -> NetworkOptions {
+        let flags = &self.overrides.network;
+        let profile = self.profile();
+        NetworkOptions {
+            ca_bundle: flags
+                .ca_bundle
+                .clone()
+                .or_else(|| profile.ca_bundle.clone())
+                .or_else(|| self.file.ca_bundle.clone()),
+            client_cert: flags
+                .client_cert
+                .clone()
+                .or_else(|| profile.client_cert.clone())
+                .or_else(|| self.file.client_cert.clone()),
+            proxy: flags
+                .proxy
+                .clone()
+                .or_else(|| profile.proxy.clone())
+                .or_else(|| self.file.proxy.clone()),
+            no_proxy: flags
+                .no_proxy
+                .clone()
+                .or_else(|| profile.no_proxy.clone())
+                .or_else(|| self.file.no_proxy.clone()),
+        }
+    }
+
+    /// Override network settings for the current invocation (not persisted);
+    /// unset fields keep their configured values.
+    pub fn set_network(&mut self, network: NetworkOptions) {
+        self.overrides.network = network;
+    }
+
+    /// Whether API calls should be answered from the cache only.
+    #[must_use]
+    pub const fn offline(&self) -> bool {
+        self.overrides.offline
+    }
+
+    /// Force offline mode for the current invocation (not persisted).
+    pub const fn set_offline(&mut self, offline: bool) {
+        self.overrides.offline = offline;
+    }
+
+    /// Record or replay mode requested for the current invocation, if any.
+    #[must_use]
+    pub const fn cassette(&self) -> Option<&CassetteMode> {
+        self.overrides.cassette.as_ref()
+    }
+
+    /// Record requests to, or replay them from, a fixture directory for the
+    /// current invocation (not persisted).
+    pub fn set_cassette(&mut self, cassette: CassetteMode) {
+        self.overrides.cassette = Some(cassette);
+    }
+
+    /// HAR file receiving an HTTP trace for the current invocation, if any.
+    #[must_use]
+    pub fn trace_http(&self) -> Option<&Path> {
+        self.overrides.trace_http.as_deref()
+    }
+
+    /// Trace HTTP exchanges to a HAR file for the current invocation (not
+    /// persisted).
+    pub fn set_trace_http(&mut self, path: PathBuf) {
+        self.overrides.trace_http = Some(path);
+    }
+
+    /// Items requested per page for list calls in the current invocation, if
+    /// overridden.
+    #[must_use]
+    pub const fn page_size(&self) -> Option<usize> {
+        self.overrides.page_size
+    }
+
+    /// Request `size` items per page for the current invocation (not
+    /// persisted).
+    pub const fn set_page_size(&mut self, size: usize) {
+        self.overrides.page_size = Some(size);
+    }
+
+    /// Offset token the first listing of the current invocation resumes from.
+    #[must_use]
+    pub fn start_offset(&self) -> Option<&str> {
+        self.overrides.start_offset.as_deref()
+    }
+
+    /// Resume the first listing from `offset` for the current invocation (not
+    /// persisted).
+    pub fn set_start_offset(&mut self, offset: String) {
+        self.overrides.start_offset = Some(offset);
+    }
+
+    /// Whether list commands print the offset they can resume from.
+    #[must_use]
+    pub const fn print_next_offset(&self) -> bool {
+        self.overrides.print_next_offset
+    }
+
+    /// Print the resume offset after each list command of the current
+    /// invocation (not persisted).
+    pub const fn set_print_next_offset(&mut self, enabled: bool) {
+        self.overrides.print_next_offset = enabled;
+    }
 }
 
 impl fmt::Debug for Config {
@@ -288,6 +799,7 @@ impl fmt::Debug for Config {
             .field("file", &self.file)
             .field("overrides", &self.overrides)
             .field("paths", &self.paths)
+            .field("profile", &self.profile)
             .finish()
     }
 }
@@ -342,6 +854,14 @@ struct Overrides {
     default_assignee: Option<String>,
     default_project: Option<String>,
     personal_access_token: Option<SecretString>,
+    offline: bool,
+    cassette: Option<CassetteMode>,
+    trace_http: Option<PathBuf>,
+    page_size: Option<usize>,
+    start_offset: Option<String>,
+    print_next_offset: bool,
+    network: NetworkOptions,
+    profile: Option<String>,
 }
 
 impl fmt::Debug for Overrides {
@@ -355,6 +875,14 @@ impl fmt::Debug for Overrides {
                 "personal_access_token",
                 &self.personal_access_token.as_ref().map(|_| "REDACTED"),
             )
+            .field("offline", &self.offline)
+            .field("cassette", &self.cassette)
+            .field("trace_http", &self.trace_http)
+            .field("page_size", &self.page_size)
+            .field("start_offset", &self.start_offset)
+            .field("print_next_offset", &self.print_next_offset)
+            .field("network", &self.network)
+            .field("profile", &self.profile)
             .finish()
     }
 }
@@ -369,6 +897,17 @@ impl Overrides {
             personal_access_token: env::var(ENV_TOKEN)
                 .ok()
                 .map(|s| SecretString::new(s.into())),
+            offline: env::var(ENV_OFFLINE)
+                .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes")),
+            cassette: None,
+            trace_http: None,
+            page_size: None,
+            start_offset: None,
+            print_next_offset: false,
+            network: NetworkOptions::default(),
+            profile: env::var(ENV_PROFILE)
+                .ok()
+                .filter(|value| !value.trim().is_empty()),
         }
     }
 }
@@ -378,6 +917,7 @@ struct ConfigPaths {
     config_file: PathBuf,
     data_dir: PathBuf,
     cache_dir: PathBuf,
+    runtime_dir: Option<PathBuf>,
 }
 
 impl fmt::Debug for ConfigPaths {
@@ -386,6 +926,7 @@ impl fmt::Debug for ConfigPaths {
             .field("config_file", &self.config_file)
             .field("data_dir", &self.data_dir)
             .field("cache_dir", &self.cache_dir)
+            .field("runtime_dir", &self.runtime_dir)
             .finish()
     }
 }
@@ -412,14 +953,32 @@ fn resolve_paths() -> Result<ConfigPaths> {
         PathBuf::from,
     );
     let cache_dir = data_dir.join("cache");
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("asana-cli"));
 
     Ok(ConfigPaths {
         config_file: config_dir.join("config.toml"),
         data_dir,
         cache_dir,
+        runtime_dir,
     })
 }
 
+fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "invalid profile name `{name}`; use letters, digits, `-`, or `_`"
+        ))
+    }
+}
+
 fn read_config_file(path: &Path) -> Result<FileConfig> {
     if !path.exists() {
         debug!(config_path = %path.display(), "configuration file not found; using defaults");
@@ -435,7 +994,7 @@ fn read_config_file(path: &Path) -> Result<FileConfig> {
 
 #[cfg(test)]
 #[allow(unsafe_code)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use serial_test::serial;
     use std::ffi::OsStr;
@@ -453,7 +1012,9 @@ mod tests {
         }
     }
 
-    fn with_temp_env<F>(config_home: &TempDir, data_home: &TempDir, f: F)
+    /// Run `f` with the configuration and data directories pointed at
+    /// temporary locations, clearing the `ASANA_CLI_*` overrides afterwards.
+    pub fn with_temp_env<F>(config_home: &TempDir, data_home: &TempDir, f: F)
     where
         F: FnOnce(),
     {
@@ -467,6 +1028,7 @@ mod tests {
         remove_env(ENV_WORKSPACE);
         remove_env(ENV_ASSIGNEE);
         remove_env(ENV_PROJECT);
+        remove_env(ENV_PROFILE);
     }
 
     #[test]
@@ -557,4 +1119,89 @@ mod tests {
             assert!(cfg.default_assignee().is_none());
         });
     }
+
+    #[test]
+    #[serial]
+    fn profiles_keep_separate_settings_and_caches() {
+        let config_home = TempDir::new().unwrap();
+        let data_home = TempDir::new().unwrap();
+
+        with_temp_env(&config_home, &data_home, || {
+            let mut cfg = Config::load().expect("load config");
+            cfg.set_default_workspace(Some("personal".into()))
+                .expect("store workspace");
+            cfg.file_config_mut().cache_stale_fallback = Some(true);
+            cfg.copy_profile(DEFAULT_PROFILE, "client")
+                .expect("copy profile");
+            cfg.set_profile("client").expect("select profile");
+            cfg.set_default_workspace(Some("client-ws".into()))
+                .expect("store client workspace");
+            cfg.file_config_mut().proxy = Some("http://proxy.corp:3128".into());
+            cfg.save().expect("save proxy");
+            assert_ne!(cfg.cache_dir(), data_home.path().join("cache"));
+
+            let cfg = Config::load().expect("reload config");
+            assert_eq!(cfg.profile_name(), DEFAULT_PROFILE);
+            assert_eq!(cfg.default_workspace(), Some("personal"));
+            assert_eq!(cfg.profile_names(), vec![DEFAULT_PROFILE, "client"]);
+
+            set_env(ENV_PROFILE, "client");
+            let mut cfg = Config::load().expect("reload with env profile");
+            assert_eq!(cfg.default_workspace(), Some("client-ws"));
+            assert!(cfg.cache_stale_fallback(), "cache settings are inherited");
+            assert_eq!(
+                cfg.network().proxy.as_deref(),
+                Some("http://proxy.corp:3128"),
+                "network settings are inherited"
+            );
+            cfg.set_network(NetworkOptions {
+                no_proxy: Some("localhost".into()),
+                ..NetworkOptions::default()
+            });
+            assert_eq!(cfg.network().no_proxy.as_deref(), Some("localhost"));
+            assert!(cfg.network().proxy.is_some(), "flags only replace set keys");
+            assert!(cfg.cache_dir().ends_with("profiles/client/cache"));
+            assert!(cfg.cache_dir().exists());
+
+            cfg.use_profile("client").expect("persist selection");
+            remove_env(ENV_PROFILE);
+            let cfg = Config::load().expect("reload with persisted profile");
+            assert_eq!(cfg.profile_name(), "client");
+            assert!(
+                Config::load()
+                    .expect("load")
+                    .copy_profile("client", "client")
+                    .is_err()
+            );
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn encrypted_token_replaces_plaintext_copy() {
+        let config_home = TempDir::new().unwrap();
+        let data_home = TempDir::new().unwrap();
+
+        with_temp_env(&config_home, &data_home, || {
+            let mut cfg = Config::load().expect("load config");
+            cfg.store_personal_access_token(&SecretString::new("plain".into()))
+                .expect("store token");
+            let passphrase = SecretString::new("passphrase".into());
+            let sealed =
+                SealedToken::seal(&SecretString::new("sealed".into()), &passphrase).expect("seal");
+            cfg.store_encrypted_token(&sealed).expect("store sealed");
+
+            let cfg = Config::load().expect("reload config");
+            assert_eq!(cfg.token_source(), TokenSource::EncryptedConfigFile);
+            assert!(cfg.personal_access_token().is_none());
+            let stored = cfg.encrypted_token().expect("parse").expect("present");
+            let key = stored.derive_key(&passphrase).expect("derive");
+            assert_eq!(stored.open(&key).expect("open").expose_secret(), "sealed");
+            let raw = fs::read_to_string(cfg.path()).expect("read config");
+            assert!(
+                !raw.contains("sealed\""),
+                "token must not be stored in plain text"
+            );
+        });
+    }
 }
@@ -1,4 +1,189 @@
 //! Common error handling helpers for the Asana CLI.
+//!
+//! Failures are classified into an [`ErrorKind`] with a stable process exit
+//! code so scripts can branch on the failure type, and reported on stderr
+//! either as human-readable text with Asana's hints or as a JSON object.
+
+use crate::api::{ApiError, AsanaErrorDetail};
+use reqwest::StatusCode;
+use serde::Serialize;
 
 /// Result type alias leveraging `anyhow` for rich context.
 pub type Result<T> = anyhow::Result<T>;
+
+/// Failure categories with stable exit codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Any failure without a more specific category.
+    General,
+    /// Invalid command-line usage (reported by the argument parser).
+    Usage,
+    /// Missing, invalid, or rejected credentials.
+    Authentication,
+    /// The requested resource does not exist.
+    NotFound,
+    /// Asana kept rate limiting the request after retries.
+    RateLimited,
+    /// Asana rejected the request as invalid (HTTP 400/422).
+    Validation,
+    /// Offline mode had no cached response for the request.
+    Offline,
+    /// Asana could not be reached or answered with a server error.
+    Network,
+}
+
+impl ErrorKind {
+    /// Process exit code for this kind of failure.
+    #[must_use]
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::General => 1,
+            Self::Usage => 2,
+            Self::Authentication => 3,
+            Self::NotFound => 4,
+            Self::RateLimited => 5,
+            Self::Validation => 6,
+            Self::Offline => 7,
+            Self::Network => 8,
+        }
+    }
+
+    /// Classify an error by the first [`ApiError`] in its chain.
+    #[must_use]
+    pub fn classify(err: &anyhow::Error) -> Self {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<ApiError>())
+            .map_or(Self::General, Self::from_api_error)
+    }
+
+    fn from_api_error(err: &ApiError) -> Self {
+        match err {
+            ApiError::Authentication(_) => Self::Authentication,
+            ApiError::RateLimited { .. } => Self::RateLimited,
+            ApiError::Offline { .. } => Self::Offline,
+            ApiError::Network(_) => Self::Network,
+            ApiError::Http { status, .. } => match *status {
+                StatusCode::NOT_FOUND => Self::NotFound,
+                StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::Validation,
+                status if status.is_server_error() => Self::Network,
+                _ => Self::General,
+            },
+            _ => Self::General,
+        }
+    }
+}
+
+/// JSON shape of an error written to stderr with `--format json`.
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    /// Top-level error message.
+    pub message: String,
+    /// Failure category.
+    pub kind: ErrorKind,
+    /// Exit code the process terminates with.
+    pub exit_code: i32,
+    /// HTTP status returned by Asana, when the failure came from a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Structured details from Asana's `errors` array.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<AsanaErrorDetail>,
+    /// Messages of the underlying causes, outermost first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<String>,
+}
+
+impl ErrorReport {
+    /// Build a report for `err`.
+    #[must_use]
+    pub fn new(err: &anyhow::Error) -> Self {
+        let kind = ErrorKind::classify(err);
+        let api = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ApiError>());
+        Self {
+            message: err.to_string(),
+            kind,
+            exit_code: kind.exit_code(),
+            status: api.and_then(ApiError::status).map(|status| status.as_u16()),
+            errors: api.map(ApiError::asana_errors).unwrap_or_default(),
+            causes: err.chain().skip(1).map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// Print `err` to stderr and return the exit code to terminate with.
+///
+/// Human output lists the cause chain followed by Asana's `help` text and
+/// error phrases; `json` emits a single [`ErrorReport`] object instead.
+#[must_use]
+pub fn report(err: &anyhow::Error, json: bool) -> i32 {
+    let report = ErrorReport::new(err);
+    if json {
+        match serde_json::to_string(&report) {
+            Ok(serialized) => eprintln!("{serialized}"),
+            Err(_) => eprintln!("{err:?}"),
+        }
+    } else {
+        eprintln!("{}", render_human(&report));
+    }
+    report.exit_code
+}
+
+fn render_human(report: &ErrorReport) -> String {
+    let mut lines = vec![format!("error: {}", report.message)];
+    lines.extend(
+        report
+            .causes
+            .iter()
+            .map(|cause| format!("  caused by: {cause}")),
+    );
+    for detail in &report.errors {
+        if let Some(help) = &detail.help {
+            lines.push(format!("help: {help}"));
+        }
+        if let Some(phrase) = &detail.phrase {
+            lines.push(format!(
+                "note: quote error phrase \"{phrase}\" when contacting Asana support"
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn api_errors_map_to_stable_exit_codes() {
+        let not_found: anyhow::Error =
+            ApiError::from_status(StatusCode::NOT_FOUND, String::new()).into();
+        assert_eq!(ErrorKind::classify(&not_found).exit_code(), 4);
+
+        let validation = Err::<(), _>(ApiError::from_status(
+            StatusCode::BAD_REQUEST,
+            r#"{"errors":[{"message":"name: Missing input","help":"Provide a name"}]}"#.into(),
+        ))
+        .context("failed to create task")
+        .expect_err("error");
+        let report = ErrorReport::new(&validation);
+        assert_eq!(report.kind, ErrorKind::Validation);
+        assert_eq!(report.exit_code, 6);
+        assert_eq!(report.status, Some(400));
+        assert_eq!(report.causes, ["HTTP 400 Bad Request: name: Missing input"]);
+        assert!(render_human(&report).contains("help: Provide a name"));
+
+        let offline: anyhow::Error = ApiError::Offline {
+            resource: "/users/me".into(),
+        }
+        .into();
+        assert_eq!(ErrorKind::classify(&offline), ErrorKind::Offline);
+        assert_eq!(
+            ErrorKind::classify(&anyhow::anyhow!("bad input")),
+            ErrorKind::General
+        );
+    }
+}
@@ -16,6 +16,8 @@ pub mod filters;
 pub mod models;
 /// Output rendering helpers.
 pub mod output;
+/// Passphrase encryption for stored tokens.
+pub mod secrets;
 /// User configurable templates.
 pub mod templates;
 
@@ -7,12 +7,5 @@ fn main() {
         eprintln!("failed to initialize tracing: {err}");
     }
 
-    match cli::run() {
-        Ok(code) => std::process::exit(code),
-        Err(err) => {
-            tracing::error!(error = %err, "command execution failed");
-            eprintln!("{err:?}");
-            std::process::exit(1);
-        }
-    }
+    std::process::exit(cli::run_and_report());
 }
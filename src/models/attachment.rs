@@ -41,6 +41,19 @@ pub struct AttachmentListParams {
     pub task_gid: String,
     /// Maximum number to fetch.
     pub limit: Option<usize>,
+    /// Offset token to start from, as printed by `--print-next-offset`.
+    pub offset: Option<String>,
+}
+
+impl AttachmentListParams {
+    /// Convert the structure into query string pairs.
+    #[must_use]
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        self.offset
+            .iter()
+            .map(|offset| ("offset".into(), offset.clone()))
+            .collect()
+    }
 }
 
 /// Parameters for uploading attachments.
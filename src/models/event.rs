@@ -0,0 +1,60 @@
+//! Events API payloads describing changes to watched resources.
+
+use super::user::UserReference;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Resource touched by an event, or the parent it was changed within.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventResource {
+    /// Globally unique identifier.
+    pub gid: String,
+    /// Resource type marker (`task`, `story`, `project`, ...).
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    /// Resource subtype, e.g. `comment_added` for stories.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_subtype: Option<String>,
+    /// Display name, when Asana includes one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Field-level detail for `changed` events.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventChange {
+    /// Name of the field that changed.
+    pub field: String,
+    /// Kind of change (`changed`, `added`, `removed`).
+    pub action: String,
+    /// New value for scalar fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<Value>,
+    /// Value added to a collection field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_value: Option<Value>,
+    /// Value removed from a collection field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed_value: Option<Value>,
+}
+
+/// A single change reported by the Events API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Event {
+    /// What happened (`changed`, `added`, `removed`, `deleted`, `undeleted`).
+    pub action: String,
+    /// The resource that was affected.
+    pub resource: EventResource,
+    /// Containing resource for `added`/`removed` events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<EventResource>,
+    /// Field-level change details.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<EventChange>,
+    /// User who triggered the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<UserReference>,
+    /// When the event occurred.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
@@ -2,18 +2,21 @@
 
 pub mod attachment;
 pub mod custom_field;
+pub mod event;
 pub mod project;
 pub mod section;
 pub mod story;
 pub mod tag;
 pub mod task;
 pub mod user;
+pub mod webhook;
 pub mod workspace;
 
 pub use attachment::{Attachment, AttachmentListParams, AttachmentUploadParams};
 pub use custom_field::{
     CustomField, CustomFieldDateValue, CustomFieldEnumOption, CustomFieldType, CustomFieldValue,
 };
+pub use event::{Event, EventChange, EventResource};
 pub use project::{
     MemberPermission, Project, ProjectCreateData, ProjectCreateRequest, ProjectFilter,
     ProjectListParams, ProjectMember, ProjectMembers, ProjectSort, ProjectStatus, ProjectTemplate,
@@ -32,10 +35,11 @@ pub use tag::{
     TagUpdateBuilder, TagUpdateData, TagUpdateRequest, TagValidationError,
 };
 pub use task::{
-    Task, TaskAssigneeStatus, TaskCreateBuilder, TaskCreateData, TaskCreateRequest, TaskListParams,
-    TaskMembership, TaskProjectReference, TaskReference, TaskSearchParams, TaskSectionReference,
-    TaskSort, TaskTagReference, TaskUpdateBuilder, TaskUpdateData, TaskUpdateRequest,
-    TaskValidationError,
+    Task, TaskAssigneeStatus, TaskCreateBuilder, TaskCreateData, TaskCreateRequest, TaskExternal,
+    TaskListParams, TaskMembership, TaskProjectReference, TaskReference, TaskSearchParams,
+    TaskSectionReference, TaskSort, TaskTagReference, TaskUpdateBuilder, TaskUpdateData,
+    TaskUpdateRequest, TaskValidationError,
 };
 pub use user::{User, UserIdentity, UserListParams, UserPhoto, UserReference};
+pub use webhook::{Webhook, WebhookCreateData, WebhookCreateRequest, WebhookListParams};
 pub use workspace::{Workspace, WorkspaceListParams, WorkspaceReference};
@@ -77,6 +77,44 @@ pub struct Project {
 }
 
 impl Project {
+    /// `opt_fields` describing a project in listings when no renderer narrows
+    /// them.
+    pub const LIST_FIELDS: &'static [&'static str] = &[
+        "gid",
+        "name",
+        "archived",
+        "color",
+        "created_at",
+        "modified_at",
+        "workspace.name",
+        "workspace.gid",
+        "team.name",
+        "team.gid",
+        "owner.name",
+        "owner.gid",
+        "due_on",
+        "start_on",
+    ];
+
+    /// `opt_fields` describing a single project in full.
+    pub const DETAIL_FIELDS: &'static [&'static str] = &[
+        "gid",
+        "name",
+        "notes",
+        "color",
+        "archived",
+        "public",
+        "workspace.name",
+        "workspace.gid",
+        "team.name",
+        "owner.name",
+        "owner.gid",
+        "due_on",
+        "start_on",
+        "created_at",
+        "modified_at",
+    ];
+
     /// Determine whether the project matches a set of filters.
     #[must_use]
     pub fn matches(&self, filters: &[ProjectFilter]) -> bool {
@@ -137,14 +175,19 @@ pub struct ProjectListParams {
     pub team: Option<String>,
     /// Filter archived flag.
     pub archived: Option<bool>,
-    /// Additional fields to request.
+    /// Additional fields to request on top of [`Project::LIST_FIELDS`].
     pub fields: BTreeSet<String>,
+    /// Request only `fields` (plus what local filters and sorting read)
+    /// instead of adding them to [`Project::LIST_FIELDS`].
+    pub exact_fields: bool,
     /// Maximum number of items to fetch (client side constraint).
     pub limit: Option<usize>,
     /// Optional saved filter expressions.
     pub filters: Vec<ProjectFilter>,
     /// Sort field.
     pub sort: Option<ProjectSort>,
+    /// Offset token to start from, as printed by `--print-next-offset`.
+    pub offset: Option<String>,
 }
 
 impl ProjectListParams {
@@ -165,6 +208,9 @@ impl ProjectListParams {
             let field_list = self.fields.iter().cloned().collect::<Vec<_>>().join(",");
             pairs.push(("opt_fields".into(), field_list));
         }
+        if let Some(offset) = &self.offset {
+            pairs.push(("offset".into(), offset.clone()));
+        }
         pairs
     }
 }
@@ -194,6 +240,24 @@ pub enum ProjectFilter {
 }
 
 impl ProjectFilter {
+    /// `opt_fields` the filter reads when evaluated locally.
+    #[must_use]
+    pub fn opt_fields(&self) -> Vec<String> {
+        let (Self::Equals(field, _)
+        | Self::NotEquals(field, _)
+        | Self::Regex(field, _)
+        | Self::Contains(field, _)) = self;
+        match field.as_str() {
+            "name" | "gid" | "notes" | "color" | "archived" | "public" | "due_on" | "start_on"
+            | "created_at" | "modified_at" => vec![field.clone()],
+            "workspace" | "team" => vec![format!("{field}.name")],
+            "owner" => vec!["owner.name".into(), "owner.email".into()],
+            "owner.name" | "owner_name" => vec!["owner.name".into()],
+            "owner.email" | "owner_email" => vec!["owner.email".into()],
+            _ => Vec::new(),
+        }
+    }
+
     /// Evaluate filter against a project instance.
     #[must_use]
     pub fn matches(&self, project: &Project) -> bool {
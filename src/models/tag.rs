@@ -109,13 +109,19 @@ pub struct TagListParams {
     pub workspace: String,
     /// Maximum number of items to fetch (client side).
     pub limit: Option<usize>,
+    /// Offset token to start from, as printed by `--print-next-offset`.
+    pub offset: Option<String>,
 }
 
 impl TagListParams {
     /// Convert the structure into query string pairs.
     #[must_use]
     pub fn to_query(&self) -> Vec<(String, String)> {
-        vec![("workspace".into(), self.workspace.clone())]
+        let mut query = vec![("workspace".into(), self.workspace.clone())];
+        if let Some(offset) = &self.offset {
+            query.push(("offset".into(), offset.clone()));
+        }
+        query
     }
 }
 
@@ -136,6 +136,28 @@ impl Default for TaskAssigneeStatus {
     }
 }
 
+/// Caller-assigned identity stored on a task through Asana's `external` field.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct TaskExternal {
+    /// Identifier unique per app; the task is addressable as `external:<gid>`.
+    pub gid: String,
+    /// Free-form data stored alongside the identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl TaskExternal {
+    /// External identity carrying only an identifier.
+    #[must_use]
+    pub fn new(gid: impl Into<String>) -> Self {
+        Self {
+            gid: gid.into(),
+            data: None,
+        }
+    }
+}
+
 /// Full task payload returned by the Asana API.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -225,9 +247,83 @@ pub struct Task {
     /// Number of subtasks this task contains.
     #[serde(default)]
     pub num_subtasks: Option<i64>,
+    /// Caller-assigned external identity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external: Option<TaskExternal>,
 }
 
 impl Task {
+    /// `opt_fields` describing a task in listings when no renderer narrows them.
+    pub const LIST_FIELDS: &'static [&'static str] = &[
+        "gid",
+        "name",
+        "completed",
+        "completed_at",
+        "due_on",
+        "due_at",
+        "start_on",
+        "start_at",
+        "assignee.name",
+        "assignee.gid",
+        "assignee.email",
+        "resource_type",
+        "resource_subtype",
+        "modified_at",
+        "workspace.name",
+        "workspace.gid",
+        "projects.name",
+        "projects.gid",
+        "tags.name",
+        "tags.gid",
+        "memberships.project.name",
+        "memberships.project.gid",
+        "memberships.section.name",
+        "memberships.section.gid",
+        "permalink_url",
+        "num_subtasks",
+    ];
+
+    /// `opt_fields` describing a single task in full.
+    pub const DETAIL_FIELDS: &'static [&'static str] = &[
+        "gid",
+        "name",
+        "completed",
+        "completed_at",
+        "due_on",
+        "due_at",
+        "start_on",
+        "start_at",
+        "notes",
+        "html_notes",
+        "assignee",
+        "assignee_status",
+        "assignee.name",
+        "assignee.email",
+        "completed_by",
+        "created_at",
+        "modified_at",
+        "workspace",
+        "workspace.name",
+        "workspace.gid",
+        "parent",
+        "projects",
+        "projects.name",
+        "projects.gid",
+        "memberships",
+        "tags",
+        "followers",
+        "followers.name",
+        "followers.email",
+        "dependencies",
+        "dependents",
+        "custom_fields",
+        "attachments",
+        "permalink_url",
+        "resource_subtype",
+        "num_subtasks",
+        "external",
+    ];
+
     /// Determines whether the task is currently incomplete.
     #[must_use]
     pub const fn is_open(&self) -> bool {
@@ -256,8 +352,11 @@ pub struct TaskListParams {
     pub include_subtasks: bool,
     /// Maximum number of items to fetch (client side).
     pub limit: Option<usize>,
-    /// Additional fields to request.
+    /// Additional fields to request on top of [`Task::LIST_FIELDS`].
     pub fields: BTreeSet<String>,
+    /// Request only `fields` (plus what local filters and sorting read)
+    /// instead of adding them to [`Task::LIST_FIELDS`].
+    pub exact_fields: bool,
     /// Sort order applied post-fetch.
     pub sort: Option<TaskSort>,
     /// Post-fetch completion filter.
@@ -266,6 +365,8 @@ pub struct TaskListParams {
     pub due_before: Option<String>,
     /// Post-fetch due date lower bound (inclusive, YYYY-MM-DD).
     pub due_after: Option<String>,
+    /// Offset token to start from, as printed by `--print-next-offset`.
+    pub offset: Option<String>,
 }
 
 impl TaskListParams {
@@ -301,20 +402,30 @@ impl TaskListParams {
             let field_list = self.fields.iter().cloned().collect::<Vec<_>>().join(",");
             pairs.push(("opt_fields".into(), field_list));
         }
+        if let Some(offset) = &self.offset {
+            pairs.push(("offset".into(), offset.clone()));
+        }
         pairs
     }
 
     /// Apply local filters after API pagination.
     pub fn apply_post_filters(&self, tasks: &mut Vec<Task>) {
-        if let Some(expected) = self.completed {
-            tasks.retain(|task| task.completed == expected);
-        }
-        if let Some(due_before) = &self.due_before {
-            tasks.retain(|task| task.due_on.as_ref().is_some_and(|due| due <= due_before));
-        }
-        if let Some(due_after) = &self.due_after {
-            tasks.retain(|task| task.due_on.as_ref().is_some_and(|due| due >= due_after));
-        }
+        tasks.retain(|task| self.matches_post_filters(task));
+    }
+
+    /// Whether `task` passes the client-side completion and due date filters.
+    #[must_use]
+    pub fn matches_post_filters(&self, task: &Task) -> bool {
+        self.completed
+            .is_none_or(|expected| task.completed == expected)
+            && self
+                .due_before
+                .as_ref()
+                .is_none_or(|due_before| task.due_on.as_ref().is_some_and(|due| due <= due_before))
+            && self
+                .due_after
+                .as_ref()
+                .is_none_or(|due_after| task.due_on.as_ref().is_some_and(|due| due >= due_after))
     }
 }
 
@@ -376,7 +487,7 @@ pub struct TaskSearchParams {
     pub sort_ascending: bool,
     /// Maximum number of items to fetch.
     pub limit: Option<usize>,
-    /// Additional fields to request.
+    /// Additional fields to request on top of [`Task::LIST_FIELDS`].
     pub fields: BTreeSet<String>,
 }
 
@@ -499,6 +610,9 @@ pub struct TaskCreateData {
     /// Custom field assignments.
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub custom_fields: BTreeMap<String, serde_json::Value>,
+    /// External identity making the create idempotent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<TaskExternal>,
 }
 
 /// API envelope for create requests.
@@ -536,6 +650,7 @@ impl TaskCreateBuilder {
                 tags: Vec::new(),
                 followers: Vec::new(),
                 custom_fields: BTreeMap::new(),
+                external: None,
             },
         }
     }
@@ -656,6 +771,14 @@ impl TaskCreateBuilder {
         self
     }
 
+    /// Tag the task with an external identifier so repeated creates update it
+    /// instead of duplicating it.
+    #[must_use]
+    pub fn external_id(mut self, id: impl Into<String>) -> Self {
+        self.data.external = Some(TaskExternal::new(id));
+        self
+    }
+
     /// Finalise the builder into a request payload performing validation.
     ///
     /// # Errors
@@ -731,6 +854,9 @@ pub struct TaskUpdateData {
     /// Custom field updates.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_fields: Option<BTreeMap<String, serde_json::Value>>,
+    /// External identity change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<TaskExternal>,
 }
 
 impl TaskUpdateData {
@@ -751,6 +877,33 @@ impl TaskUpdateData {
             && self.followers.is_none()
             && self.projects.is_none()
             && self.custom_fields.is_none()
+            && self.external.is_none()
+    }
+}
+
+impl From<TaskCreateData> for TaskUpdateData {
+    /// Apply a create payload to an existing task. Workspace and section only
+    /// matter when a task is created, and empty collections leave the existing
+    /// ones untouched.
+    fn from(data: TaskCreateData) -> Self {
+        let non_empty = |values: Vec<String>| Some(values).filter(|values| !values.is_empty());
+        Self {
+            name: Some(data.name),
+            notes: data.notes.map(Some),
+            html_notes: data.html_notes.map(Some),
+            completed: None,
+            assignee: data.assignee.map(Some),
+            due_on: data.due_on.map(Some),
+            due_at: data.due_at.map(Some),
+            start_on: data.start_on.map(Some),
+            start_at: data.start_at.map(Some),
+            parent: data.parent.map(Some),
+            tags: non_empty(data.tags),
+            followers: non_empty(data.followers),
+            projects: non_empty(data.projects),
+            custom_fields: Some(data.custom_fields).filter(|fields| !fields.is_empty()),
+            external: data.external,
+        }
     }
 }
 
@@ -1024,6 +1177,27 @@ mod tests {
         assert_eq!(request.data.parent.as_deref(), Some("T1"));
     }
 
+    #[test]
+    fn create_payload_converts_to_update_for_external_ids() {
+        let request = TaskCreateBuilder::new("Imported")
+            .workspace("ws-1")
+            .section("S1")
+            .notes("body")
+            .external_id("import-1")
+            .build()
+            .expect("builder should succeed");
+        let json = serde_json::to_value(&request).expect("serialize");
+        assert_eq!(json["data"]["external"]["gid"], "import-1");
+
+        let update = TaskUpdateData::from(request.data);
+        assert_eq!(update.name.as_deref(), Some("Imported"));
+        assert_eq!(update.notes, Some(Some("body".to_string())));
+        assert_eq!(update.projects, None);
+        let json = serde_json::to_value(&update).expect("serialize");
+        assert!(json.get("workspace").is_none());
+        assert!(json.get("section").is_none());
+    }
+
     #[test]
     fn update_builder_requires_changes() {
         let builder = TaskUpdateBuilder::new();
@@ -96,4 +96,17 @@ pub struct UserListParams {
     pub workspace_gid: String,
     /// Maximum number to fetch.
     pub limit: Option<usize>,
+    /// Offset token to start from, as printed by `--print-next-offset`.
+    pub offset: Option<String>,
+}
+
+impl UserListParams {
+    /// Convert the structure into query string pairs.
+    #[must_use]
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        self.offset
+            .iter()
+            .map(|offset| ("offset".into(), offset.clone()))
+            .collect()
+    }
 }
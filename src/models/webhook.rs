@@ -0,0 +1,79 @@
+//! Webhook subscription data structures.
+
+use super::event::EventResource;
+use serde::{Deserialize, Serialize};
+
+/// Webhook subscription delivering events for a resource to a target URL.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Webhook {
+    /// Globally unique identifier.
+    pub gid: String,
+    /// Resource type marker.
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    /// Whether Asana is currently delivering events.
+    #[serde(default)]
+    pub active: bool,
+    /// Resource the webhook is subscribed to.
+    #[serde(default)]
+    pub resource: Option<EventResource>,
+    /// URL receiving event deliveries.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Creation timestamp.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Timestamp of the last successful delivery.
+    #[serde(default)]
+    pub last_success_at: Option<String>,
+    /// Timestamp of the last failed delivery.
+    #[serde(default)]
+    pub last_failure_at: Option<String>,
+    /// Response received on the last failed delivery.
+    #[serde(default)]
+    pub last_failure_content: Option<String>,
+}
+
+/// Parameters for listing webhooks.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookListParams {
+    /// Workspace filter (required by Asana).
+    pub workspace: String,
+    /// Only return webhooks for this resource.
+    pub resource: Option<String>,
+    /// Maximum number of items to fetch (client side).
+    pub limit: Option<usize>,
+    /// Offset token to start from, as printed by `--print-next-offset`.
+    pub offset: Option<String>,
+}
+
+impl WebhookListParams {
+    /// Convert the structure into query string pairs.
+    #[must_use]
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut query = vec![("workspace".into(), self.workspace.clone())];
+        if let Some(resource) = &self.resource {
+            query.push(("resource".into(), resource.clone()));
+        }
+        if let Some(offset) = &self.offset {
+            query.push(("offset".into(), offset.clone()));
+        }
+        query
+    }
+}
+
+/// Payload for creating webhooks.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WebhookCreateData {
+    /// Resource to subscribe to.
+    pub resource: String,
+    /// URL that receives the handshake and event deliveries.
+    pub target: String,
+}
+
+/// API envelope for create requests.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookCreateRequest {
+    /// Wrapped data payload.
+    pub data: WebhookCreateData,
+}
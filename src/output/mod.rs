@@ -1,11 +1,17 @@
 //! Output helpers for rendering command results.
 
 pub mod project;
+pub mod select;
+pub(crate) mod stream;
 pub mod task;
 
+use chrono::{DateTime, SecondsFormat, Utc};
 use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Value, json};
 use std::fmt;
 use std::str::FromStr;
+use std::time::SystemTime;
 
 /// Supported output formats for project-oriented commands.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -98,3 +104,53 @@ impl FromStr for TaskOutputFormat {
         }
     }
 }
+
+/// Serialise command output as pretty-printed JSON.
+///
+/// # Errors
+/// Returns an error if the value cannot be serialised.
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(value)
+}
+
+/// Mark pretty-printed JSON output built from stale cache data.
+///
+/// Output is wrapped as `{ "data": ..., "meta": { "stale": true,
+/// "stale_as_of": ... } }`, so scripts can tell it apart from live results.
+/// Output from live data (`stale_as_of` is `None`) is returned unchanged.
+#[must_use]
+pub fn mark_stale(json: String, stale_as_of: Option<SystemTime>) -> String {
+    let Some(as_of) = stale_as_of else {
+        return json;
+    };
+    let meta = serde_json::to_string_pretty(&stale_meta(as_of)).unwrap_or_default();
+    format!(
+        "{{\n  \"data\": {},\n  \"meta\": {}\n}}",
+        json.replace('\n', "\n  "),
+        meta.replace('\n', "\n  ")
+    )
+}
+
+/// `meta` object attached to output built from stale cache data.
+fn stale_meta(as_of: SystemTime) -> Value {
+    json!({
+        "stale": true,
+        "stale_as_of": DateTime::<Utc>::from(as_of).to_rfc3339_opts(SecondsFormat::Secs, true),
+    })
+}
+
+/// Serialise one CSV record, preceded by the header row when `header` is set,
+/// so listings can be written as records arrive.
+///
+/// # Errors
+/// Returns an error if the record cannot be serialised.
+pub(crate) fn csv_row<T: Serialize>(row: &T, header: bool) -> crate::error::Result<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(header)
+        .from_writer(vec![]);
+    writer.serialize(row)?;
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| anyhow::anyhow!("failed to finalize CSV writer: {err}"))?;
+    Ok(String::from_utf8(bytes)?)
+}
@@ -3,12 +3,16 @@
 use crate::{
     error::Result,
     models::{Project, ProjectMember},
-    output::ProjectOutputFormat,
+    output::{
+        ProjectOutputFormat,
+        stream::{ListWriter, RowStyle},
+    },
 };
 use anyhow::Context;
 use csv::WriterBuilder;
 use serde::Serialize;
 use serde_json::Value;
+use std::{collections::BTreeSet, time::SystemTime};
 use tabled::{
     Table, Tabled,
     settings::{Alignment, Modify, Style, object::Rows},
@@ -35,6 +39,69 @@ fn apply_style(table: &mut Table, style: TableStyleKind) {
     }
 }
 
+/// Fields read by [`ProjectRow`]; label helpers fall back from name to email.
+const PROJECT_ROW_FIELDS: &[&str] = &[
+    "name",
+    "workspace.name",
+    "owner.name",
+    "owner.email",
+    "archived",
+    "due_on",
+    "modified_at",
+];
+
+/// Column widths for streamed project tables, in [`ProjectRow`] order.
+const PROJECT_ROW_WIDTHS: &[usize] = &[19, 40, 20, 24, 8, 10, 24];
+
+/// Fields read by both detail views; members and statuses are fetched
+/// separately.
+const PROJECT_DETAIL_FIELDS: &[&str] = &[
+    "name",
+    "archived",
+    "public",
+    "workspace.name",
+    "team.name",
+    "owner.name",
+    "owner.email",
+    "start_on",
+    "due_on",
+    "created_at",
+    "modified_at",
+];
+
+/// `opt_fields` needed to render a project list in `format`.
+///
+/// JSON shows whole records, so it asks for [`Project::LIST_FIELDS`].
+#[must_use]
+pub fn project_list_fields(format: ProjectOutputFormat) -> BTreeSet<String> {
+    let fields = match format {
+        ProjectOutputFormat::Json => Project::LIST_FIELDS,
+        ProjectOutputFormat::Table | ProjectOutputFormat::Csv | ProjectOutputFormat::Markdown => {
+            PROJECT_ROW_FIELDS
+        }
+    };
+    fields.iter().map(ToString::to_string).collect()
+}
+
+/// `opt_fields` needed to render a single project in `format`.
+///
+/// JSON shows the whole record, so it asks for [`Project::DETAIL_FIELDS`].
+#[must_use]
+pub fn project_detail_fields(format: ProjectOutputFormat) -> BTreeSet<String> {
+    let sections: &[&[&str]] = match format {
+        ProjectOutputFormat::Json => &[Project::DETAIL_FIELDS],
+        ProjectOutputFormat::Csv => &[PROJECT_DETAIL_FIELDS],
+        ProjectOutputFormat::Table | ProjectOutputFormat::Markdown => {
+            &[PROJECT_DETAIL_FIELDS, &["notes"]]
+        }
+    };
+    sections
+        .iter()
+        .flat_map(|fields| fields.iter())
+        .map(ToString::to_string)
+        .collect()
+}
+
 /// Render a collection of projects in the requested format.
 ///
 /// # Errors
@@ -46,7 +113,7 @@ pub fn render_project_list(
     tty: bool,
 ) -> Result<String> {
     match format {
-        ProjectOutputFormat::Json => Ok(serde_json::to_string_pretty(projects)?),
+        ProjectOutputFormat::Json => Ok(super::to_json(projects)?),
         ProjectOutputFormat::Csv => render_projects_csv(projects),
         ProjectOutputFormat::Markdown => {
             Ok(render_projects_table(projects, TableStyleKind::Markdown))
@@ -62,6 +129,61 @@ pub fn render_project_list(
     }
 }
 
+/// Renders a project listing as projects arrive from a stream.
+///
+/// Every format is written as projects are pushed: CSV and markdown rows,
+/// table rows at fixed column widths (longer cells are cut with `…`), and
+/// JSON as the elements of an array.
+#[derive(Debug)]
+pub struct ProjectListRenderer {
+    writer: ListWriter,
+}
+
+impl ProjectListRenderer {
+    /// Create a renderer for `format`; `tty` selects the table style.
+    #[must_use]
+    pub fn new(format: ProjectOutputFormat, tty: bool) -> Self {
+        let writer = match format {
+            ProjectOutputFormat::Json => ListWriter::json(),
+            ProjectOutputFormat::Csv => ListWriter::csv(),
+            ProjectOutputFormat::Markdown => {
+                ListWriter::table::<ProjectRow>(RowStyle::Markdown, PROJECT_ROW_WIDTHS)
+            }
+            ProjectOutputFormat::Table => {
+                ListWriter::table::<ProjectRow>(RowStyle::boxed(tty), PROJECT_ROW_WIDTHS)
+            }
+        };
+        Self { writer }
+    }
+
+    /// Add a project, returning output that can be printed right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON or CSV serialization fails.
+    pub fn push(&mut self, project: &Project) -> Result<Option<String>> {
+        self.writer
+            .push(project, |project| ProjectRow::from(project))
+            .map(Some)
+    }
+
+    /// Record that the listing so far includes stale cache data written at
+    /// `stale_as_of`. JSON output is only wrapped with the stale-cache `meta`
+    /// when this was noted before the first push.
+    pub const fn note_stale(&mut self, stale_as_of: Option<SystemTime>) {
+        self.writer.note_stale(stale_as_of);
+    }
+
+    /// Output closing the listing (the table border or JSON bracket), if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if JSON serialization fails.
+    pub fn finish(self) -> Result<Option<String>> {
+        self.writer.finish()
+    }
+}
+
 fn render_projects_table(projects: &[Project], style: TableStyleKind) -> String {
     let rows: Vec<ProjectRow> = projects.iter().map(ProjectRow::from).collect();
     let mut table = Table::new(rows);
@@ -90,7 +212,7 @@ pub fn render_project_detail(
     tty: bool,
 ) -> Result<String> {
     match format {
-        ProjectOutputFormat::Json => Ok(serde_json::to_string_pretty(project)?),
+        ProjectOutputFormat::Json => Ok(super::to_json(project)?),
         ProjectOutputFormat::Csv => render_detail_csv(project),
         ProjectOutputFormat::Markdown => Ok(render_detail_table(project, TableStyleKind::Markdown)),
         ProjectOutputFormat::Table => {
@@ -286,7 +408,7 @@ pub fn render_project_members(
     tty: bool,
 ) -> Result<String> {
     match format {
-        ProjectOutputFormat::Json => Ok(serde_json::to_string_pretty(members)?),
+        ProjectOutputFormat::Json => Ok(super::to_json(members)?),
         ProjectOutputFormat::Csv => render_members_csv(members),
         ProjectOutputFormat::Markdown => {
             Ok(render_members_table(members, TableStyleKind::Markdown))
@@ -0,0 +1,137 @@
+//! Path selectors for picking values out of raw API responses.
+//!
+//! A selector is a chain of steps such as `data[0].name` or `$.data[*].gid`:
+//! `.key` or `["key"]` reads an object field, `[n]` (or `.n`) an array
+//! element, and `[*]`, `[]`, or `.*` every element. Once a wildcard has been
+//! applied the result is an array of matches. Missing fields select `null`.
+
+use crate::error::Result;
+use anyhow::{anyhow, bail};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    All,
+}
+
+/// Apply `selector` to `value`.
+///
+/// # Errors
+/// Returns an error when the selector cannot be parsed.
+pub fn select(value: &Value, selector: &str) -> Result<Value> {
+    let steps = parse(selector)?;
+    let mut current = vec![value.clone()];
+    let mut fanned_out = false;
+    for step in &steps {
+        current = match step {
+            Step::Key(key) => current
+                .iter()
+                .map(|value| value.get(key).cloned().unwrap_or(Value::Null))
+                .collect(),
+            Step::Index(index) => current
+                .iter()
+                .map(|value| {
+                    match value {
+                        Value::Array(items) => items.get(*index),
+                        other => other.get(index.to_string()),
+                    }
+                    .cloned()
+                    .unwrap_or(Value::Null)
+                })
+                .collect(),
+            Step::All => {
+                fanned_out = true;
+                current
+                    .into_iter()
+                    .flat_map(|value| match value {
+                        Value::Array(items) => items,
+                        Value::Object(map) => map.into_iter().map(|(_, value)| value).collect(),
+                        _ => Vec::new(),
+                    })
+                    .collect()
+            }
+        };
+    }
+    if fanned_out {
+        Ok(Value::Array(current))
+    } else {
+        Ok(current.pop().unwrap_or(Value::Null))
+    }
+}
+
+fn parse(selector: &str) -> Result<Vec<Step>> {
+    let trimmed = selector.trim();
+    let mut rest = trimmed.strip_prefix('$').unwrap_or(trimmed);
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after
+                .find(']')
+                .ok_or_else(|| anyhow!("unclosed `[` in selector `{selector}`"))?;
+            let inner = after[..end].trim();
+            steps.push(match inner {
+                "" | "*" => Step::All,
+                quoted if quoted.len() >= 2 && quoted.starts_with('"') && quoted.ends_with('"') => {
+                    Step::Key(quoted[1..quoted.len() - 1].to_string())
+                }
+                index => Step::Index(
+                    index
+                        .parse()
+                        .map_err(|_| anyhow!("invalid index `{index}` in selector `{selector}`"))?,
+                ),
+            });
+            rest = &after[end + 1..];
+            continue;
+        }
+        rest = rest.strip_prefix('.').unwrap_or(rest);
+        let end = rest.find(['.', '[']).unwrap_or(rest.len());
+        let segment = &rest[..end];
+        if segment.is_empty() {
+            if rest.is_empty() {
+                break;
+            }
+            if rest.starts_with('[') {
+                continue;
+            }
+            bail!("empty field name in selector `{selector}`");
+        }
+        steps.push(match segment {
+            "*" => Step::All,
+            digits if digits.bytes().all(|byte| byte.is_ascii_digit()) => {
+                Step::Index(digits.parse()?)
+            }
+            key => Step::Key(key.to_string()),
+        });
+        rest = &rest[end..];
+    }
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn selectors_pick_fields_indices_and_wildcards() {
+        let value = json!({
+            "data": [
+                {"gid": "1", "name": "Alpha", "owner": {"name": "Ann"}},
+                {"gid": "2", "name": "Beta", "owner": null}
+            ]
+        });
+        assert_eq!(select(&value, "data[0].name").unwrap(), json!("Alpha"));
+        assert_eq!(select(&value, ".data.1.gid").unwrap(), json!("2"));
+        assert_eq!(select(&value, "$.data[*].gid").unwrap(), json!(["1", "2"]));
+        assert_eq!(
+            select(&value, "data[].owner.name").unwrap(),
+            json!(["Ann", null])
+        );
+        assert_eq!(select(&value, r#"data[1]["name"]"#).unwrap(), json!("Beta"));
+        assert_eq!(select(&value, "").unwrap(), value);
+        assert!(select(&value, "data[x]").is_err());
+        assert!(select(&value, "data..gid").is_err());
+    }
+}
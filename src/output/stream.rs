@@ -0,0 +1,399 @@
+//! Incremental rendering for listings whose records arrive from a stream.
+//!
+//! Tables cannot measure their widest cell before the last record arrives, so
+//! streamed tables use fixed column widths and cut longer cells with `…`.
+//! Markdown pads to the same widths without cutting, and JSON arrays are laid
+//! out exactly as [`super::to_json`] lays out a complete listing.
+
+use crate::error::Result;
+use serde::Serialize;
+use std::{fmt::Write as _, time::SystemTime};
+use tabled::{Tabled, grid::util::string::get_text_width, settings::width::Truncate};
+
+/// Incremental writer for one listing format.
+#[derive(Debug)]
+pub enum ListWriter {
+    /// CSV records, with the header before the first.
+    Csv {
+        /// Records written so far.
+        rows_written: usize,
+    },
+    /// Table or markdown rows.
+    Table(TableRowWriter),
+    /// Elements of a JSON array of whole records.
+    Json(JsonArrayWriter),
+}
+
+impl ListWriter {
+    /// Writer for CSV records.
+    pub const fn csv() -> Self {
+        Self::Csv { rows_written: 0 }
+    }
+
+    /// Writer for table rows of `R` in `style`; see [`TableRowWriter::new`].
+    pub fn table<R: Tabled>(style: RowStyle, widths: &[usize]) -> Self {
+        Self::Table(TableRowWriter::new::<R>(style, widths))
+    }
+
+    /// Writer for a JSON array.
+    pub fn json() -> Self {
+        Self::Json(JsonArrayWriter::default())
+    }
+
+    /// Output for `record`, which tables and CSV show as `to_row(record)`.
+    ///
+    /// # Errors
+    /// Returns an error if JSON or CSV serialization fails.
+    pub fn push<T, R>(&mut self, record: &T, to_row: impl FnOnce(&T) -> R) -> Result<String>
+    where
+        T: Serialize,
+        R: Tabled + Serialize,
+    {
+        match self {
+            Self::Csv { rows_written } => {
+                let row = super::csv_row(&to_row(record), *rows_written == 0)?;
+                *rows_written += 1;
+                Ok(row)
+            }
+            Self::Table(writer) => Ok(writer.push(&to_row(record))),
+            Self::Json(writer) => Ok(writer.push(record)?),
+        }
+    }
+
+    /// Record stale cache data in the listing; only JSON marks it.
+    pub const fn note_stale(&mut self, stale_as_of: Option<SystemTime>) {
+        if let Self::Json(writer) = self {
+            writer.note_stale(stale_as_of);
+        }
+    }
+
+    /// Output closing the listing, if any.
+    ///
+    /// # Errors
+    /// Returns an error if JSON serialization fails.
+    pub fn finish(self) -> Result<Option<String>> {
+        match self {
+            Self::Csv { .. } => Ok(None),
+            Self::Table(writer) => Ok(writer.finish()),
+            Self::Json(writer) => Ok(Some(writer.finish()?)),
+        }
+    }
+}
+
+/// Border style for a streamed table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowStyle {
+    /// Rounded box with a rule under the header (interactive terminals).
+    Rounded,
+    /// Square box with a rule between every row (pipes).
+    Modern,
+    /// GitHub flavoured markdown.
+    Markdown,
+}
+
+impl RowStyle {
+    /// Box style for a table printed to a terminal or not.
+    pub const fn boxed(tty: bool) -> Self {
+        if tty { Self::Rounded } else { Self::Modern }
+    }
+
+    /// Left, junction, and right characters for a horizontal rule.
+    const fn rule(self, position: Rule) -> (char, char, char) {
+        match (self, position) {
+            (Self::Rounded, Rule::Top) => ('╭', '┬', '╮'),
+            (Self::Rounded, Rule::Bottom) => ('╰', '┴', '╯'),
+            (Self::Modern, Rule::Top) => ('┌', '┬', '┐'),
+            (Self::Modern, Rule::Bottom) => ('└', '┴', '┘'),
+            (Self::Markdown, _) => ('|', '|', '|'),
+            (_, Rule::Middle) => ('├', '┼', '┤'),
+        }
+    }
+
+    const fn vertical(self) -> char {
+        match self {
+            Self::Markdown => '|',
+            Self::Rounded | Self::Modern => '│',
+        }
+    }
+
+    const fn horizontal(self) -> char {
+        match self {
+            Self::Markdown => '-',
+            Self::Rounded | Self::Modern => '─',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// Writes table rows one at a time with fixed column widths.
+#[derive(Debug)]
+pub struct TableRowWriter {
+    style: RowStyle,
+    headers: Vec<String>,
+    widths: Vec<usize>,
+    rows_written: usize,
+}
+
+impl TableRowWriter {
+    /// Writer for rows of `R`, with each column at least as wide as `widths`
+    /// and its header.
+    pub fn new<R: Tabled>(style: RowStyle, widths: &[usize]) -> Self {
+        let headers: Vec<String> = R::headers().into_iter().map(String::from).collect();
+        let widths = headers
+            .iter()
+            .enumerate()
+            .map(|(index, header)| {
+                widths
+                    .get(index)
+                    .copied()
+                    .unwrap_or_default()
+                    .max(get_text_width(header))
+            })
+            .collect();
+        Self {
+            style,
+            headers,
+            widths,
+            rows_written: 0,
+        }
+    }
+
+    /// Lines for `row`, preceded by the header before the first row.
+    pub fn push<R: Tabled>(&mut self, row: &R) -> String {
+        let mut out = if self.rows_written == 0 {
+            self.header()
+        } else if self.style == RowStyle::Modern {
+            self.rule(Rule::Middle)
+        } else {
+            String::new()
+        };
+        let cells: Vec<String> = row.fields().into_iter().map(String::from).collect();
+        out.push_str(&self.line(&cells, false));
+        self.rows_written += 1;
+        out
+    }
+
+    /// Closing lines, or the header alone when no row was pushed.
+    pub fn finish(self) -> Option<String> {
+        let mut out = if self.rows_written == 0 {
+            self.header()
+        } else {
+            String::new()
+        };
+        if self.style != RowStyle::Markdown {
+            out.push_str(&self.rule(Rule::Bottom));
+        }
+        let out = out.trim_end_matches('\n');
+        (!out.is_empty()).then(|| out.to_string())
+    }
+
+    fn header(&self) -> String {
+        let mut out = String::new();
+        if self.style != RowStyle::Markdown {
+            out.push_str(&self.rule(Rule::Top));
+        }
+        out.push_str(&self.line(&self.headers, true));
+        out.push_str(&self.rule(Rule::Middle));
+        out
+    }
+
+    fn rule(&self, position: Rule) -> String {
+        let (left, junction, right) = self.style.rule(position);
+        let horizontal = self.style.horizontal().to_string();
+        let segments: Vec<String> = self
+            .widths
+            .iter()
+            .map(|width| horizontal.repeat(width + 2))
+            .collect();
+        format!("{left}{}{right}\n", segments.join(&junction.to_string()))
+    }
+
+    fn line(&self, cells: &[String], centered: bool) -> String {
+        let vertical = self.style.vertical();
+        let mut out = String::new();
+        out.push(vertical);
+        for (cell, width) in cells.iter().zip(&self.widths) {
+            let cell = cell.replace('\n', " ");
+            let cell = if self.style == RowStyle::Markdown {
+                cell
+            } else {
+                cut(&cell, *width)
+            };
+            let gap = width.saturating_sub(get_text_width(&cell));
+            let (before, after) = if centered {
+                (gap / 2, gap - gap / 2)
+            } else {
+                (0, gap)
+            };
+            let _ = write!(
+                out,
+                " {}{cell}{} {vertical}",
+                " ".repeat(before),
+                " ".repeat(after)
+            );
+        }
+        out.push('\n');
+        out
+    }
+}
+
+/// `text` cut to `width` columns, ending in `…` when anything was removed.
+fn cut(text: &str, width: usize) -> String {
+    if get_text_width(text) <= width {
+        return text.to_string();
+    }
+    format!("{}…", Truncate::truncate(text, width.saturating_sub(1)))
+}
+
+/// Writes a JSON array element by element.
+///
+/// Whether the listing is wrapped with stale-cache metadata is decided when
+/// the first element arrives, since the opening bracket is printed then, so
+/// staleness noted after that only shows up in the `meta` of a wrapped
+/// listing.
+#[derive(Debug, Default)]
+pub struct JsonArrayWriter {
+    items_written: usize,
+    wrapped: bool,
+    stale_as_of: Option<SystemTime>,
+}
+
+impl JsonArrayWriter {
+    /// Record that the listing so far includes stale cache data written at
+    /// `stale_as_of`, as reported by [`ApiClient::served_stale`](crate::api::ApiClient::served_stale).
+    pub const fn note_stale(&mut self, stale_as_of: Option<SystemTime>) {
+        if stale_as_of.is_some() {
+            self.stale_as_of = stale_as_of;
+        }
+    }
+
+    /// Text for `item`, preceded by the opening bracket or a separator.
+    pub fn push<T: Serialize>(&mut self, item: &T) -> serde_json::Result<String> {
+        let mut out = if self.items_written == 0 {
+            self.wrapped = self.stale_as_of.is_some();
+            if self.wrapped {
+                "{\n  \"data\": [\n".to_string()
+            } else {
+                "[\n".to_string()
+            }
+        } else {
+            ",\n".to_string()
+        };
+        let indent = if self.wrapped { 4 } else { 2 };
+        out.push_str(&indented(&serde_json::to_string_pretty(item)?, indent));
+        self.items_written += 1;
+        Ok(out)
+    }
+
+    /// Closing text, or the whole (empty) array when nothing was pushed.
+    pub fn finish(self) -> serde_json::Result<String> {
+        if self.items_written == 0 {
+            return Ok(super::mark_stale(
+                super::to_json(&[] as &[()])?,
+                self.stale_as_of,
+            ));
+        }
+        let Some(as_of) = self.stale_as_of.filter(|_| self.wrapped) else {
+            return Ok("\n]".to_string());
+        };
+        let meta = super::stale_meta(as_of);
+        Ok(format!(
+            "\n  ],\n  \"meta\": {}\n}}",
+            indented(&serde_json::to_string_pretty(&meta)?, 2).trim_start()
+        ))
+    }
+}
+
+fn indented(text: &str, spaces: usize) -> String {
+    let pad = " ".repeat(spaces);
+    text.lines()
+        .map(|line| format!("{pad}{line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Tabled)]
+    struct Row {
+        #[tabled(rename = "GID")]
+        gid: &'static str,
+        #[tabled(rename = "Name")]
+        name: &'static str,
+    }
+
+    #[test]
+    fn streamed_json_matches_the_whole_listing() {
+        let items = [json!({ "gid": "1", "tags": ["a"] }), json!({ "gid": "2" })];
+        let mut writer = JsonArrayWriter::default();
+        let mut streamed = String::new();
+        for item in &items {
+            streamed.push_str(&writer.push(item).expect("push"));
+        }
+        streamed.push_str(&writer.finish().expect("finish"));
+        assert_eq!(
+            streamed,
+            serde_json::to_string_pretty(&items).expect("json")
+        );
+        let empty = JsonArrayWriter::default().finish().expect("finish");
+        assert_eq!(empty, "[]");
+    }
+
+    #[test]
+    fn streamed_json_from_stale_data_matches_the_marked_listing() {
+        let items = [json!({ "gid": "1", "tags": ["a"] }), json!({ "gid": "2" })];
+        let as_of = Some(SystemTime::UNIX_EPOCH);
+        let mut writer = JsonArrayWriter::default();
+        writer.note_stale(as_of);
+        let mut streamed = String::new();
+        for item in &items {
+            streamed.push_str(&writer.push(item).expect("push"));
+        }
+        streamed.push_str(&writer.finish().expect("finish"));
+        let whole = crate::output::mark_stale(crate::output::to_json(&items).expect("json"), as_of);
+        assert_eq!(streamed, whole);
+        let parsed: serde_json::Value = serde_json::from_str(&whole).expect("valid JSON");
+        assert_eq!(parsed["data"], json!(items));
+        assert_eq!(parsed["meta"]["stale"], json!(true));
+    }
+
+    #[test]
+    fn streamed_tables_use_fixed_widths() {
+        let mut writer = TableRowWriter::new::<Row>(RowStyle::Rounded, &[3, 6]);
+        let mut table = writer.push(&Row {
+            gid: "1",
+            name: "Short",
+        });
+        table.push_str(&writer.push(&Row {
+            gid: "22",
+            name: "Much longer name",
+        }));
+        table.push_str(&writer.finish().expect("bottom"));
+        assert_eq!(
+            table,
+            "╭─────┬────────╮\n\
+             │ GID │  Name  │\n\
+             ├─────┼────────┤\n\
+             │ 1   │ Short  │\n\
+             │ 22  │ Much … │\n\
+             ╰─────┴────────╯"
+        );
+
+        let mut writer = TableRowWriter::new::<Row>(RowStyle::Markdown, &[3, 6]);
+        let row = writer.push(&Row {
+            gid: "1",
+            name: "Much longer name",
+        });
+        assert!(row.ends_with("| 1   | Much longer name |\n"), "{row}");
+        assert!(writer.finish().is_none());
+    }
+}
@@ -3,11 +3,15 @@
 use crate::{
     error::Result,
     models::{CustomField, Task, UserReference},
-    output::TaskOutputFormat,
+    output::{
+        TaskOutputFormat,
+        stream::{ListWriter, RowStyle},
+    },
 };
 use anyhow::Context;
 use csv::WriterBuilder;
 use serde::Serialize;
+use std::{collections::BTreeSet, time::SystemTime};
 use tabled::{
     Table, Tabled,
     settings::{Alignment, Modify, Style, object::Rows},
@@ -43,6 +47,97 @@ fn format_user_with_email(user: &UserReference) -> String {
     }
 }
 
+/// Fields read by [`TaskRow`]; label helpers fall back from name to email.
+const TASK_ROW_FIELDS: &[&str] = &[
+    "name",
+    "completed",
+    "due_on",
+    "assignee.name",
+    "assignee.email",
+    "projects.name",
+];
+
+/// Column widths for streamed task tables, in [`TaskRow`] order.
+const TASK_ROW_WIDTHS: &[usize] = &[19, 48, 4, 10, 24, 24];
+
+/// Fields read by the basic and collection sections of the detail views.
+const TASK_DETAIL_FIELDS: &[&str] = &[
+    "name",
+    "completed",
+    "completed_at",
+    "assignee.name",
+    "assignee.email",
+    "workspace.name",
+    "due_on",
+    "due_at",
+    "start_on",
+    "start_at",
+    "parent.name",
+    "projects.name",
+    "tags.name",
+    "followers.name",
+    "followers.email",
+    "dependencies.name",
+    "dependents.name",
+    "external.gid",
+];
+
+/// Fields read by [`custom_field_display`].
+const CUSTOM_FIELD_FIELDS: &[&str] = &[
+    "custom_fields.name",
+    "custom_fields.type",
+    "custom_fields.display_value",
+    "custom_fields.text_value",
+    "custom_fields.number_value",
+    "custom_fields.enum_value.name",
+    "custom_fields.multi_enum_values.name",
+    "custom_fields.date_value",
+];
+
+/// Fields read only by the detail table.
+const TASK_DETAIL_TABLE_FIELDS: &[&str] = &[
+    "permalink_url",
+    "notes",
+    "html_notes",
+    "attachments.name",
+    "attachments.permanent_url",
+];
+
+/// `opt_fields` needed to render a task list in `format`.
+///
+/// JSON shows whole records, so it asks for [`Task::LIST_FIELDS`].
+#[must_use]
+pub fn task_list_fields(format: TaskOutputFormat) -> BTreeSet<String> {
+    let fields = match format {
+        TaskOutputFormat::Json => Task::LIST_FIELDS,
+        TaskOutputFormat::Table | TaskOutputFormat::Csv | TaskOutputFormat::Markdown => {
+            TASK_ROW_FIELDS
+        }
+    };
+    fields.iter().map(ToString::to_string).collect()
+}
+
+/// `opt_fields` needed to render a single task in `format`.
+///
+/// JSON shows the whole record, so it asks for [`Task::DETAIL_FIELDS`].
+#[must_use]
+pub fn task_detail_fields(format: TaskOutputFormat) -> BTreeSet<String> {
+    let sections: &[&[&str]] = match format {
+        TaskOutputFormat::Json => &[Task::DETAIL_FIELDS],
+        TaskOutputFormat::Csv => &[TASK_DETAIL_FIELDS, CUSTOM_FIELD_FIELDS],
+        TaskOutputFormat::Table | TaskOutputFormat::Markdown => &[
+            TASK_DETAIL_FIELDS,
+            CUSTOM_FIELD_FIELDS,
+            TASK_DETAIL_TABLE_FIELDS,
+        ],
+    };
+    sections
+        .iter()
+        .flat_map(|fields| fields.iter())
+        .map(ToString::to_string)
+        .collect()
+}
+
 /// Render a collection of tasks in the requested format.
 ///
 /// # Errors
@@ -50,7 +145,7 @@ fn format_user_with_email(user: &UserReference) -> String {
 /// Returns an error if serialization fails.
 pub fn render_task_list(tasks: &[Task], format: TaskOutputFormat, tty: bool) -> Result<String> {
     match format {
-        TaskOutputFormat::Json => Ok(serde_json::to_string_pretty(tasks)?),
+        TaskOutputFormat::Json => Ok(super::to_json(tasks)?),
         TaskOutputFormat::Csv => render_task_list_csv(tasks),
         TaskOutputFormat::Markdown => Ok(render_task_list_table(tasks, TableStyleKind::Markdown)),
         TaskOutputFormat::Table => {
@@ -64,6 +159,59 @@ pub fn render_task_list(tasks: &[Task], format: TaskOutputFormat, tty: bool) ->
     }
 }
 
+/// Renders a task listing as tasks arrive from a stream.
+///
+/// Every format is written as tasks are pushed: CSV and markdown rows, table
+/// rows at fixed column widths (longer cells are cut with `…`), and JSON as
+/// the elements of an array.
+#[derive(Debug)]
+pub struct TaskListRenderer {
+    writer: ListWriter,
+}
+
+impl TaskListRenderer {
+    /// Create a renderer for `format`; `tty` selects the table style.
+    #[must_use]
+    pub fn new(format: TaskOutputFormat, tty: bool) -> Self {
+        let writer = match format {
+            TaskOutputFormat::Json => ListWriter::json(),
+            TaskOutputFormat::Csv => ListWriter::csv(),
+            TaskOutputFormat::Markdown => {
+                ListWriter::table::<TaskRow>(RowStyle::Markdown, TASK_ROW_WIDTHS)
+            }
+            TaskOutputFormat::Table => {
+                ListWriter::table::<TaskRow>(RowStyle::boxed(tty), TASK_ROW_WIDTHS)
+            }
+        };
+        Self { writer }
+    }
+
+    /// Add a task, returning output that can be printed right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn push(&mut self, task: &Task) -> Result<Option<String>> {
+        self.writer.push(task, |task| TaskRow::from(task)).map(Some)
+    }
+
+    /// Record that the listing so far includes stale cache data written at
+    /// `stale_as_of`. JSON output is only wrapped with the stale-cache `meta`
+    /// when this was noted before the first push.
+    pub const fn note_stale(&mut self, stale_as_of: Option<SystemTime>) {
+        self.writer.note_stale(stale_as_of);
+    }
+
+    /// Output closing the listing (the table border or JSON bracket), if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn finish(self) -> Result<Option<String>> {
+        self.writer.finish()
+    }
+}
+
 fn render_task_list_table(tasks: &[Task], style: TableStyleKind) -> String {
     let rows: Vec<TaskRow> = tasks.iter().map(TaskRow::from).collect();
     let mut table = Table::new(rows);
@@ -90,7 +238,7 @@ fn render_task_list_csv(tasks: &[Task]) -> Result<String> {
 /// Returns an error if serialization fails.
 pub fn render_task_detail(task: &Task, format: TaskOutputFormat, tty: bool) -> Result<String> {
     match format {
-        TaskOutputFormat::Json => Ok(serde_json::to_string_pretty(task)?),
+        TaskOutputFormat::Json => Ok(super::to_json(task)?),
         TaskOutputFormat::Csv => render_task_detail_csv(task),
         TaskOutputFormat::Markdown => Ok(render_task_detail_table(task, TableStyleKind::Markdown)),
         TaskOutputFormat::Table => {
@@ -116,6 +264,9 @@ fn add_basic_task_rows(rows: &mut Vec<KeyValueRow>, task: &Task) {
     rows.push(KeyValueRow::new("GID", &task.gid));
     rows.push(KeyValueRow::new("Name", &task.name));
     rows.push(KeyValueRow::new("Completed", task.completed.to_string()));
+    if let Some(external) = task.external.as_ref() {
+        rows.push(KeyValueRow::new("External ID", &external.gid));
+    }
     if let Some(completed_at) = task.completed_at.as_ref() {
         rows.push(KeyValueRow::new("Completed At", completed_at));
     }
@@ -249,6 +400,9 @@ fn render_task_detail_csv(task: &Task) -> Result<String> {
     push("gid", &task.gid)?;
     push("name", &task.name)?;
     push("completed", &task.completed.to_string())?;
+    if let Some(external) = task.external.as_ref() {
+        push("external_id", &external.gid)?;
+    }
     if let Some(completed_at) = task.completed_at.as_ref() {
         push("completed_at", completed_at)?;
     }
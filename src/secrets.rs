@@ -0,0 +1,316 @@
+//! Passphrase encryption for tokens stored in the configuration file.
+//!
+//! Sealed tokens use a key derived from the passphrase with Argon2id and are
+//! encrypted with XChaCha20-Poly1305, so a modified or truncated value fails
+//! to open instead of yielding a wrong token. The encoded form is
+//! `v1$<salt>$<nonce>$<ciphertext>` with unpadded base64 fields.
+
+use crate::error::Result;
+use anyhow::{Context, anyhow};
+use argon2::Argon2;
+use base64::{Engine as _, engine::general_purpose::STANDARD_NO_PAD};
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
+use secrecy::{ExposeSecret, SecretString};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+use zeroize::Zeroizing;
+
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+const FORMAT_VERSION: &str = "v1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// Key derived from a passphrase for one sealed token.
+pub struct UnlockKey(Zeroizing<[u8; KEY_LEN]>);
+
+/// Encrypted token as stored in `config.toml`.
+#[derive(Debug, Clone)]
+pub struct SealedToken {
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Vec<u8>,
+}
+
+impl SealedToken {
+    /// Encrypt `token` under a key derived from `passphrase`.
+    ///
+    /// # Errors
+    /// Returns an error if randomness or key derivation fails.
+    pub fn seal(token: &SecretString, passphrase: &SecretString) -> Result<Self> {
+        let mut salt = [0_u8; SALT_LEN];
+        let mut nonce = [0_u8; NONCE_LEN];
+        getrandom::fill(&mut salt).map_err(|err| anyhow!("failed to generate salt: {err}"))?;
+        getrandom::fill(&mut nonce).map_err(|err| anyhow!("failed to generate nonce: {err}"))?;
+
+        let key = derive_key(passphrase, &salt)?;
+        let ciphertext = cipher(&key)
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: token.expose_secret().as_bytes(),
+                    aad: FORMAT_VERSION.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow!("failed to encrypt token"))?;
+        Ok(Self {
+            salt,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Parse the encoded form written by [`SealedToken::encode`].
+    ///
+    /// # Errors
+    /// Returns an error if the value is not a supported sealed token.
+    pub fn parse(encoded: &str) -> Result<Self> {
+        let mut parts = encoded.trim().split('$');
+        let (Some(version), Some(salt), Some(nonce), Some(ciphertext), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(anyhow!("encrypted token is malformed"));
+        };
+        if version != FORMAT_VERSION {
+            return Err(anyhow!("unsupported encrypted token format `{version}`"));
+        }
+        let decode = |field: &str| {
+            STANDARD_NO_PAD
+                .decode(field)
+                .context("encrypted token is malformed")
+        };
+        Ok(Self {
+            salt: decode(salt)?
+                .try_into()
+                .map_err(|_| anyhow!("encrypted token salt has the wrong length"))?,
+            nonce: decode(nonce)?
+                .try_into()
+                .map_err(|_| anyhow!("encrypted token nonce has the wrong length"))?,
+            ciphertext: decode(ciphertext)?,
+        })
+    }
+
+    /// Encode for storage in the configuration file.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        format!(
+            "{FORMAT_VERSION}${}${}${}",
+            STANDARD_NO_PAD.encode(self.salt),
+            STANDARD_NO_PAD.encode(self.nonce),
+            STANDARD_NO_PAD.encode(&self.ciphertext)
+        )
+    }
+
+    /// Derive the key that opens this token.
+    ///
+    /// # Errors
+    /// Returns an error if key derivation fails.
+    pub fn derive_key(&self, passphrase: &SecretString) -> Result<UnlockKey> {
+        derive_key(passphrase, &self.salt)
+    }
+
+    /// Decrypt the token.
+    ///
+    /// # Errors
+    /// Returns an error if the key is wrong or the value was tampered with.
+    pub fn open(&self, key: &UnlockKey) -> Result<SecretString> {
+        let plaintext = Zeroizing::new(
+            cipher(key)
+                .decrypt(
+                    &XNonce::from(self.nonce),
+                    Payload {
+                        msg: &self.ciphertext,
+                        aad: FORMAT_VERSION.as_bytes(),
+                    },
+                )
+                .map_err(|_| anyhow!("wrong passphrase or corrupted encrypted token"))?,
+        );
+        let token = std::str::from_utf8(&plaintext).context("decrypted token is not UTF-8")?;
+        Ok(SecretString::new(token.into()))
+    }
+}
+
+fn derive_key(passphrase: &SecretString, salt: &[u8]) -> Result<UnlockKey> {
+    let mut key = Zeroizing::new([0_u8; KEY_LEN]);
+    Argon2::default()
+        .hash_password_into(passphrase.expose_secret().as_bytes(), salt, key.as_mut())
+        .map_err(|err| anyhow!("failed to derive key from passphrase: {err}"))?;
+    Ok(UnlockKey(key))
+}
+
+fn cipher(key: &UnlockKey) -> XChaCha20Poly1305 {
+    XChaCha20Poly1305::new(key.0.as_ref().into())
+}
+
+/// Short-lived cache of an unlock key, so the passphrase is not requested on
+/// every invocation.
+///
+/// The key is written to a `0600` file under the runtime directory (usually a
+/// per-user tmpfs) together with its expiry, and ignored once it expires.
+#[derive(Debug, Clone)]
+pub struct UnlockCache {
+    path: PathBuf,
+}
+
+impl UnlockCache {
+    /// Cache for `profile` inside `runtime_dir`.
+    #[must_use]
+    pub fn new(runtime_dir: &Path, profile: &str) -> Self {
+        Self {
+            path: runtime_dir.join(format!("unlock-{profile}")),
+        }
+    }
+
+    /// Cached key, if one exists and has not expired.
+    #[must_use]
+    pub fn load(&self) -> Option<UnlockKey> {
+        let contents = Zeroizing::new(fs::read_to_string(&self.path).ok()?);
+        let (expires_at, key) = contents.trim().split_once(' ')?;
+        if expires_at.parse::<u64>().ok()? <= unix_now() {
+            self.clear();
+            return None;
+        }
+        let bytes = Zeroizing::new(STANDARD_NO_PAD.decode(key).ok()?);
+        let key: [u8; KEY_LEN] = bytes.as_slice().try_into().ok()?;
+        Some(UnlockKey(Zeroizing::new(key)))
+    }
+
+    /// Remember `key` for `ttl`.
+    ///
+    /// # Errors
+    /// Returns an error if the cache file cannot be written.
+    pub fn store(&self, key: &UnlockKey, ttl: Duration) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+            #[cfg(unix)]
+            fs::set_permissions(parent, fs::Permissions::from_mode(0o700))
+                .with_context(|| format!("failed to secure {}", parent.display()))?;
+        }
+        let contents = Zeroizing::new(format!(
+            "{} {}",
+            unix_now().saturating_add(ttl.as_secs()),
+            STANDARD_NO_PAD.encode(key.0.as_ref())
+        ));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options
+            .open(&self.path)
+            .with_context(|| format!("failed to write {}", self.path.display()))?;
+        std::io::Write::write_all(&mut file, contents.as_bytes())
+            .with_context(|| format!("failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// Forget the cached key.
+    pub fn clear(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Decrypt `sealed`, reusing a cached unlock key when available and asking
+/// `prompt` for the passphrase otherwise. Keys obtained from a passphrase are
+/// cached for `ttl` when a cache is given.
+///
+/// # Errors
+/// Returns an error if the prompt fails or the passphrase is wrong.
+pub fn unlock_token<F>(
+    sealed: &SealedToken,
+    cache: Option<(&UnlockCache, Duration)>,
+    prompt: F,
+) -> Result<SecretString>
+where
+    F: FnOnce() -> Result<SecretString>,
+{
+    if let Some((cache, _)) = cache {
+        if let Some(key) = cache.load() {
+            if let Ok(token) = sealed.open(&key) {
+                return Ok(token);
+            }
+            // The token was re-encrypted since the key was cached.
+            cache.clear();
+        }
+    }
+
+    let key = sealed.derive_key(&prompt()?)?;
+    let token = sealed.open(&key)?;
+    if let Some((cache, ttl)) = cache {
+        if let Err(err) = cache.store(&key, ttl) {
+            warn!("failed to cache token unlock: {err:#}");
+        }
+    }
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sealed_tokens_round_trip_and_reject_wrong_passphrases() {
+        let token = SecretString::new("pat-value".into());
+        let passphrase = SecretString::new("correct horse".into());
+        let sealed = SealedToken::seal(&token, &passphrase).expect("seal");
+
+        let parsed = SealedToken::parse(&sealed.encode()).expect("parse");
+        let key = parsed.derive_key(&passphrase).expect("derive");
+        assert_eq!(
+            parsed.open(&key).expect("open").expose_secret(),
+            "pat-value"
+        );
+
+        let wrong = parsed
+            .derive_key(&SecretString::new("battery staple".into()))
+            .expect("derive");
+        assert!(parsed.open(&wrong).is_err());
+        assert!(SealedToken::parse("v2$a$b$c").is_err());
+    }
+
+    #[test]
+    fn unlock_cache_skips_the_prompt_until_expiry() {
+        let runtime = TempDir::new().expect("runtime dir");
+        let cache = UnlockCache::new(runtime.path(), "default");
+        let passphrase = SecretString::new("secret".into());
+        let sealed =
+            SealedToken::seal(&SecretString::new("pat".into()), &passphrase).expect("seal");
+        let prompts = Cell::new(0);
+        let prompt = || {
+            prompts.set(prompts.get() + 1);
+            Ok(passphrase.clone())
+        };
+
+        let ttl = Duration::from_secs(60);
+        unlock_token(&sealed, Some((&cache, ttl)), prompt).expect("first unlock");
+        let token = unlock_token(&sealed, Some((&cache, ttl)), prompt).expect("cached unlock");
+        assert_eq!(token.expose_secret(), "pat");
+        assert_eq!(prompts.get(), 1);
+
+        cache.clear();
+        unlock_token(&sealed, Some((&cache, Duration::ZERO)), prompt).expect("unlock");
+        assert_eq!(prompts.get(), 2);
+        assert!(cache.load().is_none(), "expired unlock is discarded");
+    }
+}
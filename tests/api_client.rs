@@ -1,11 +1,23 @@
 //! Integration coverage for the asynchronous API client.
 
-use asana_cli::api::{ApiClient, ApiError, AuthToken, ListResponse};
-use futures_util::StreamExt;
+use asana_cli::api::{
+    AccessTokenProvider, ApiClient, ApiClientBuilder, ApiError, AsanaApi, AuthToken, ListResponse,
+    TokenProvider, TransferProgress, create_tasks_batch, get_task, get_task_with_exact_fields,
+    list_tasks_stream, upload_attachment, upload_attachment_with_progress,
+};
+use asana_cli::models::{AttachmentUploadParams, Task, TaskCreateBuilder, TaskListParams};
+use futures_util::{StreamExt, future::BoxFuture};
 use mockito::{Matcher, Server};
 use secrecy::SecretString;
 use serde::Deserialize;
 use serde_json::Value;
+use std::{
+    io::{Read, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 use tempfile::TempDir;
 use tokio::time::Duration;
 
@@ -78,6 +90,92 @@ async fn paginate_workspaces_streams_all_pages() {
     }
 }
 
+#[tokio::test]
+async fn task_stream_yields_filtered_items_across_pages() {
+    let mut server = Server::new_async().await;
+    let _first_page = server
+        .mock("GET", "/tasks")
+        .match_query(Matcher::UrlEncoded("project".into(), "P1".into()))
+        .with_status(200)
+        .with_body(
+            r#"{
+                "data": [
+                    { "gid": "1", "name": "Open", "completed": false },
+                    { "gid": "2", "name": "Done", "completed": true }
+                ],
+                "next_page": { "offset": "page-2", "path": "/tasks" }
+            }"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let _second_page = server
+        .mock("GET", "/tasks")
+        .match_query(Matcher::UrlEncoded("offset".into(), "page-2".into()))
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "3", "name": "Later", "completed": false }] }"#)
+        .create_async()
+        .await;
+
+    let cache = TempDir::new().expect("temporary cache dir");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("stream-token".into())))
+        .base_url(server.url())
+        .cache_dir(cache.path().join("cache"))
+        .build()
+        .expect("client initialises");
+
+    let params = TaskListParams {
+        project: Some("P1".into()),
+        completed: Some(false),
+        ..TaskListParams::default()
+    };
+    let stream = list_tasks_stream(&client, params);
+    tokio::pin!(stream);
+
+    let first = stream.next().await.expect("first item").expect("task");
+    assert_eq!(first.gid, "1");
+    let rest: Vec<String> = stream.map(|task| task.expect("task").gid).collect().await;
+    assert_eq!(rest, vec!["3".to_string()]);
+}
+
+#[tokio::test]
+async fn get_task_adds_fields_to_the_defaults_unless_asked_for_exact_fields() {
+    let mut server = Server::new_async().await;
+    let mut defaults = Task::DETAIL_FIELDS.to_vec();
+    defaults.sort_unstable();
+    let defaults = defaults.join(",");
+    let additive = server
+        .mock("GET", "/tasks/7")
+        .match_query(Matcher::UrlEncoded("opt_fields".into(), defaults))
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "7", "name": "Seven" } }"#)
+        .create_async()
+        .await;
+    let exact = server
+        .mock("GET", "/tasks/7")
+        .match_query(Matcher::UrlEncoded("opt_fields".into(), "gid,name".into()))
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "7", "name": "Seven" } }"#)
+        .create_async()
+        .await;
+
+    let cache = TempDir::new().expect("temporary cache dir");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("fields-token".into())))
+        .base_url(server.url())
+        .cache_dir(cache.path().join("cache"))
+        .build()
+        .expect("client initialises");
+
+    get_task(&client, "7", vec!["gid".into()])
+        .await
+        .expect("task with default fields");
+    get_task_with_exact_fields(&client, "7", vec!["gid".into()])
+        .await
+        .expect("task with exact fields");
+    additive.assert_async().await;
+    exact.assert_async().await;
+}
+
 #[tokio::test]
 async fn rate_limit_recovers_after_retry() {
     {
@@ -167,6 +265,396 @@ async fn rate_limit_failure_surfaces_retry_after() {
     }
 }
 
+#[derive(Default)]
+struct CountingProgress {
+    starts: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl TransferProgress for CountingProgress {
+    fn start(&self, position: u64, _total: Option<u64>) {
+        self.starts.fetch_add(1, Ordering::Relaxed);
+        self.bytes.store(position, Ordering::Relaxed);
+    }
+
+    fn advance(&self, bytes: u64) {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn finish(&self) {}
+}
+
+#[tokio::test]
+async fn uploads_share_retry_and_error_mapping() {
+    let mut server = Server::new_async().await;
+    let unavailable = server
+        .mock("POST", "/tasks/1/attachments")
+        .with_status(503)
+        .with_body("try later")
+        .expect(3)
+        .create_async()
+        .await;
+    let _denied = server
+        .mock("POST", "/tasks/2/attachments")
+        .with_status(401)
+        .with_body("bad token")
+        .create_async()
+        .await;
+
+    let cache = TempDir::new().expect("temporary cache dir");
+    let file = cache.path().join("notes.txt");
+    std::fs::write(&file, "attachment body").expect("write upload");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("upload-token".into())))
+        .base_url(server.url())
+        .cache_dir(cache.path().join("cache"))
+        .retry_base_delay(Duration::from_millis(1))
+        .max_retries(2)
+        .build()
+        .expect("client initialises");
+
+    let progress = Arc::new(CountingProgress::default());
+    let params = |task: &str| AttachmentUploadParams {
+        task_gid: task.to_string(),
+        file_path: file.clone(),
+        name: None,
+    };
+    let err = upload_attachment_with_progress(
+        &client,
+        params("1"),
+        Some(progress.clone() as Arc<dyn TransferProgress>),
+    )
+    .await
+    .expect_err("server stays unavailable");
+    assert!(matches!(err, ApiError::Http { status, .. } if status.as_u16() == 503));
+    unavailable.assert_async().await;
+    assert_eq!(progress.starts.load(Ordering::Relaxed), 3);
+
+    let err = upload_attachment(&client, params("2"))
+        .await
+        .expect_err("token rejected");
+    assert!(matches!(err, ApiError::Authentication(body) if body == "bad token"));
+}
+
+#[tokio::test]
+async fn downloads_resume_partial_files() {
+    let mut server = Server::new_async().await;
+    let _rest = server
+        .mock("GET", "/files/report.csv")
+        .match_header("authorization", "Bearer download-token")
+        .match_header("range", "bytes=4-")
+        .match_header("if-range", "\"v1\"")
+        .with_status(206)
+        .with_body("1,2\n")
+        .create_async()
+        .await;
+    let _changed = server
+        .mock("GET", "/files/report.csv")
+        .match_header("if-range", "\"old\"")
+        .with_status(200)
+        .with_header("etag", "\"v2\"")
+        .with_body("c,d\n3,4\n")
+        .create_async()
+        .await;
+
+    let dir = TempDir::new().expect("temporary dir");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("download-token".into())))
+        .base_url(server.url())
+        .cache_dir(dir.path().join("cache"))
+        .build()
+        .expect("client initialises");
+
+    let destination = dir.path().join("report.csv");
+    write_partial_download(&dir, "report.csv", "\"v1\"");
+    let progress = CountingProgress::default();
+    let url = format!("{}/files/report.csv", server.url());
+    let written = client
+        .download_file_to(&url, &destination, Some(8), Some(&progress))
+        .await
+        .expect("download succeeds");
+    assert_eq!(written, 8);
+    assert_eq!(progress.bytes.load(Ordering::Relaxed), 8);
+    assert_eq!(
+        std::fs::read_to_string(&destination).expect("read download"),
+        "a,b\n1,2\n"
+    );
+    assert!(!dir.path().join("report.csv.part").exists());
+    assert!(!dir.path().join("report.csv.part.validator").exists());
+
+    // The file changed since the partial download, so it is fetched again.
+    write_partial_download(&dir, "changed.csv", "\"old\"");
+    client
+        .download_file_to(&url, &dir.path().join("changed.csv"), Some(8), None)
+        .await
+        .expect("download restarts");
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("changed.csv")).expect("read download"),
+        "c,d\n3,4\n"
+    );
+
+    write_partial_download(&dir, "short.csv", "\"v1\"");
+    let err = client
+        .download_file_to(&url, &dir.path().join("short.csv"), Some(20), None)
+        .await
+        .expect_err("size mismatch");
+    assert!(err.to_string().contains("expected 20"));
+    assert!(!dir.path().join("short.csv").exists());
+
+    let _whole = server
+        .mock("GET", "/files/report.csv")
+        .match_header("range", Matcher::Missing)
+        .with_status(200)
+        .with_body("c,d\n3,4\n")
+        .create_async()
+        .await;
+    let bytes = client.download_file(&url).await.expect("download succeeds");
+    assert_eq!(bytes, b"c,d\n3,4\n");
+}
+
+fn write_partial_download(dir: &TempDir, name: &str, validator: &str) {
+    std::fs::write(dir.path().join(format!("{name}.part")), "a,b\n").expect("write partial");
+    std::fs::write(dir.path().join(format!("{name}.part.validator")), validator)
+        .expect("write validator");
+}
+
+#[tokio::test]
+async fn interrupted_downloads_resume_with_if_range() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let port = listener.local_addr().expect("address").port();
+    // The first response is cut off after four of its eight bytes.
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in [
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 8\r\n\r\na,b\n",
+            "HTTP/1.1 206 Partial Content\r\nContent-Length: 4\r\n\r\n1,2\n",
+        ] {
+            let (mut socket, _) = listener.accept().expect("accept");
+            let mut request = [0u8; 4096];
+            let read = socket.read(&mut request).expect("read request");
+            requests.push(String::from_utf8_lossy(&request[..read]).to_lowercase());
+            socket
+                .write_all(response.as_bytes())
+                .expect("write response");
+        }
+        requests
+    });
+
+    let dir = TempDir::new().expect("temporary dir");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("download-token".into())))
+        .cache_dir(dir.path().join("cache"))
+        .retry_base_delay(Duration::from_millis(1))
+        .build()
+        .expect("client initialises");
+    let destination = dir.path().join("report.csv");
+    let written = client
+        .download_file_to(
+            &format!("http://127.0.0.1:{port}/files/report.csv"),
+            &destination,
+            Some(8),
+            None,
+        )
+        .await
+        .expect("download resumes");
+    assert_eq!(written, 8);
+    assert_eq!(
+        std::fs::read_to_string(&destination).expect("read download"),
+        "a,b\n1,2\n"
+    );
+
+    let requests = server.join().expect("server thread");
+    assert!(!requests[0].contains("range:"), "{}", requests[0]);
+    assert!(requests[1].contains("range: bytes=4-"), "{}", requests[1]);
+    assert!(requests[1].contains("if-range: \"v1\""), "{}", requests[1]);
+}
+
+#[tokio::test]
+async fn batch_creates_rejected_as_duplicates_return_the_existing_task() {
+    let mut server = Server::new_async().await;
+    let _missing = server
+        .mock("GET", "/tasks/external:row%2F1")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_body(r#"{ "errors": [{ "message": "Unknown object" }] }"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let _found = server
+        .mock("GET", "/tasks/external:row%2F1")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "T1", "name": "Row one" } }"#)
+        .create_async()
+        .await;
+    // The batch was already applied by an attempt whose response was lost.
+    let _batch = server
+        .mock("POST", "/batch")
+        .with_status(200)
+        .with_body(
+            r#"{ "data": [ { "status_code": 400,
+                "body": { "errors": [{ "message": "external.gid is already in use" }] } } ] }"#,
+        )
+        .create_async()
+        .await;
+
+    let dir = TempDir::new().expect("temporary dir");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("batch-token".into())))
+        .base_url(server.url())
+        .cache_dir(dir.path().join("cache"))
+        .build()
+        .expect("client initialises");
+    let request = TaskCreateBuilder::new("Row one")
+        .workspace("ws-1")
+        .external_id("row/1")
+        .build()
+        .expect("valid request");
+    let results = create_tasks_batch(&client, vec![request])
+        .await
+        .expect("batch request");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().expect("existing task").gid, "T1");
+}
+
+struct RotatingProvider {
+    current: std::sync::Mutex<&'static str>,
+}
+
+impl AccessTokenProvider for RotatingProvider {
+    fn access_token(&self) -> BoxFuture<'_, Result<AuthToken, ApiError>> {
+        let token = *self.current.lock().expect("token lock");
+        Box::pin(async move { Ok(AuthToken::new(SecretString::new(token.into()))) })
+    }
+
+    fn refresh<'a>(&'a self, _rejected: &'a AuthToken) -> BoxFuture<'a, Result<bool, ApiError>> {
+        *self.current.lock().expect("token lock") = "fresh-token";
+        Box::pin(async { Ok(true) })
+    }
+}
+
+#[tokio::test]
+async fn rejected_tokens_are_refreshed_once() {
+    let mut server = Server::new_async().await;
+    let rejected = server
+        .mock("GET", "/users/me")
+        .match_header("authorization", "Bearer expired-token")
+        .with_status(401)
+        .expect(1)
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("GET", "/users/me")
+        .match_header("authorization", "Bearer fresh-token")
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "1", "name": "Refreshed" } }"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let cache = TempDir::new().expect("temporary cache dir");
+    let provider = Arc::new(RotatingProvider {
+        current: std::sync::Mutex::new("expired-token"),
+    });
+    let client = ApiClientBuilder::with_token_provider(provider)
+        .base_url(server.url())
+        .cache_dir(cache.path().join("cache"))
+        .build()
+        .expect("client initialises");
+
+    let user: Value = client
+        .get_current_user()
+        .await
+        .expect("request succeeds after refresh");
+    assert_eq!(user["data"]["name"], "Refreshed");
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+}
+
+struct LegacyProvider;
+
+impl TokenProvider for LegacyProvider {
+    fn personal_access_token(&self) -> SecretString {
+        SecretString::new("legacy-token".into())
+    }
+}
+
+#[tokio::test]
+async fn personal_access_token_providers_authenticate_requests() {
+    let mut server = Server::new_async().await;
+    let accepted = server
+        .mock("GET", "/users/me")
+        .match_header("authorization", "Bearer legacy-token")
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "1", "name": "Legacy" } }"#)
+        .create_async()
+        .await;
+
+    let cache = TempDir::new().expect("temporary cache dir");
+    let client = ApiClientBuilder::with_token_provider(Arc::new(LegacyProvider))
+        .base_url(server.url())
+        .cache_dir(cache.path().join("cache"))
+        .build()
+        .expect("client initialises");
+
+    let user: Value = client.get_current_user().await.expect("request succeeds");
+    assert_eq!(user["data"]["name"], "Legacy");
+    accepted.assert_async().await;
+}
+
+#[tokio::test]
+async fn http_trace_records_har_entries() {
+    let mut server = Server::new_async().await;
+    let _failure = server
+        .mock("GET", "/users/me")
+        .with_status(503)
+        .with_body("unavailable")
+        .expect(1)
+        .create_async()
+        .await;
+    let _success = server
+        .mock("GET", "/users/me")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{ "data": { "gid": "1", "name": "Traced" } }"#)
+        .create_async()
+        .await;
+
+    let dir = TempDir::new().expect("temporary dir");
+    let trace = dir.path().join("trace.har");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("trace-token".into())))
+        .base_url(server.url())
+        .cache_dir(dir.path().join("cache"))
+        .retry_base_delay(Duration::from_millis(1))
+        .trace_http(Some(trace.clone()))
+        .build()
+        .expect("client initialises");
+
+    for _ in 0..2 {
+        let user: Value = client.get_current_user().await.expect("request succeeds");
+        assert_eq!(user["data"]["name"], "Traced");
+    }
+
+    let har: Value =
+        serde_json::from_slice(&std::fs::read(&trace).expect("trace written")).expect("valid JSON");
+    assert_eq!(har["log"]["version"], "1.2");
+    let entries = har["log"]["entries"].as_array().expect("entries");
+    let statuses: Vec<_> = entries
+        .iter()
+        .map(|entry| entry["response"]["status"].as_u64())
+        .collect();
+    assert_eq!(statuses, [Some(503), Some(200), Some(200)]);
+    assert_eq!(
+        entries[1]["response"]["content"]["text"]
+            .as_str()
+            .map(|t| t.contains("Traced")),
+        Some(true)
+    );
+    assert!(entries[1].get("_fromCache").is_none());
+    assert_eq!(entries[2]["_fromCache"], "cache");
+    assert!(entries[0]["timings"]["wait"].as_f64().is_some());
+
+    let raw = std::fs::read_to_string(&trace).expect("trace readable");
+    assert!(!raw.contains("trace-token"), "token leaked into trace");
+    assert!(raw.contains("Bearer [REDACTED]"));
+}
+
 #[tokio::test]
 async fn optional_live_smoke_test() {
     let token = match std::env::var("ASANA_CLI_TEST_TOKEN") {
@@ -195,3 +683,35 @@ async fn optional_live_smoke_test() {
         "live workspace listing should return at least one workspace"
     );
 }
+
+#[tokio::test]
+async fn api_client_serves_the_asana_api_trait() {
+    let mut server = Server::new_async().await;
+    let _me = server
+        .mock("GET", "/users/me")
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "42", "name": "Ada" } }"#)
+        .create();
+    let _missing = server
+        .mock("GET", "/tasks/404")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_body(r#"{ "errors": [ { "message": "task: Not Found" } ] }"#)
+        .create();
+
+    let cache = TempDir::new().expect("temporary cache dir");
+    let client = ApiClient::builder(AuthToken::new(SecretString::new("trait-token".into())))
+        .base_url(server.url())
+        .cache_dir(cache.path().join("cache"))
+        .build()
+        .expect("client initialises");
+    let api: &dyn AsanaApi = &client;
+
+    let user = api.get_current_user().await.expect("current user");
+    assert_eq!((user.gid.as_str(), user.name.as_str()), ("42", "Ada"));
+    let err = api
+        .get_task("404", Vec::new())
+        .await
+        .expect_err("missing task");
+    assert_eq!(err.status().map(|status| status.as_u16()), Some(404));
+}
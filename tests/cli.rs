@@ -120,6 +120,22 @@ fn doctor_runs_with_isolated_home() {
     );
 }
 
+#[test]
+fn doctor_reports_unreadable_ca_bundle() {
+    let temp_home = TempDir::new().expect("temp dir");
+    let output = Command::new(bin_path())
+        .args(["doctor", "--ca-bundle", "/nonexistent/corp-ca.pem"])
+        .env("HOME", temp_home.path())
+        .output()
+        .expect("failed to execute doctor");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "doctor should fail: {stdout}");
+    assert!(
+        stdout.contains("failed to read CA bundle /nonexistent/corp-ca.pem"),
+        "unexpected output: {stdout}"
+    );
+}
+
 #[test]
 fn config_set_token_and_get_reports_stored_status() {
     let config_home = TempDir::new().expect("config home");
@@ -221,6 +237,124 @@ fn config_test_validates_token_against_mock_api() {
     }
 }
 
+#[cfg(unix)]
+#[test]
+fn token_command_supplies_the_token() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    {
+        let mut server = Server::new();
+        let _m = server
+            .mock("GET", "/users/me")
+            .match_header("authorization", "Bearer helper-token")
+            .with_status(200)
+            .with_body(r#"{ "data": { "name": "Helper User" } }"#)
+            .create();
+
+        let envs = standard_env(&config_home, &data_home, &server.url());
+        for args in [
+            &["config", "set", "token", "--token", "stored-token"][..],
+            &[
+                "config",
+                "set",
+                "token-command",
+                "--command",
+                "echo helper-token",
+            ][..],
+        ] {
+            let output = run_command_with_env(args, &envs);
+            assert!(
+                output.status.success(),
+                "{args:?} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let get_output = run_command_with_env(&["config", "get"], &envs);
+        let stdout = String::from_utf8_lossy(&get_output.stdout);
+        assert!(
+            stdout.contains("Token source: token_command `echo helper-token`"),
+            "unexpected config get output: {stdout}"
+        );
+
+        let test_output = run_command_with_env(&["config", "test"], &envs);
+        assert!(
+            test_output.status.success(),
+            "config test failed: {}",
+            String::from_utf8_lossy(&test_output.stderr)
+        );
+        let stdout = String::from_utf8_lossy(&test_output.stdout);
+        assert!(stdout.contains("validated for Helper User"));
+    }
+}
+
+#[test]
+fn profiles_select_separate_credentials() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    {
+        let mut server = Server::new();
+        let _m = server
+            .mock("GET", "/users/me")
+            .match_header("authorization", "Bearer client-token")
+            .with_status(200)
+            .with_body(r#"{ "data": { "name": "Client User" } }"#)
+            .create();
+
+        let envs = standard_env(&config_home, &data_home, &server.url());
+        for args in [
+            &["config", "set", "token", "--token", "personal-token"][..],
+            &["config", "profile", "copy", "default", "client"][..],
+            &[
+                "--profile",
+                "client",
+                "config",
+                "set",
+                "token",
+                "--token",
+                "client-token",
+            ][..],
+            &["config", "profile", "use", "client"][..],
+        ] {
+            let output = run_command_with_env(args, &envs);
+            assert!(
+                output.status.success(),
+                "{args:?} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let list = run_command_with_env(&["config", "profile", "list"], &envs);
+        let stdout = String::from_utf8_lossy(&list.stdout);
+        assert!(stdout.contains("  default"), "unexpected list: {stdout}");
+        assert!(stdout.contains("* client"), "unexpected list: {stdout}");
+
+        let test_output = run_command_with_env(&["config", "test"], &envs);
+        assert!(
+            test_output.status.success(),
+            "config test failed: {}",
+            String::from_utf8_lossy(&test_output.stderr)
+        );
+        assert!(data_home.path().join("profiles/client/cache").is_dir());
+
+        let mut default_envs = envs.clone();
+        default_envs.push(("ASANA_PROFILE", "default".to_string()));
+        let get_output = run_command_with_env(&["config", "get"], &default_envs);
+        let stdout = String::from_utf8_lossy(&get_output.stdout);
+        assert!(
+            stdout.contains("Profile: default"),
+            "unexpected config get output: {stdout}"
+        );
+        let rejected = run_command_with_env(&["config", "test"], &default_envs);
+        assert!(
+            !rejected.status.success(),
+            "default profile uses another token"
+        );
+    }
+}
+
 #[test]
 fn project_list_outputs_json() {
     let config_home = TempDir::new().expect("config home");
@@ -847,15 +981,40 @@ fn task_create_batch_processes_json() {
     {
         let mut server = Server::new();
         let create_mock = server
-            .mock("POST", "/tasks")
+            .mock("POST", "/batch")
             .match_header("authorization", "Bearer task-token")
+            .match_body(Matcher::PartialJson(json!({
+                "data": {
+                    "actions": [
+                        {
+                            "method": "post",
+                            "relative_path": "/tasks",
+                            "data": { "name": "Batch A", "workspace": "ws-123" }
+                        },
+                        {
+                            "method": "post",
+                            "relative_path": "/tasks",
+                            "data": { "name": "Batch B", "projects": ["P1"] }
+                        }
+                    ]
+                }
+            })))
             .with_status(200)
             .with_body(
                 r#"{
-                    "data": { "gid": "T100", "name": "Batch A", "completed": false }
+                    "data": [
+                        {
+                            "status_code": 201,
+                            "body": { "data": { "gid": "T100", "name": "Batch A", "completed": false } }
+                        },
+                        {
+                            "status_code": 201,
+                            "body": { "data": { "gid": "T101", "name": "Batch B", "completed": false } }
+                        }
+                    ]
                 }"#,
             )
-            .expect(2)
+            .expect(1)
             .create();
 
         let envs = standard_env(&config_home, &data_home, &server.url());
@@ -895,6 +1054,80 @@ fn task_create_batch_processes_json() {
     }
 }
 
+#[test]
+fn task_create_batch_continue_on_error_skips_failed_actions() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    {
+        let mut server = Server::new();
+        let batch_mock = server
+            .mock("POST", "/batch")
+            .match_header("authorization", "Bearer task-token")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "data": [
+                        {
+                            "status_code": 400,
+                            "body": { "errors": [ { "message": "workspace: Not a recognized ID" } ] }
+                        },
+                        {
+                            "status_code": 201,
+                            "body": { "data": { "gid": "T201", "name": "Batch OK", "completed": false } }
+                        }
+                    ]
+                }"#,
+            )
+            .expect(2)
+            .create();
+
+        let envs = standard_env(&config_home, &data_home, &server.url());
+        let set_output =
+            run_command_with_env(&["config", "set", "token", "--token", "task-token"], &envs);
+        assert!(set_output.status.success());
+
+        let batch_path = data_home.path().join("create.json");
+        fs::write(
+            &batch_path,
+            r#"[
+                { "name": "Batch Bad", "workspace": "bogus" },
+                { "name": "Batch OK", "workspace": "ws-123" }
+            ]"#,
+        )
+        .expect("write batch file");
+        let file = batch_path.to_str().unwrap();
+
+        let strict = run_command_with_env(
+            &["task", "create-batch", "--file", file, "--output", "json"],
+            &envs,
+        );
+        assert!(!strict.status.success());
+
+        let lenient = run_command_with_env(
+            &[
+                "task",
+                "create-batch",
+                "--file",
+                file,
+                "--continue-on-error",
+                "--output",
+                "json",
+            ],
+            &envs,
+        );
+        assert!(
+            lenient.status.success(),
+            "create-batch failed: {}",
+            String::from_utf8_lossy(&lenient.stderr)
+        );
+        let stdout = String::from_utf8_lossy(&lenient.stdout);
+        assert!(stdout.contains("T201"));
+        assert!(!stdout.contains("Batch Bad"));
+        batch_mock.assert();
+    }
+}
+
 #[test]
 fn task_update_batch_processes_json() {
     let config_home = TempDir::new().expect("config home");
@@ -903,15 +1136,28 @@ fn task_update_batch_processes_json() {
     {
         let mut server = Server::new();
         let update_mock = server
-            .mock("PUT", "/tasks/T1")
+            .mock("POST", "/batch")
             .match_header("authorization", "Bearer task-token")
             .match_body(Matcher::PartialJson(json!({
-                "data": { "name": "Updated Task" }
+                "data": {
+                    "actions": [
+                        {
+                            "method": "put",
+                            "relative_path": "/tasks/T1",
+                            "data": { "name": "Updated Task" }
+                        }
+                    ]
+                }
             })))
             .with_status(200)
             .with_body(
                 r#"{
-                    "data": { "gid": "T1", "name": "Updated Task", "completed": false }
+                    "data": [
+                        {
+                            "status_code": 200,
+                            "body": { "data": { "gid": "T1", "name": "Updated Task", "completed": false } }
+                        }
+                    ]
                 }"#,
             )
             .create();
@@ -965,15 +1211,28 @@ fn task_complete_batch_marks_tasks() {
     {
         let mut server = Server::new();
         let complete_mock = server
-            .mock("PUT", "/tasks/T9")
+            .mock("POST", "/batch")
             .match_header("authorization", "Bearer task-token")
             .match_body(Matcher::PartialJson(json!({
-                "data": { "completed": true }
+                "data": {
+                    "actions": [
+                        {
+                            "method": "put",
+                            "relative_path": "/tasks/T9",
+                            "data": { "completed": true }
+                        }
+                    ]
+                }
             })))
             .with_status(200)
             .with_body(
                 r#"{
-                    "data": { "gid": "T9", "name": "Batch Complete", "completed": true }
+                    "data": [
+                        {
+                            "status_code": 200,
+                            "body": { "data": { "gid": "T9", "name": "Batch Complete", "completed": true } }
+                        }
+                    ]
                 }"#,
             )
             .create();
@@ -1134,3 +1393,855 @@ fn task_search_recent_only_uses_cache() {
         assert!(stdout.contains("Recent Task"));
     }
 }
+
+/// Every `--output` format of `project list` and `task list` is answered
+/// from what `cache warm` stored.
+fn assert_warmed_lists_offline(envs: &[(&str, String)]) {
+    for (resource, expected) in [("project", "Cached Project"), ("task", "Cached Task")] {
+        for format in ["table", "csv", "markdown", "json"] {
+            let offline = run_command_with_env(
+                &[
+                    "--offline",
+                    resource,
+                    "list",
+                    "--workspace",
+                    "1001",
+                    "--output",
+                    format,
+                ],
+                envs,
+            );
+            assert!(
+                offline.status.success(),
+                "offline {resource} list --output {format} failed: {}",
+                String::from_utf8_lossy(&offline.stderr)
+            );
+            assert!(String::from_utf8_lossy(&offline.stdout).contains(expected));
+        }
+    }
+}
+
+#[test]
+fn cache_warm_enables_offline_commands() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let empty = r#"{ "data": [] }"#;
+    let _me = server
+        .mock("GET", "/users/me")
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "U1", "name": "Warm User" } }"#)
+        .create();
+    let _workspaces = server
+        .mock("GET", "/workspaces")
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "1001", "name": "Engineering" }] }"#)
+        .create();
+    let _projects = server
+        .mock("GET", "/projects")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "P1", "name": "Cached Project" }] }"#)
+        .create();
+    let _tasks = server
+        .mock("GET", "/tasks")
+        .match_query(Matcher::UrlEncoded("assignee".into(), "me".into()))
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "K1", "name": "Cached Task" }] }"#)
+        .create();
+    let _users = server
+        .mock("GET", "/workspaces/1001/users")
+        .with_status(200)
+        .with_body(empty)
+        .create();
+    let _tags = server
+        .mock("GET", "/workspaces/1001/tags")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "T1", "name": "Cached Tag" }] }"#)
+        .create();
+    let _fields = server
+        .mock("GET", "/workspaces/1001/custom_fields")
+        .with_status(402)
+        .with_body(r#"{ "errors": [{ "message": "premium only" }] }"#)
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "task-token"], &envs);
+    assert!(set_output.status.success());
+
+    let warm = run_command_with_env(&["cache", "warm"], &envs);
+    assert!(
+        warm.status.success(),
+        "cache warm failed: {}",
+        String::from_utf8_lossy(&warm.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&warm.stdout);
+    assert!(stdout.contains("1 tags"), "unexpected stdout: {stdout}");
+
+    server.reset();
+    drop(server);
+
+    let offline = run_command_with_env(
+        &[
+            "--offline",
+            "tag",
+            "list",
+            "--workspace",
+            "1001",
+            "--format",
+            "json",
+        ],
+        &envs,
+    );
+    assert!(
+        offline.status.success(),
+        "offline tag list failed: {}",
+        String::from_utf8_lossy(&offline.stderr)
+    );
+    assert!(String::from_utf8_lossy(&offline.stdout).contains("Cached Tag"));
+
+    assert_warmed_lists_offline(&envs);
+
+    let stats = run_command_with_env(&["cache", "stats"], &envs);
+    let stdout = String::from_utf8_lossy(&stats.stdout);
+    assert!(
+        stdout.contains("Entries: 8 (0 expired)"),
+        "unexpected stdout: {stdout}"
+    );
+
+    let clear = run_command_with_env(&["cache", "clear"], &envs);
+    assert!(String::from_utf8_lossy(&clear.stdout).contains("Removed 8 cache entries."));
+}
+
+#[test]
+fn offline_serves_expired_entries_with_stale_warning() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let _tags = server
+        .mock("GET", "/workspaces/1001/tags")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "T1", "name": "Stale Tag" }] }"#)
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "task-token"], &envs);
+    assert!(set_output.status.success());
+
+    let fetch = run_command_with_env(
+        &["tag", "list", "--workspace", "1001", "--format", "json"],
+        &envs,
+    );
+    assert!(fetch.status.success());
+    assert!(!String::from_utf8_lossy(&fetch.stdout).contains("\"meta\""));
+
+    server.reset();
+    drop(server);
+
+    // Age the cached entry past its TTL without deleting it.
+    let cache_dir = data_home.path().join("cache");
+    let shards = fs::read_dir(&cache_dir)
+        .expect("cache dir")
+        .map(|shard| shard.expect("cache shard").path())
+        .filter(|path| path.is_dir());
+    for entry in shards.flat_map(|shard| fs::read_dir(shard).expect("shard dir")) {
+        let path = entry.expect("cache entry").path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        if name.len() != 69 {
+            continue;
+        }
+        let mut value: JsonValue =
+            serde_json::from_str(&fs::read_to_string(&path).expect("read entry")).expect("json");
+        value["expires_at"] = json!(1);
+        fs::write(&path, value.to_string()).expect("write entry");
+    }
+
+    let offline = run_command_with_env(
+        &[
+            "--offline",
+            "tag",
+            "list",
+            "--workspace",
+            "1001",
+            "--format",
+            "json",
+        ],
+        &envs,
+    );
+    assert!(
+        offline.status.success(),
+        "offline tag list failed: {}",
+        String::from_utf8_lossy(&offline.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&offline.stdout);
+    let payload: JsonValue = serde_json::from_str(&stdout).expect("json output");
+    assert_eq!(payload["meta"]["stale"], json!(true));
+    assert_eq!(payload["data"][0]["name"], json!("Stale Tag"));
+    assert!(String::from_utf8_lossy(&offline.stderr).contains("stale as of"));
+}
+
+#[test]
+fn replay_serves_recorded_fixtures_without_network() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+    let fixtures = TempDir::new().expect("fixtures");
+    let fixtures_dir = fixtures.path().to_str().expect("fixtures utf-8");
+
+    let mut server = Server::new();
+    let _tags = server
+        .mock("GET", "/workspaces/1001/tags")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "T1", "name": "Recorded Tag" }] }"#)
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "task-token"], &envs);
+    assert!(set_output.status.success());
+
+    let record = run_command_with_env(
+        &[
+            "--record",
+            fixtures_dir,
+            "tag",
+            "list",
+            "--workspace",
+            "1001",
+        ],
+        &envs,
+    );
+    assert!(
+        record.status.success(),
+        "record failed: {}",
+        String::from_utf8_lossy(&record.stderr)
+    );
+    server.reset();
+    drop(server);
+
+    for entry in fs::read_dir(fixtures.path()).expect("fixtures dir") {
+        let contents = fs::read_to_string(entry.expect("fixture").path()).expect("read fixture");
+        assert!(contents.contains("[REDACTED]"));
+        assert!(!contents.contains("task-token"));
+    }
+
+    // A fresh configuration without a token replays the same run.
+    let replay_config = TempDir::new().expect("replay config home");
+    let replay_data = TempDir::new().expect("replay data home");
+    let replay_envs = standard_env(&replay_config, &replay_data, "http://127.0.0.1:9");
+    let replay = run_command_with_env(
+        &[
+            "--replay",
+            fixtures_dir,
+            "tag",
+            "list",
+            "--workspace",
+            "1001",
+        ],
+        &replay_envs,
+    );
+    assert!(
+        replay.status.success(),
+        "replay failed: {}",
+        String::from_utf8_lossy(&replay.stderr)
+    );
+    assert!(String::from_utf8_lossy(&replay.stdout).contains("Recorded Tag"));
+
+    let unmatched = run_command_with_env(
+        &[
+            "--replay",
+            fixtures_dir,
+            "tag",
+            "list",
+            "--workspace",
+            "2002",
+        ],
+        &replay_envs,
+    );
+    assert!(!unmatched.status.success());
+    assert!(
+        String::from_utf8_lossy(&unmatched.stderr).contains("no recorded response for GET"),
+        "unexpected stderr: {}",
+        String::from_utf8_lossy(&unmatched.stderr)
+    );
+}
+
+#[test]
+fn api_errors_set_exit_codes_and_json_stderr() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let _missing = server
+        .mock("GET", "/tags/404")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_body(
+            r#"{ "errors": [{ "message": "tag: Unknown object: 404", "help": "Check the gid" }] }"#,
+        )
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output = run_command_with_env(&["config", "set", "token", "--token", "t"], &envs);
+    assert!(set_output.status.success());
+
+    let human = run_command_with_env(&["tag", "show", "404"], &envs);
+    assert_eq!(human.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&human.stderr);
+    assert!(stderr.contains("tag: Unknown object: 404"), "{stderr}");
+    assert!(stderr.contains("help: Check the gid"), "{stderr}");
+
+    let json_output = run_command_with_env(&["tag", "show", "404", "--format", "json"], &envs);
+    assert_eq!(json_output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&json_output.stderr);
+    let report: JsonValue = serde_json::from_str(stderr.trim()).expect("stderr is one JSON object");
+    assert!(json_output.stdout.is_empty());
+    assert_eq!(report["kind"], "not_found");
+    assert_eq!(report["exit_code"], 4);
+    assert_eq!(report["status"], 404);
+    assert_eq!(report["errors"][0]["help"], "Check the gid");
+}
+
+#[test]
+fn watch_persists_sync_tokens_and_resyncs() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let _initial = server
+        .mock("GET", "/events")
+        .match_query(Matcher::Exact("resource=1001".into()))
+        .with_status(412)
+        .with_body(
+            r#"{ "sync": "s1", "errors": [{ "message": "Sync token invalid or too old." }] }"#,
+        )
+        .create();
+    let _changes = server
+        .mock("GET", "/events")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("resource".into(), "1001".into()),
+            Matcher::UrlEncoded("sync".into(), "s1".into()),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"{ "data": [{
+                "action": "changed",
+                "created_at": "2026-01-02T03:04:05.000Z",
+                "resource": { "gid": "T1", "resource_type": "task", "name": "Ship it" },
+                "change": { "field": "completed", "action": "changed", "new_value": true },
+                "user": { "gid": "U1", "name": "Avery" }
+            }], "sync": "s2", "has_more": false }"#,
+        )
+        .create();
+    let _expired = server
+        .mock("GET", "/events")
+        .match_query(Matcher::UrlEncoded("sync".into(), "s2".into()))
+        .with_status(412)
+        .with_body(
+            r#"{ "sync": "s3", "errors": [{ "message": "Sync token invalid or too old." }] }"#,
+        )
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output = run_command_with_env(&["config", "set", "token", "--token", "t"], &envs);
+    assert!(set_output.status.success());
+    let token_path = data_home.path().join("sync").join("1001");
+
+    let first = run_command_with_env(&["watch", "--project", "1001", "--once"], &envs);
+    assert!(
+        first.status.success(),
+        "{}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    assert!(first.stdout.is_empty());
+    assert_eq!(fs::read_to_string(&token_path).expect("token"), "s1");
+
+    let second = run_command_with_env(
+        &["watch", "--project", "1001", "--once", "--format", "ndjson"],
+        &envs,
+    );
+    assert!(
+        second.status.success(),
+        "{}",
+        String::from_utf8_lossy(&second.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&second.stdout);
+    let event: JsonValue = serde_json::from_str(stdout.trim()).expect("ndjson event");
+    assert_eq!(event["resource"]["gid"], "T1");
+    assert_eq!(event["change"]["field"], "completed");
+    assert_eq!(fs::read_to_string(&token_path).expect("token"), "s2");
+
+    let third = run_command_with_env(&["watch", "--project", "1001", "--once"], &envs);
+    assert!(third.status.success());
+    assert!(String::from_utf8_lossy(&third.stderr).contains("expired"));
+    assert_eq!(fs::read_to_string(&token_path).expect("token"), "s3");
+
+    let escape = run_command_with_env(&["watch", "--project", "../escape", "--once"], &envs);
+    assert_eq!(escape.status.code(), Some(2));
+    assert!(!data_home.path().join("escape").exists());
+}
+
+fn post_raw(port: u16, request: &str) -> String {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    let mut stream = (0..50)
+        .find_map(|_| {
+            TcpStream::connect(("127.0.0.1", port)).ok().or_else(|| {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                None
+            })
+        })
+        .expect("webhook receiver accepts connections");
+    stream.write_all(request.as_bytes()).expect("send request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("read response");
+    response
+}
+
+fn post_handshake(port: u16, path: &str, secret: &str) -> String {
+    post_raw(
+        port,
+        &format!("POST {path} HTTP/1.1\r\nX-Hook-Secret: {secret}\r\nContent-Length: 0\r\n\r\n"),
+    )
+}
+
+fn spawn_webhook_receiver(envs: &[(&str, String)]) -> (std::process::Child, u16) {
+    use std::process::Stdio;
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port();
+    let mut command = Command::new(bin_path());
+    command
+        .args(["webhook", "serve", "--port", &port.to_string()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    for (key, value) in envs {
+        command.env(key, value);
+    }
+    (command.spawn().expect("spawn receiver"), port)
+}
+
+#[test]
+fn webhook_serve_verifies_deliveries_and_delete_forgets_secrets() {
+    use hmac::{Hmac, Mac};
+    use std::io::{BufRead, BufReader};
+
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+    let mut server = Server::new();
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output = run_command_with_env(&["config", "set", "token", "--token", "t"], &envs);
+    assert!(set_output.status.success());
+
+    let (mut child, port) = spawn_webhook_receiver(&envs);
+
+    let handshake = move |secret: &str| post_handshake(port, "/hooks/p1", secret);
+    assert!(handshake("unsolicited").starts_with("HTTP/1.1 401"));
+    // A client that never sends its request must not hold up the others.
+    let _stalled = std::net::TcpStream::connect(("127.0.0.1", port)).expect("connect");
+
+    let create = server
+        .mock("POST", "/webhooks")
+        .with_status(201)
+        .with_body_from_request(move |_| {
+            let response = handshake("s3cret");
+            assert!(response.contains("X-Hook-Secret: s3cret"), "{response}");
+            json!({ "data": { "gid": "W1", "active": true, "target": "https://example.test/hooks/p1" } })
+                .to_string()
+                .into_bytes()
+        })
+        .create();
+    let output = run_command_with_env(
+        &[
+            "webhook",
+            "create",
+            "--resource",
+            "P1",
+            "--target",
+            "https://example.test/hooks/p1",
+        ],
+        &envs,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    create.assert();
+    assert!(handshake("attacker").starts_with("HTTP/1.1 401"));
+
+    let body =
+        r#"{"events":[{"action":"changed","resource":{"gid":"T1","resource_type":"task"}}]}"#;
+    let deliver = |signature: &str| {
+        post_raw(
+            port,
+            &format!(
+                "POST /hooks/p1 HTTP/1.1\r\nX-Hook-Signature: {signature}\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+    };
+    assert!(deliver("00ff").starts_with("HTTP/1.1 401"));
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"s3cret").expect("hmac key");
+    mac.update(body.as_bytes());
+    let signature = hex::encode(mac.finalize().into_bytes());
+    assert!(deliver(&signature).starts_with("HTTP/1.1 200"));
+
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().expect("stdout"))
+        .read_line(&mut line)
+        .expect("event line");
+    child.kill().ok();
+    child.wait().ok();
+    let event: JsonValue = serde_json::from_str(&line).expect("ndjson event");
+    assert_eq!(event["resource"]["gid"], "T1");
+
+    let secrets_path = data_home.path().join("webhook-secrets.json");
+    assert!(
+        fs::read_to_string(&secrets_path)
+            .expect("secrets")
+            .contains("/hooks/p1")
+    );
+
+    let _get = server
+        .mock("GET", "/webhooks/W1")
+        .with_status(200)
+        .with_body(
+            json!({ "data": { "gid": "W1", "active": true, "target": "https://example.test/hooks/p1" } })
+                .to_string(),
+        )
+        .create();
+    let delete = server
+        .mock("DELETE", "/webhooks/W1")
+        .with_status(200)
+        .with_body(r#"{ "data": {} }"#)
+        .create();
+    let output = run_command_with_env(&["webhook", "delete", "W1", "--yes"], &envs);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    delete.assert();
+    assert!(
+        !fs::read_to_string(&secrets_path)
+            .expect("secrets")
+            .contains("/hooks/p1")
+    );
+}
+
+#[test]
+fn api_command_merges_pages_and_sends_fields() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let _first = server
+        .mock("GET", "/portfolios")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("workspace".into(), "1001".into()),
+            Matcher::UrlEncoded("limit".into(), "100".into()),
+        ]))
+        .with_status(200)
+        .with_body(
+            r#"{
+                "data": [{ "gid": "P1", "name": "Roadmap" }],
+                "next_page": { "offset": "page-2", "path": "/portfolios" }
+            }"#,
+        )
+        .expect(1)
+        .create();
+    let _second = server
+        .mock("GET", "/portfolios")
+        .match_query(Matcher::UrlEncoded("offset".into(), "page-2".into()))
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "P2", "name": "Launches" }] }"#)
+        .create();
+    let _create = server
+        .mock("POST", "/goals")
+        .match_body(Matcher::Json(
+            serde_json::json!({ "data": { "name": "Ship it", "workspace": "1001" } }),
+        ))
+        .with_status(201)
+        .with_body(r#"{ "data": { "gid": "G1", "name": "Ship it" } }"#)
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "raw-token"], &envs);
+    assert!(set_output.status.success());
+
+    let listed = run_command_with_env(
+        &[
+            "api",
+            "GET",
+            "/portfolios",
+            "-f",
+            "workspace=1001",
+            "--paginate",
+            "--select",
+            "data[*].gid",
+        ],
+        &envs,
+    );
+    assert!(
+        listed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&listed.stderr)
+    );
+    let gids: JsonValue = serde_json::from_slice(&listed.stdout).expect("selected JSON");
+    assert_eq!(gids, serde_json::json!(["P1", "P2"]));
+
+    let created = run_command_with_env(
+        &[
+            "api",
+            "post",
+            "goals",
+            "-f",
+            "name=Ship it",
+            "-f",
+            "workspace=1001",
+            "--select",
+            "data.gid",
+        ],
+        &envs,
+    );
+    assert!(
+        created.status.success(),
+        "{}",
+        String::from_utf8_lossy(&created.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&created.stdout).trim(), "G1");
+}
+
+#[test]
+fn page_size_and_offsets_resume_listings() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let _first = server
+        .mock("GET", "/workspaces/1001/tags")
+        .match_query(Matcher::UrlEncoded("limit".into(), "2".into()))
+        .with_status(200)
+        .with_body(
+            r#"{
+                "data": [{ "gid": "T1", "name": "One" }, { "gid": "T2", "name": "Two" }],
+                "next_page": { "offset": "page-2", "path": "/workspaces/1001/tags" }
+            }"#,
+        )
+        .expect(1)
+        .create();
+    let _second = server
+        .mock("GET", "/workspaces/1001/tags")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("limit".into(), "2".into()),
+            Matcher::UrlEncoded("offset".into(), "page-2".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{ "data": [{ "gid": "T3", "name": "Three" }] }"#)
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "page-token"], &envs);
+    assert!(set_output.status.success());
+
+    let first = run_command_with_env(
+        &[
+            "tag",
+            "list",
+            "--workspace",
+            "1001",
+            "--limit",
+            "2",
+            "--page-size",
+            "2",
+            "--print-next-offset",
+            "--format",
+            "json",
+        ],
+        &envs,
+    );
+    assert!(
+        first.status.success(),
+        "{}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    let tags: JsonValue = serde_json::from_slice(&first.stdout).expect("tag list JSON");
+    assert_eq!(tags.as_array().map(Vec::len), Some(2));
+    assert!(String::from_utf8_lossy(&first.stderr).contains("next_offset=page-2"));
+
+    let resumed = run_command_with_env(
+        &[
+            "tag",
+            "list",
+            "--workspace",
+            "1001",
+            "--page-size",
+            "2",
+            "--start-offset",
+            "page-2",
+            "--print-next-offset",
+            "--format",
+            "json",
+        ],
+        &envs,
+    );
+    assert!(resumed.status.success());
+    let tags: JsonValue = serde_json::from_slice(&resumed.stdout).expect("tag list JSON");
+    assert_eq!(tags[0]["gid"], "T3");
+    assert!(
+        String::from_utf8_lossy(&resumed.stderr)
+            .lines()
+            .any(|line| line == "next_offset=")
+    );
+
+    let rejected = run_command_with_env(&["tag", "list", "--page-size", "500"], &envs);
+    assert_eq!(rejected.status.code(), Some(2));
+}
+
+#[test]
+fn task_list_requests_only_rendered_fields() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    let mut server = Server::new();
+    let list_mock = server
+        .mock("GET", "/tasks")
+        .match_query(Matcher::UrlEncoded(
+            "opt_fields".into(),
+            "assignee.email,assignee.name,completed,due_on,memberships.section.name,name,\
+             projects.name"
+                .into(),
+        ))
+        .with_status(200)
+        .with_body(
+            r#"{
+                "data": [{
+                    "gid": "T1",
+                    "name": "Rendered",
+                    "completed": false,
+                    "projects": [{ "gid": "P1", "name": "Roadmap" }]
+                }]
+            }"#,
+        )
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "task-token"], &envs);
+    assert!(set_output.status.success());
+
+    let output = run_command_with_env(
+        &[
+            "task",
+            "list",
+            "--workspace",
+            "ws-123",
+            "--fields",
+            "memberships.section.name",
+            "--output",
+            "csv",
+        ],
+        &envs,
+    );
+    assert!(
+        output.status.success(),
+        "task list failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Roadmap"));
+    list_mock.assert();
+}
+
+#[test]
+fn task_create_with_external_id_updates_existing_task() {
+    let config_home = TempDir::new().expect("config home");
+    let data_home = TempDir::new().expect("data home");
+
+    // The slash must stay part of the id rather than start a new path segment.
+    let mut server = Server::new();
+    let _missing = server
+        .mock("GET", "/tasks/external:import%2F7")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_body(r#"{ "errors": [{ "message": "task: Unknown object: external:import/7" }] }"#)
+        .expect(1)
+        .create();
+    let _found = server
+        .mock("GET", "/tasks/external:import%2F7")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "T7", "name": "Imported" } }"#)
+        .create();
+    let create_mock = server
+        .mock("POST", "/tasks")
+        .match_body(Matcher::PartialJson(json!({
+            "data": { "name": "Imported", "external": { "gid": "import/7" } }
+        })))
+        .with_status(201)
+        .with_body(r#"{ "data": { "gid": "T7", "name": "Imported" } }"#)
+        .expect(1)
+        .create();
+    let update_mock = server
+        .mock("PUT", "/tasks/T7")
+        .match_body(Matcher::PartialJson(json!({
+            "data": { "name": "Imported", "notes": "second run" }
+        })))
+        .with_status(200)
+        .with_body(r#"{ "data": { "gid": "T7", "name": "Imported" } }"#)
+        .expect(1)
+        .create();
+
+    let envs = standard_env(&config_home, &data_home, &server.url());
+    let set_output =
+        run_command_with_env(&["config", "set", "token", "--token", "task-token"], &envs);
+    assert!(set_output.status.success());
+
+    let create = |notes: &str| {
+        run_command_with_env(
+            &[
+                "task",
+                "create",
+                "--name",
+                "Imported",
+                "--workspace",
+                "ws-123",
+                "--notes",
+                notes,
+                "--external-id",
+                "import/7",
+                "--output",
+                "json",
+            ],
+            &envs,
+        )
+    };
+    let first = create("first run");
+    assert!(
+        first.status.success(),
+        "{}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    let second = create("second run");
+    assert!(second.status.success());
+    assert!(String::from_utf8_lossy(&second.stderr).contains("updated T7"));
+    let payload: JsonValue = serde_json::from_slice(&second.stdout).expect("task JSON");
+    assert_eq!(payload["gid"], "T7");
+
+    create_mock.assert();
+    update_mock.assert();
+}